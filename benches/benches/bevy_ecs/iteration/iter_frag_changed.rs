@@ -0,0 +1,57 @@
+use bevy_ecs::prelude::*;
+
+macro_rules! create_entities {
+    ($world:ident; $( $variants:ident ),*) => {
+        $(
+            #[derive(Component)]
+            struct $variants(f32);
+            for _ in 0..1_000 {
+                $world.spawn().insert_bundle(($variants(0.0), Data(1.0)));
+            }
+        )*
+    };
+}
+
+#[derive(Component)]
+struct Data(f32);
+
+pub struct Benchmark<'w> {
+    world: World,
+    entities: Vec<Entity>,
+    query: QueryState<&'w Data, Changed<Data>>,
+}
+
+impl<'w> Benchmark<'w> {
+    /// Spawns 26 tables of 1000 entities each, and changes one entity in `changed_tables` of them
+    /// every run.
+    pub fn new(changed_tables: usize) -> Self {
+        let mut world = World::new();
+
+        create_entities!(world; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+
+        let entities = world
+            .query::<Entity>()
+            .iter(&world)
+            .step_by(1_000)
+            .take(changed_tables)
+            .collect();
+        let query = world.query_filtered::<&Data, Changed<Data>>();
+        Self {
+            world,
+            entities,
+            query,
+        }
+    }
+
+    pub fn run(&mut self) {
+        self.world.clear_trackers();
+        for entity in &self.entities {
+            self.world.get_mut::<Data>(*entity).unwrap().0 *= 2.0;
+        }
+        let mut sum = 0.0;
+        for data in self.query.iter(&self.world) {
+            sum += data.0;
+        }
+        assert!(sum > 0.0);
+    }
+}
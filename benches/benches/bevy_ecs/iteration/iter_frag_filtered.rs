@@ -0,0 +1,66 @@
+use bevy_ecs::prelude::*;
+
+macro_rules! create_entities {
+    ($world:ident; $( $variants:ident ),*) => {
+        $(
+            #[derive(Component)]
+            struct $variants(f32);
+            for _ in 0..20 {
+                $world.spawn().insert_bundle(($variants(0.0), Data(1.0)));
+            }
+            for _ in 0..20 {
+                $world.spawn().insert_bundle(($variants(0.0), Data(1.0), Tag));
+            }
+        )*
+    };
+}
+
+#[derive(Component)]
+struct Data(f32);
+
+#[derive(Component)]
+struct Tag;
+
+#[derive(Component)]
+struct Other;
+
+pub struct Benchmark<'w> {
+    world: World,
+    query: Option<QueryState<&'w Data, With<Tag>>>,
+}
+
+impl<'w> Benchmark<'w> {
+    /// Spawns 52 archetypes of 20 entities each plus one large archetype without `Tag`.
+    /// If `cached` is false, the query is rebuilt every run, which matches it against every
+    /// archetype again.
+    pub fn new(cached: bool) -> Self {
+        let mut world = World::new();
+
+        create_entities!(world; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+        world.spawn_batch((0..10_000).map(|_| (Data(1.0), Other)));
+
+        let query = cached.then(|| world.query_filtered::<&Data, With<Tag>>());
+        Self { world, query }
+    }
+
+    pub fn run(&mut self) {
+        let mut sum = 0.0;
+        match &mut self.query {
+            Some(query) => {
+                for data in query.iter(&self.world) {
+                    sum += data.0;
+                }
+            }
+            None => {
+                for data in self
+                    .world
+                    .query_filtered::<&Data, With<Tag>>()
+                    .iter(&self.world)
+                {
+                    sum += data.0;
+                }
+            }
+        }
+        assert_eq!(sum, 520.0);
+    }
+}
@@ -0,0 +1,46 @@
+use bevy_ecs::prelude::*;
+use glam::*;
+
+#[derive(Component, Copy, Clone)]
+struct Transform(Mat4);
+
+#[derive(Component, Copy, Clone)]
+struct Position(Vec3);
+
+#[derive(Component, Copy, Clone)]
+struct Rotation(Vec3);
+
+#[derive(Component, Copy, Clone)]
+struct Velocity(Vec3);
+
+pub struct Benchmark(World, Box<dyn System<In = (), Out = ()>>);
+
+impl Benchmark {
+    pub fn new() -> Self {
+        let mut world = World::new();
+
+        world.spawn_batch((0..10_000).map(|_| {
+            (
+                Transform(Mat4::from_scale(Vec3::ONE)),
+                Position(Vec3::X),
+                Rotation(Vec3::X),
+                Velocity(Vec3::X),
+            )
+        }));
+
+        fn query_system(mut query: Query<(&Velocity, &Rotation, &Transform, &mut Position)>) {
+            query.for_each_mut(|(velocity, rotation, transform, mut position)| {
+                position.0 += transform.0.transform_vector3(velocity.0 + rotation.0);
+            });
+        }
+
+        let mut system = IntoSystem::into_system(query_system);
+        system.initialize(&mut world);
+        system.update_archetype_component_access(&world);
+        Self(world, Box::new(system))
+    }
+
+    pub fn run(&mut self) {
+        self.1.run((), &mut self.0);
+    }
+}
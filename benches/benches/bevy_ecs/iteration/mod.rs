@@ -2,6 +2,8 @@ use criterion::*;
 
 mod heavy_compute;
 mod iter_frag;
+mod iter_frag_changed;
+mod iter_frag_filtered;
 mod iter_frag_foreach;
 mod iter_frag_foreach_sparse;
 mod iter_frag_foreach_wide;
@@ -12,6 +14,7 @@ mod iter_frag_wide_sparse;
 mod iter_simple;
 mod iter_simple_foreach;
 mod iter_simple_foreach_sparse_set;
+mod iter_simple_foreach_system;
 mod iter_simple_foreach_wide;
 mod iter_simple_foreach_wide_sparse_set;
 mod iter_simple_sparse_set;
@@ -25,6 +28,8 @@ criterion_group!(
     iterations_benches,
     iter_frag,
     iter_frag_sparse,
+    iter_frag_changed,
+    iter_frag_filtered,
     iter_simple,
     heavy_compute,
 );
@@ -57,6 +62,10 @@ fn iter_simple(c: &mut Criterion) {
         let mut bench = iter_simple_foreach::Benchmark::new();
         b.iter(move || bench.run());
     });
+    group.bench_function("foreach_system", |b| {
+        let mut bench = iter_simple_foreach_system::Benchmark::new();
+        b.iter(move || bench.run());
+    });
     group.bench_function("foreach_wide", |b| {
         let mut bench = iter_simple_foreach_wide::Benchmark::new();
         b.iter(move || bench.run());
@@ -95,6 +104,36 @@ fn iter_frag(c: &mut Criterion) {
     group.finish();
 }
 
+fn iter_frag_changed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iter_fragmented_changed");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+    group.bench_function("one_table_changed", |b| {
+        let mut bench = iter_frag_changed::Benchmark::new(1);
+        b.iter(move || bench.run());
+    });
+    group.bench_function("all_tables_changed", |b| {
+        let mut bench = iter_frag_changed::Benchmark::new(26);
+        b.iter(move || bench.run());
+    });
+    group.finish();
+}
+
+fn iter_frag_filtered(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iter_fragmented_filtered");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+    group.bench_function("cached", |b| {
+        let mut bench = iter_frag_filtered::Benchmark::new(true);
+        b.iter(move || bench.run());
+    });
+    group.bench_function("uncached", |b| {
+        let mut bench = iter_frag_filtered::Benchmark::new(false);
+        b.iter(move || bench.run());
+    });
+    group.finish();
+}
+
 fn iter_frag_sparse(c: &mut Criterion) {
     let mut group = c.benchmark_group("iter_fragmented_sparse");
     group.warm_up_time(std::time::Duration::from_millis(500));
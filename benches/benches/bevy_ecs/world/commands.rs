@@ -1,16 +1,18 @@
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    system::{Command, CommandQueue, Commands},
+    query::Without,
+    schedule::{Stage, SystemStage},
+    system::{Command, CommandQueue, Commands, Query},
     world::World,
 };
 use criterion::{black_box, Criterion};
 
-#[derive(Component)]
+#[derive(Default, Component)]
 struct A;
-#[derive(Component)]
+#[derive(Default, Component)]
 struct B;
-#[derive(Component)]
+#[derive(Default, Component)]
 struct C;
 
 pub fn empty_commands(criterion: &mut Criterion) {
@@ -124,6 +126,94 @@ pub fn insert_commands(criterion: &mut Criterion) {
     group.finish();
 }
 
+#[derive(Default, Component)]
+struct D(u64);
+#[derive(Default, Component)]
+struct E(u64);
+
+/// Inserts five components onto each entity, either from five command queues applied one after
+/// the other, from five systems of a stage that each insert one component, or as a single bundle.
+/// The commands of the systems of a stage coalesce their inserts, so the last two cases do one
+/// archetype move per entity, and the first one five.
+pub fn insert_commands_across_systems(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("insert_commands_across_systems");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+
+    let entity_count = 10_000;
+    group.bench_function("five_queues", |bencher| {
+        let mut world = World::default();
+        let mut command_queues: Vec<CommandQueue> =
+            (0..5).map(|_| CommandQueue::default()).collect();
+
+        bencher.iter(|| {
+            let entities: Vec<Entity> = (0..entity_count).map(|_| world.spawn().id()).collect();
+            for (i, command_queue) in command_queues.iter_mut().enumerate() {
+                let mut commands = Commands::new(command_queue, &world);
+                for entity in entities.iter() {
+                    let mut entity = commands.entity(*entity);
+                    match i {
+                        0 => entity.insert(A),
+                        1 => entity.insert(B),
+                        2 => entity.insert(C),
+                        3 => entity.insert(D(0)),
+                        _ => entity.insert(E(0)),
+                    };
+                }
+            }
+            for command_queue in command_queues.iter_mut() {
+                command_queue.apply(&mut world);
+            }
+            world.clear_entities();
+        });
+    });
+    group.bench_function("five_systems", |bencher| {
+        fn insert<T: Component + Default>(
+            mut commands: Commands,
+            query: Query<Entity, Without<T>>,
+        ) {
+            for entity in query.iter() {
+                commands.entity(entity).insert(T::default());
+            }
+        }
+
+        let mut world = World::default();
+        let mut stage = SystemStage::parallel()
+            .with_system(insert::<A>)
+            .with_system(insert::<B>)
+            .with_system(insert::<C>)
+            .with_system(insert::<D>)
+            .with_system(insert::<E>);
+
+        bencher.iter(|| {
+            for _ in 0..entity_count {
+                world.spawn();
+            }
+            stage.run(&mut world);
+            world.clear_entities();
+        });
+    });
+    group.bench_function("one_bundle", |bencher| {
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+
+        bencher.iter(|| {
+            let entities: Vec<Entity> = (0..entity_count).map(|_| world.spawn().id()).collect();
+            let mut commands = Commands::new(&mut command_queue, &world);
+            for entity in entities.iter() {
+                commands
+                    .entity(*entity)
+                    .insert_bundle((A, B, C, D(0), E(0)));
+            }
+            drop(commands);
+            command_queue.apply(&mut world);
+            world.clear_entities();
+        });
+    });
+
+    group.finish();
+}
+
 struct FakeCommandA;
 struct FakeCommandB(u64);
 
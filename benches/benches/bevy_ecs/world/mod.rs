@@ -11,6 +11,7 @@ criterion_group!(
     empty_commands,
     spawn_commands,
     insert_commands,
+    insert_commands_across_systems,
     fake_commands,
     zero_sized_commands,
     medium_sized_commands,
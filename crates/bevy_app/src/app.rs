@@ -1,13 +1,18 @@
-use crate::{CoreStage, Plugin, PluginGroup, PluginGroupBuilder, StartupSchedule, StartupStage};
+use crate::{
+    ClearTrackers, CoreStage, Plugin, PluginGroup, PluginGroupBuilder, SchedulePaused,
+    StartupSchedule, StartupStage,
+};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
+    component::Component,
     event::{Event, Events},
-    prelude::{FromWorld, IntoExclusiveSystem},
+    prelude::{ExclusiveSystemDescriptorCoercion, FromWorld, IntoExclusiveSystem},
     schedule::{
-        IntoSystemDescriptor, Schedule, ShouldRun, Stage, StageLabel, State, StateData, SystemSet,
-        SystemStage,
+        ExecutorConfig, IntoSystemDescriptor, Schedule, ShouldRun, Stage, StageLabel, State,
+        StateData, SystemSet, SystemStage,
     },
-    system::Resource,
+    system::{CommandBudget, IntoSystem, Resource},
+    tracked::update_previous,
     world::World,
 };
 use bevy_utils::{tracing::debug, HashMap};
@@ -57,6 +62,8 @@ pub struct App {
     /// A container of [`Stage`]s set to be run in a linear order.
     pub schedule: Schedule,
     sub_apps: HashMap<Box<dyn AppLabel>, SubApp>,
+    /// The stage of the [shutdown systems](Self::add_shutdown_system), until it has run.
+    shutdown: Option<SystemStage>,
 }
 
 /// Each `SubApp` has its own [`Schedule`] and [`World`], enabling a separation of concerns.
@@ -73,7 +80,16 @@ impl Default for App {
 
         app.add_default_stages()
             .add_event::<AppExit>()
-            .add_system_to_stage(CoreStage::Last, World::clear_trackers.exclusive_system());
+            .add_system_to_stage(
+                CoreStage::Last,
+                World::clear_trackers
+                    .exclusive_system()
+                    .label(ClearTrackers),
+            )
+            .add_system_to_stage(
+                CoreStage::First,
+                CommandBudget::apply_backlog_system.exclusive_system(),
+            );
 
         #[cfg(feature = "bevy_ci_testing")]
         {
@@ -100,15 +116,30 @@ impl App {
             schedule: Default::default(),
             runner: Box::new(run_once),
             sub_apps: HashMap::default(),
+            shutdown: Some(SystemStage::single_threaded()),
         }
     }
 
     /// Advances the execution of the [`Schedule`] by one cycle.
     ///
-    /// This method also updates sub apps.
+    /// This method also updates sub apps. It does nothing while the app is paused, see
+    /// [`App::pause`]. The thread count requested with
+    /// [`ExecutorConfig::set_worker_threads`] is applied before the update.
     ///
     /// See [`add_sub_app`](Self::add_sub_app) and [`run_once`](Schedule::run_once) for more details.
     pub fn update(&mut self) {
+        let paused = self
+            .world
+            .get_resource::<SchedulePaused>()
+            .is_some_and(SchedulePaused::is_paused);
+        if paused && !self.world.resource_mut::<SchedulePaused>().take_step() {
+            return;
+        }
+        if let Some(mut config) = self.world.get_resource_mut::<ExecutorConfig>() {
+            if config.pending_worker_threads().is_some() {
+                config.apply();
+            }
+        }
         #[cfg(feature = "trace")]
         let _bevy_frame_update_span = info_span!("frame").entered();
         self.schedule.run(&mut self.world);
@@ -117,6 +148,34 @@ impl App {
         }
     }
 
+    /// Pauses the app: [`App::update`] then does nothing until [`App::resume`] is called, except
+    /// for the single updates requested with [`App::step`].
+    ///
+    /// See [`SchedulePaused`] for more details.
+    pub fn pause(&mut self) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(SchedulePaused::default)
+            .pause();
+        self
+    }
+
+    /// Resumes the app after [`App::pause`].
+    pub fn resume(&mut self) -> &mut Self {
+        if let Some(mut paused) = self.world.get_resource_mut::<SchedulePaused>() {
+            paused.resume();
+        }
+        self
+    }
+
+    /// Lets the next [`App::update`] of the paused app run entirely, before the app is paused
+    /// again. It has no effect if the app isn't paused.
+    pub fn step(&mut self) -> &mut Self {
+        if let Some(mut paused) = self.world.get_resource_mut::<SchedulePaused>() {
+            paused.step();
+        }
+        self
+    }
+
     /// Starts the application by calling the app's [runner function](Self::set_runner).
     ///
     /// Finalizes the [`App`] configuration. For general usage, see the example on the item
@@ -377,6 +436,102 @@ impl App {
         self
     }
 
+    /// Adds a debug-only system to the [update stage](Self::add_default_stages).
+    ///
+    /// See [`add_debug_system_to_stage`](Self::add_debug_system_to_stage).
+    pub fn add_debug_system<Params>(
+        &mut self,
+        system: impl IntoSystemDescriptor<Params>,
+    ) -> &mut Self {
+        self.add_debug_system_to_stage(CoreStage::Update, system)
+    }
+
+    /// Adds a debug-only system to the [`Stage`] identified by `stage_label`.
+    ///
+    /// The system is only registered when the `debug_systems` feature is enabled. Otherwise it is
+    /// dropped right away: it is never initialized, so neither are the resources it uses, and it
+    /// costs nothing at runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # let mut app = App::new();
+    /// fn count_entities(query: Query<Entity>) {
+    ///     println!("{} entities", query.iter().count());
+    /// }
+    ///
+    /// app.add_debug_system_to_stage(CoreStage::Last, count_entities);
+    /// ```
+    pub fn add_debug_system_to_stage<Params>(
+        &mut self,
+        stage_label: impl StageLabel,
+        system: impl IntoSystemDescriptor<Params>,
+    ) -> &mut Self {
+        #[cfg(feature = "debug_systems")]
+        self.add_system_to_stage(stage_label, system);
+        #[cfg(not(feature = "debug_systems"))]
+        let _ = (stage_label, system);
+        self
+    }
+
+    /// Adds a finalizer to the [`Stage`] identified by `stage_label`: a system that runs once at
+    /// the end of each run of the stage, after every other system and their commands.
+    ///
+    /// Finalizers ignore ordering constraints and run in registration order, which suits
+    /// per-stage cleanup. See [`SystemStage::add_stage_finalizer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # let mut app = App::new();
+    /// # fn reset_frame_counters() {}
+    /// #
+    /// app.add_stage_finalizer(CoreStage::Update, reset_frame_counters);
+    /// ```
+    pub fn add_stage_finalizer<Params>(
+        &mut self,
+        stage_label: impl StageLabel,
+        system: impl IntoSystem<(), (), Params>,
+    ) -> &mut Self {
+        self.schedule.add_stage_finalizer(stage_label, system);
+        self
+    }
+
+    /// Adds a system built by `build_system` to each [`Stage`] identified by `stage_labels`.
+    ///
+    /// `build_system` is called once per stage, so the same system, along with its labels,
+    /// ordering constraints and run criteria, can be added to several stages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # let mut app = App::new();
+    /// # fn handle_input() {}
+    /// #
+    /// app.add_system_to_stages([CoreStage::PreUpdate, CoreStage::PostUpdate], || {
+    ///     handle_input.label("input")
+    /// });
+    /// ```
+    pub fn add_system_to_stages<Params, S: IntoSystemDescriptor<Params>>(
+        &mut self,
+        stage_labels: impl IntoIterator<Item = impl StageLabel>,
+        build_system: impl Fn() -> S,
+    ) -> &mut Self {
+        for stage_label in stage_labels {
+            self.add_system_to_stage(stage_label, build_system());
+        }
+        self
+    }
+
     /// Adds a [`SystemSet`] to the [`Stage`] identified by `stage_label`.
     ///
     /// # Examples
@@ -462,6 +617,61 @@ impl App {
         self.add_startup_system_set_to_stage(StartupStage::Startup, system_set)
     }
 
+    /// Adds a system that runs once when the app shuts down, to tear down resources such as
+    /// files, sockets or GPU buffers.
+    ///
+    /// Shutdown systems run on a single thread, with the commands of each system applied before
+    /// the next one runs, so they can be ordered with labels to drop resources in a defined
+    /// order. See [`App::shutdown`] for when they run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// struct LogFile(std::fs::File);
+    ///
+    /// fn close_log(mut commands: Commands) {
+    ///     commands.remove_resource::<LogFile>();
+    /// }
+    ///
+    /// App::new()
+    ///     .add_shutdown_system(close_log);
+    /// ```
+    pub fn add_shutdown_system<Params>(
+        &mut self,
+        system: impl IntoSystemDescriptor<Params>,
+    ) -> &mut Self {
+        if let Some(stage) = &mut self.shutdown {
+            stage.add_system(system);
+        }
+        self
+    }
+
+    /// Adds a [`SystemSet`] of [shutdown systems](Self::add_shutdown_system).
+    pub fn add_shutdown_system_set(&mut self, system_set: SystemSet) -> &mut Self {
+        if let Some(stage) = &mut self.shutdown {
+            stage.add_system_set(system_set);
+        }
+        self
+    }
+
+    /// Runs the [shutdown systems](Self::add_shutdown_system) of this app, then those of its sub
+    /// apps. Only the first call does anything, so they run exactly once.
+    ///
+    /// Runners call this when the app loop exits. Dropping an [`App`] doesn't run its shutdown
+    /// systems, so an app that is updated by hand, or whose loop stops because of a panic, must
+    /// call this itself if they should run.
+    pub fn shutdown(&mut self) {
+        if let Some(mut stage) = self.shutdown.take() {
+            stage.run(&mut self.world);
+        }
+        for sub_app in self.sub_apps.values_mut() {
+            sub_app.app.shutdown();
+        }
+    }
+
     /// Adds a system to the [startup schedule](Self::add_default_stages), in the stage
     /// identified by `stage_label`.
     ///
@@ -631,6 +841,36 @@ impl App {
         self
     }
 
+    /// Keeps the value each `T` component had at the end of the previous frame in a
+    /// [`Previous<T>`](bevy_ecs::tracked::Previous) component, by adding [`update_previous::<T>`] to [`CoreStage::First`].
+    ///
+    /// Systems can then query `&Previous<T>` to read the previous frame while others write the
+    /// current `T`, such as for render extraction or interpolation. This should be called once per
+    /// component type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{prelude::*, tracked::Previous};
+    /// #
+    /// #[derive(Component, Clone)]
+    /// struct Position(f32);
+    ///
+    /// fn interpolate(query: Query<(&Position, &Previous<Position>)>) {
+    ///     for (position, previous) in query.iter() {
+    ///         let halfway = (position.0 + previous.0) / 2.0;
+    ///     }
+    /// }
+    ///
+    /// App::new()
+    ///     .register_double_buffered::<Position>()
+    ///     .add_system(interpolate);
+    /// ```
+    pub fn register_double_buffered<T: Component + Clone>(&mut self) -> &mut Self {
+        self.add_system_to_stage(CoreStage::First, update_previous::<T>)
+    }
+
     /// Inserts a [`Resource`] to the current [`App`] and overwrites any [`Resource`] previously added of the same type.
     ///
     /// A [`Resource`] in Bevy represents globally unique data. [`Resource`]s must be added to Bevy apps
@@ -933,6 +1173,7 @@ impl App {
 
 fn run_once(mut app: App) {
     app.update();
+    app.shutdown();
 }
 
 /// An event that indicates the [`App`] should exit. This will fully exit the app process at the
@@ -943,3 +1184,120 @@ fn run_once(mut app: App) {
 /// frame is over.
 #[derive(Debug, Clone, Default)]
 pub struct AppExit;
+
+#[cfg(test)]
+mod tests {
+    use crate::{App, AppExit, ScheduleRunnerPlugin, ScheduleRunnerSettings};
+    use bevy_ecs::{
+        event::EventWriter,
+        system::{Res, ResMut},
+    };
+    use bevy_utils::Duration;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Default)]
+    struct Counter(usize);
+
+    fn count(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    #[cfg(feature = "debug_systems")]
+    #[test]
+    fn debug_system_runs() {
+        let mut app = App::new();
+        app.init_resource::<Counter>().add_debug_system(count);
+        app.update();
+        assert_eq!(app.world.resource::<Counter>().0, 1);
+    }
+
+    #[cfg(not(feature = "debug_systems"))]
+    #[test]
+    fn debug_system_is_absent() {
+        let mut app = App::new();
+        // `count` would panic if it ran, since there is no `Counter`.
+        app.add_debug_system(count);
+        app.update();
+        assert!(!app.world.contains_resource::<Counter>());
+    }
+
+    #[test]
+    fn pause_and_step() {
+        let mut app = App::new();
+        app.init_resource::<Counter>().add_system(count);
+        app.update();
+        assert_eq!(app.world.resource::<Counter>().0, 1);
+
+        app.pause();
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<Counter>().0, 1);
+
+        // A step runs exactly one update.
+        app.step();
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<Counter>().0, 2);
+
+        app.resume();
+        // Steps only apply to paused apps.
+        app.step();
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<Counter>().0, 4);
+        app.pause();
+        app.update();
+        assert_eq!(app.world.resource::<Counter>().0, 4);
+    }
+
+    #[test]
+    fn shutdown_system_runs_once_on_exit() {
+        #[derive(Default)]
+        struct Shutdowns(Arc<AtomicUsize>);
+
+        fn exit_after_three_frames(counter: Res<Counter>, mut exit: EventWriter<AppExit>) {
+            if counter.0 == 3 {
+                exit.send(AppExit);
+            }
+        }
+
+        fn shut_down(shutdowns: Res<Shutdowns>) {
+            shutdowns.0.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let shutdowns = Shutdowns::default();
+        let runs = shutdowns.0.clone();
+        let mut app = App::new();
+        app.insert_resource(ScheduleRunnerSettings::run_loop(Duration::ZERO))
+            .add_plugin(ScheduleRunnerPlugin)
+            .init_resource::<Counter>()
+            .insert_resource(shutdowns)
+            .add_system(count)
+            .add_system(exit_after_three_frames)
+            .add_shutdown_system(shut_down);
+        app.run();
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+        // Shutting down again doesn't run the systems twice.
+        let shutdowns = Shutdowns::default();
+        let runs = shutdowns.0.clone();
+        let mut app = App::new();
+        app.insert_resource(shutdowns)
+            .add_shutdown_system(shut_down);
+        app.shutdown();
+        app.shutdown();
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+        // Only runners shut the app down, dropping it doesn't.
+        let shutdowns = Shutdowns::default();
+        let runs = shutdowns.0.clone();
+        let mut app = App::new();
+        app.insert_resource(shutdowns)
+            .add_shutdown_system(shut_down);
+        drop(app);
+        assert_eq!(runs.load(Ordering::Relaxed), 0);
+    }
+}
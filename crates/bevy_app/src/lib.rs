@@ -3,6 +3,7 @@
 #![warn(missing_docs)]
 
 mod app;
+mod pause;
 mod plugin;
 mod plugin_group;
 mod schedule_runner;
@@ -12,6 +13,7 @@ mod ci_testing;
 
 pub use app::*;
 pub use bevy_derive::DynamicPlugin;
+pub use pause::*;
 pub use plugin::*;
 pub use plugin_group::*;
 pub use schedule_runner::*;
@@ -24,7 +26,16 @@ pub mod prelude {
     };
 }
 
-use bevy_ecs::schedule::StageLabel;
+use bevy_ecs::schedule::{StageLabel, SystemLabel};
+
+/// The label of the system that clears the change trackers of the world, with
+/// [`World::clear_trackers`](bevy_ecs::world::World::clear_trackers), at the start of
+/// [`CoreStage::Last`].
+///
+/// Exclusive systems at the start of [`CoreStage::Last`] that read the components removed during
+/// the whole frame should run before it.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub struct ClearTrackers;
 
 /// The names of the default [`App`] stages.
 ///
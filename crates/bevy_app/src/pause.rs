@@ -0,0 +1,50 @@
+/// A resource that pauses the whole [`App`](crate::App) while it is paused, for debugging.
+///
+/// While paused, [`App::update`](crate::App::update) runs nothing: no stage, no command flush and
+/// no sub app. Calling [`SchedulePaused::step`] then lets exactly one full update run before the
+/// app is paused again, which advances the app one frame at a time.
+///
+/// Unlike run criteria, which are evaluated per stage or per system, this gates the whole update
+/// at once, so a frame either runs entirely or not at all.
+///
+/// The resource is usually handled through [`App::pause`](crate::App::pause),
+/// [`App::resume`](crate::App::resume) and [`App::step`](crate::App::step), but systems can also
+/// pause the app by changing it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SchedulePaused {
+    paused: bool,
+    step: bool,
+}
+
+impl SchedulePaused {
+    /// Returns `true` if the app is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pauses the app. A step that was requested but hasn't run yet is kept.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes the app, which then updates normally.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.step = false;
+    }
+
+    /// Requests a single update of the paused app. It has no effect if the app isn't paused.
+    pub fn step(&mut self) {
+        self.step = self.paused;
+    }
+
+    /// Returns `true` if a step was requested and hasn't run yet.
+    pub fn is_step_requested(&self) -> bool {
+        self.step
+    }
+
+    /// Returns `true` if a step was requested, and consumes it.
+    pub(crate) fn take_step(&mut self) -> bool {
+        std::mem::take(&mut self.step)
+    }
+}
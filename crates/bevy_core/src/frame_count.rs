@@ -0,0 +1,52 @@
+use bevy_ecs::system::ResMut;
+
+/// The number of frames the [`App`](bevy_app::App) has completed.
+///
+/// It is incremented at the end of every frame by [`update_frame_count`], which the
+/// [`CorePlugin`](crate::CorePlugin) adds to [`CoreStage::Last`](bevy_app::CoreStage::Last),
+/// so systems see `0` during the first frame. It wraps around to `0` on overflow, which makes it
+/// suitable for things like seeding or time-stamping events, but not for measuring long
+/// durations.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FrameCount(pub u32);
+
+/// Increments the [`FrameCount`] resource.
+pub fn update_frame_count(mut frame_count: ResMut<FrameCount>) {
+    frame_count.0 = frame_count.0.wrapping_add(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_frame_count, FrameCount};
+    use bevy_app::{App, CoreStage};
+    use bevy_ecs::system::{ResMut, SystemChangeTick};
+
+    #[derive(Default)]
+    struct SeenTicks(Vec<(u32, u32)>);
+
+    fn record_ticks(ticks: SystemChangeTick, mut seen: ResMut<SeenTicks>) {
+        seen.0.push((ticks.last_change_tick(), ticks.change_tick()));
+    }
+
+    #[test]
+    fn frame_count_and_change_ticks_advance() {
+        let mut app = App::new();
+        app.init_resource::<FrameCount>()
+            .init_resource::<SeenTicks>()
+            .add_system(record_ticks)
+            .add_system_to_stage(CoreStage::Last, update_frame_count);
+
+        for frame in 1..=3 {
+            app.update();
+            assert_eq!(app.world.resource::<FrameCount>().0, frame);
+        }
+
+        let seen = &app.world.resource::<SeenTicks>().0;
+        assert_eq!(seen.len(), 3);
+        for pair in seen.windows(2) {
+            let ((_, previous), (last, current)) = (pair[0], pair[1]);
+            assert_eq!(last, previous);
+            assert!(current > previous);
+        }
+    }
+}
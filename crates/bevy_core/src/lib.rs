@@ -1,21 +1,28 @@
 #![warn(missing_docs)]
 //! This crate provides core functionality for Bevy Engine.
 
+mod frame_count;
 mod name;
+mod name_index;
 mod task_pool_options;
 
 pub use bytemuck::{bytes_of, cast_slice, Pod, Zeroable};
+pub use frame_count::*;
 pub use name::*;
+pub use name_index::*;
 pub use task_pool_options::*;
 
 pub mod prelude {
     //! The Bevy Core Prelude.
     #[doc(hidden)]
-    pub use crate::{DefaultTaskPoolOptions, Name};
+    pub use crate::{DefaultTaskPoolOptions, Name, NameLookup};
 }
 
-use bevy_app::prelude::*;
-use bevy_ecs::entity::Entity;
+use bevy_app::{prelude::*, ClearTrackers};
+use bevy_ecs::{
+    entity::Entity,
+    prelude::{ExclusiveSystemDescriptorCoercion, IntoExclusiveSystem},
+};
 use bevy_utils::HashSet;
 use std::ops::Range;
 
@@ -34,6 +41,18 @@ impl Plugin for CorePlugin {
 
         app.register_type::<Entity>().register_type::<Name>();
 
+        app.init_resource::<FrameCount>()
+            .add_system_to_stage(CoreStage::Last, update_frame_count);
+
+        // The index reads the names removed during the whole frame, before they are cleared.
+        app.init_resource::<NameIndex>().add_system_to_stage(
+            CoreStage::Last,
+            update_name_index
+                .exclusive_system()
+                .at_start()
+                .before(ClearTrackers),
+        );
+
         register_rust_types(app);
         register_math_types(app);
     }
@@ -0,0 +1,212 @@
+use crate::Name;
+use bevy_ecs::{
+    entity::Entity,
+    query::Changed,
+    system::{Query, RemovedComponents, ResMut},
+    world::World,
+};
+use bevy_utils::HashMap;
+
+/// A resource mapping [`Name`]s to the entities that have them, for looking entities up by name
+/// while debugging.
+///
+/// It is updated once per frame by [`update_name_index`], which the
+/// [`CorePlugin`](crate::CorePlugin) runs at the start of
+/// [`CoreStage::Last`](bevy_app::CoreStage::Last), before
+/// [`ClearTrackers`](bevy_app::ClearTrackers), so it reflects the names spawned, despawned,
+/// removed or changed before that stage. Names aren't unique: entities sharing a name are kept in
+/// the order they were named.
+#[derive(Debug, Default)]
+pub struct NameIndex {
+    entities: HashMap<String, Vec<Entity>>,
+    names: HashMap<Entity, String>,
+}
+
+impl NameIndex {
+    /// Returns the first entity named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<Entity> {
+        self.get_all(name).first().copied()
+    }
+
+    /// Returns all the entities named `name`.
+    pub fn get_all(&self, name: &str) -> &[Entity] {
+        self.entities.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the number of named entities.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns `true` if no entity is named.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    fn insert(&mut self, entity: Entity, name: &str) {
+        if self.names.get(&entity).map(String::as_str) == Some(name) {
+            return;
+        }
+        self.remove(entity);
+        self.names.insert(entity, name.to_string());
+        self.entities
+            .entry(name.to_string())
+            .or_default()
+            .push(entity);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let name = match self.names.remove(&entity) {
+            Some(name) => name,
+            None => return,
+        };
+        if let Some(entities) = self.entities.get_mut(&name) {
+            entities.retain(|&named| named != entity);
+            if entities.is_empty() {
+                self.entities.remove(&name);
+            }
+        }
+    }
+}
+
+/// Updates the [`NameIndex`] with the [`Name`]s added, changed or removed since it last ran,
+/// including those of despawned entities.
+///
+/// The removals are read from [`RemovedComponents`], which are cleared by
+/// [`ClearTrackers`](bevy_app::ClearTrackers), so this should run just before it to see the
+/// removals of the whole frame. Its cost only depends on the number of names that changed.
+pub fn update_name_index(
+    mut index: ResMut<NameIndex>,
+    changed: Query<(Entity, &Name), Changed<Name>>,
+    removed: RemovedComponents<Name>,
+) {
+    for entity in removed.iter() {
+        index.remove(entity);
+    }
+    // An entity whose name was removed then added again is indexed again here.
+    for (entity, name) in changed.iter() {
+        index.insert(entity, name.as_str());
+    }
+}
+
+/// Looks entities up by [`Name`] in a [`World`], using its [`NameIndex`].
+///
+/// [`Name`] isn't known to `bevy_ecs`, so these lookups are an extension trait rather than
+/// methods of [`World`]. It is part of the prelude.
+pub trait NameLookup {
+    /// Returns the first entity named `name`, if any.
+    fn get_entity_by_name(&self, name: &str) -> Option<Entity>;
+
+    /// Returns all the entities named `name`.
+    fn get_entities_by_name(&self, name: &str) -> Vec<Entity>;
+}
+
+impl NameLookup for World {
+    fn get_entity_by_name(&self, name: &str) -> Option<Entity> {
+        self.get_resource::<NameIndex>()?.get(name)
+    }
+
+    fn get_entities_by_name(&self, name: &str) -> Vec<Entity> {
+        self.get_resource::<NameIndex>()
+            .map_or_else(Vec::new, |index| index.get_all(name).to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_name_index, NameIndex, NameLookup};
+    use crate::Name;
+    use bevy_app::{App, ClearTrackers, CoreStage};
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        prelude::IntoExclusiveSystem,
+        schedule::ExclusiveSystemDescriptorCoercion,
+        system::{Commands, Query},
+    };
+
+    /// An app that updates its [`NameIndex`] like the [`CorePlugin`](crate::CorePlugin) does.
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<NameIndex>().add_system_to_stage(
+            CoreStage::Last,
+            update_name_index
+                .exclusive_system()
+                .at_start()
+                .before(ClearTrackers),
+        );
+        app
+    }
+
+    #[test]
+    fn look_up_entities_by_name() {
+        let mut app = app();
+        let player = app.world.spawn().insert(Name::new("player")).id();
+        let first = app.world.spawn().insert(Name::new("enemy")).id();
+        let second = app.world.spawn().insert(Name::new("enemy")).id();
+        app.world.spawn();
+        app.update();
+
+        let world = &app.world;
+        assert_eq!(world.get_entity_by_name("player"), Some(player));
+        assert_eq!(world.get_entity_by_name("enemy"), Some(first));
+        assert_eq!(world.get_entities_by_name("enemy"), vec![first, second]);
+        assert_eq!(world.get_entity_by_name("boss"), None);
+        assert_eq!(world.resource::<NameIndex>().len(), 3);
+
+        app.world.get_mut::<Name>(first).unwrap().set("boss");
+        app.world.despawn(player);
+        app.update();
+
+        let world = &app.world;
+        assert_eq!(world.get_entity_by_name("boss"), Some(first));
+        assert_eq!(world.get_entities_by_name("enemy"), vec![second]);
+        assert_eq!(world.get_entity_by_name("player"), None);
+
+        app.world.entity_mut(second).remove::<Name>();
+        app.update();
+        assert!(app.world.get_entities_by_name("enemy").is_empty());
+        assert_eq!(app.world.resource::<NameIndex>().len(), 1);
+    }
+
+    #[test]
+    fn despawn_late_in_frame() {
+        #[derive(Component)]
+        struct Despawn(CoreStage);
+
+        fn despawn_in(stage: CoreStage) -> impl FnMut(Commands, Query<(Entity, &Despawn)>) {
+            move |mut commands, query| {
+                for (entity, despawn) in query.iter() {
+                    if despawn.0 == stage {
+                        commands.entity(entity).despawn();
+                    }
+                }
+            }
+        }
+
+        let mut app = app();
+        app.add_system_to_stage(CoreStage::PostUpdate, despawn_in(CoreStage::PostUpdate))
+            .add_system_to_stage(CoreStage::Last, despawn_in(CoreStage::Last));
+        app.world.spawn().insert(Name::new("enemy"));
+        let post_update = app.world.spawn().insert(Name::new("post_update")).id();
+        let last = app.world.spawn().insert(Name::new("last")).id();
+        app.update();
+        assert_eq!(app.world.resource::<NameIndex>().len(), 3);
+
+        // The entities are despawned after the index was updated in the frame before, one of them
+        // after the trackers were cleared.
+        app.world
+            .entity_mut(post_update)
+            .insert(Despawn(CoreStage::PostUpdate));
+        app.world.entity_mut(last).insert(Despawn(CoreStage::Last));
+        app.update();
+        app.update();
+
+        let world = &app.world;
+        assert!(!world.entities().contains(post_update));
+        assert!(!world.entities().contains(last));
+        assert_eq!(world.get_entity_by_name("post_update"), None);
+        assert_eq!(world.get_entity_by_name("last"), None);
+        assert_eq!(world.resource::<NameIndex>().len(), 1);
+    }
+}
@@ -255,6 +255,11 @@ pub fn derive_world_query_impl(ast: DeriveInput) -> TokenStream {
                     true #(&& self.#field_idents.archetype_filter_fetch(_archetype_index))*
                 }
 
+                #[inline]
+                unsafe fn may_pass_filter(&self) -> bool {
+                    true #(&& self.#field_idents.may_pass_filter())*
+                }
+
                 fn update_component_access(state: &Self::State, _access: &mut #path::query::FilteredAccess<#path::component::ComponentId>) {
                     #( #path::query::#fetch_type_alias::<'static, #field_types> :: update_component_access(&state.#field_idents, _access);  )*
                 }
@@ -381,7 +381,7 @@ impl SparseSetIndex for ArchetypeComponentId {
 pub struct Archetypes {
     pub(crate) archetypes: Vec<Archetype>,
     pub(crate) archetype_component_count: usize,
-    archetype_ids: HashMap<ArchetypeIdentity, ArchetypeId>,
+    archetype_ids: HashMap<ArchetypeIdentity, ArchetypeId, fxhash::FxBuildHasher>,
 }
 
 impl Default for Archetypes {
@@ -266,7 +266,7 @@ impl BundleInfo {
     /// `entity`, `bundle` must match this [`BundleInfo`]'s type
     #[inline]
     #[allow(clippy::too_many_arguments)]
-    unsafe fn write_components<T: Bundle>(
+    unsafe fn write_components<T: BundleComponents>(
         &self,
         table: &mut Table,
         sparse_sets: &mut SparseSets,
@@ -279,7 +279,7 @@ impl BundleInfo {
         // NOTE: get_components calls this closure on each component in "bundle order".
         // bundle_info.component_ids are also in "bundle order"
         let mut bundle_component = 0;
-        bundle.get_components(|component_ptr| {
+        bundle.take_components(|component_ptr| {
             let component_id = *self.component_ids.get_unchecked(bundle_component);
             match self.storage_types[bundle_component] {
                 StorageType::Table => {
@@ -422,6 +422,21 @@ impl<'a, 'b> BundleInserter<'a, 'b> {
         entity: Entity,
         archetype_index: usize,
         bundle: T,
+    ) -> EntityLocation {
+        self.insert_components(entity, archetype_index, bundle)
+    }
+
+    /// Same as [`BundleInserter::insert`], for the components of any bundle, including the
+    /// bundles registered with [`Bundles::init_dynamic_info`].
+    ///
+    /// # Safety
+    /// See [`BundleInserter::insert`].
+    #[inline]
+    pub(crate) unsafe fn insert_components<T: BundleComponents>(
+        &mut self,
+        entity: Entity,
+        archetype_index: usize,
+        bundle: T,
     ) -> EntityLocation {
         let location = EntityLocation {
             index: archetype_index,
@@ -577,10 +592,35 @@ impl<'a, 'b> BundleSpawner<'a, 'b> {
     }
 }
 
+/// The components of a bundle, passed in "bundle order" to write them.
+pub(crate) trait BundleComponents {
+    fn take_components(self, func: impl FnMut(OwningPtr<'_>));
+}
+
+impl<T: Bundle> BundleComponents for T {
+    #[inline]
+    fn take_components(self, func: impl FnMut(OwningPtr<'_>)) {
+        self.get_components(func);
+    }
+}
+
+/// The components of a bundle registered with [`Bundles::init_dynamic_info`], in the order of
+/// its component ids.
+pub(crate) struct DynamicComponents<'a>(pub(crate) Vec<OwningPtr<'a>>);
+
+impl<'a> BundleComponents for DynamicComponents<'a> {
+    #[inline]
+    fn take_components(self, func: impl FnMut(OwningPtr<'_>)) {
+        self.0.into_iter().for_each(func);
+    }
+}
+
 #[derive(Default)]
 pub struct Bundles {
     bundle_infos: Vec<BundleInfo>,
     bundle_ids: HashMap<TypeId, BundleId>,
+    /// The bundles that were only registered by their component ids.
+    dynamic_bundle_ids: HashMap<Box<[ComponentId]>, BundleId>,
 }
 
 impl Bundles {
@@ -613,6 +653,31 @@ impl Bundles {
         // SAFETY: index either exists, or was initialized
         unsafe { self.bundle_infos.get_unchecked(id.0) }
     }
+
+    /// Returns the [`BundleInfo`] of the bundle made of the given components, in this order.
+    ///
+    /// # Safety
+    ///
+    /// `component_ids` must be valid [`ComponentId`]'s
+    pub(crate) unsafe fn init_dynamic_info<'a>(
+        &'a mut self,
+        components: &mut Components,
+        component_ids: &[ComponentId],
+    ) -> &'a BundleInfo {
+        let id = match self.dynamic_bundle_ids.get(component_ids) {
+            Some(id) => *id,
+            None => {
+                let id = BundleId(self.bundle_infos.len());
+                let bundle_info =
+                    initialize_bundle("dynamic bundle", component_ids.to_vec(), id, components);
+                self.bundle_infos.push(bundle_info);
+                self.dynamic_bundle_ids.insert(component_ids.into(), id);
+                id
+            }
+        };
+        // SAFETY: index either exists, or was initialized
+        self.bundle_infos.get_unchecked(id.0)
+    }
 }
 
 /// # Safety
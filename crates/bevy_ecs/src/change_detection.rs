@@ -1,6 +1,10 @@
 //! Types that detect when their internal data mutate.
 
-use crate::{component::ComponentTicks, ptr::PtrMut, system::Resource};
+use crate::{
+    component::{ColumnChangeTick, ComponentTicks},
+    ptr::PtrMut,
+    system::Resource,
+};
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::Reflect;
 use std::ops::{Deref, DerefMut};
@@ -26,7 +30,8 @@ pub const MAX_CHANGE_AGE: u32 = u32::MAX - (2 * CHECK_TICK_THRESHOLD - 1);
 /// Using types that implement [`DetectChanges`], such as [`ResMut`], provide
 /// a way to query if a value has been mutated in another system.
 /// Normally change detecting is triggered by either [`DerefMut`] or [`AsMut`], however
-/// it can be manually triggered via [`DetectChanges::set_changed`].
+/// it can be manually triggered via [`DetectChanges::set_changed`]. Reading the value through
+/// [`Deref`] or [`AsRef`], for example to pass a `&T` to a read-only helper, never triggers it.
 ///
 /// ```
 /// use bevy_ecs::prelude::*;
@@ -43,6 +48,11 @@ pub const MAX_CHANGE_AGE: u32 = u32::MAX - (2 * CHECK_TICK_THRESHOLD - 1);
 /// ```
 ///
 pub trait DetectChanges {
+    /// The type contained within this smart pointer.
+    ///
+    /// For example, for `ResMut<T>` this would be `T`.
+    type Inner: ?Sized;
+
     /// Returns `true` if this value was added after the system last ran.
     fn is_added(&self) -> bool;
 
@@ -65,11 +75,25 @@ pub trait DetectChanges {
     /// [`SystemChangeTick`](crate::system::SystemChangeTick)
     /// [`SystemParam`](crate::system::SystemParam).
     fn last_changed(&self) -> u32;
+
+    /// Returns a mutable reference to the contained value, without flagging it as changed.
+    ///
+    /// Changes made through this reference are not seen by [`Changed`](crate::query::Changed)
+    /// filters or [`is_changed`](DetectChanges::is_changed). This is useful for mutations that
+    /// other systems shouldn't react to, such as restoring a deserialized value or caching
+    /// derived data in the component itself.
+    ///
+    /// **Note**: Bypassing change detection can easily leave systems relying on it with stale
+    /// data. Call [`set_changed`](DetectChanges::set_changed) afterwards if the change should
+    /// be seen after all.
+    fn bypass_change_detection(&mut self) -> &mut Self::Inner;
 }
 
 macro_rules! change_detection_impl {
     ($name:ident < $( $generics:tt ),+ >, $target:ty, $($traits:ident)?) => {
         impl<$($generics),* $(: $traits)?> DetectChanges for $name<$($generics),*> {
+            type Inner = $target;
+
             #[inline]
             fn is_added(&self) -> bool {
                 self.ticks
@@ -86,14 +110,17 @@ macro_rules! change_detection_impl {
 
             #[inline]
             fn set_changed(&mut self) {
-                self.ticks
-                    .component_ticks
-                    .set_changed(self.ticks.change_tick);
+                self.ticks.set_changed();
             }
 
             #[inline]
             fn last_changed(&self) -> u32 {
-                self.ticks.last_change_tick
+                self.ticks.component_ticks.changed
+            }
+
+            #[inline]
+            fn bypass_change_detection(&mut self) -> &mut Self::Inner {
+                self.value
             }
         }
 
@@ -161,10 +188,22 @@ macro_rules! impl_debug {
 
 pub(crate) struct Ticks<'a> {
     pub(crate) component_ticks: &'a mut ComponentTicks,
+    /// The change tick of the storage column holding the value, if it is a component.
+    pub(crate) column_tick: Option<&'a ColumnChangeTick>,
     pub(crate) last_change_tick: u32,
     pub(crate) change_tick: u32,
 }
 
+impl<'a> Ticks<'a> {
+    #[inline]
+    pub(crate) fn set_changed(&mut self) {
+        self.component_ticks.set_changed(self.change_tick);
+        if let Some(column_tick) = self.column_tick {
+            column_tick.record(self.change_tick);
+        }
+    }
+}
+
 /// Unique mutable borrow of a resource.
 ///
 /// See the [`World`](crate::world::World) documentation to see the usage of a resource.
@@ -216,6 +255,84 @@ change_detection_impl!(Mut<'a, T>, T,);
 impl_into_inner!(Mut<'a, T>, T,);
 impl_debug!(Mut<'a, T>,);
 
+/// Shared borrow of an entity's component, along with its change ticks.
+///
+/// Using `Ref<T>` in a [`Query`](crate::system::Query) reads the component like `&T`, but also
+/// tells whether it was added or changed since the system last ran. Unlike the
+/// [`Added`](crate::query::Added) and [`Changed`](crate::query::Changed) filters, this matches
+/// every entity with the component, so a system can branch on each entity in a single pass.
+///
+/// ```
+/// # use bevy_ecs::{change_detection::Ref, prelude::*};
+/// #[derive(Component, Debug)]
+/// struct Transform(f32);
+///
+/// fn print_transforms(query: Query<Ref<Transform>>) {
+///     for transform in query.iter() {
+///         if transform.is_changed() {
+///             println!("moved to {:?}", *transform);
+///         } else {
+///             println!("stayed at {:?}", *transform);
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(print_transforms);
+/// ```
+pub struct Ref<'a, T> {
+    pub(crate) value: &'a T,
+    pub(crate) component_ticks: &'a ComponentTicks,
+    pub(crate) last_change_tick: u32,
+    pub(crate) change_tick: u32,
+}
+
+impl<'a, T> Ref<'a, T> {
+    /// Returns `true` if this value was added after the system last ran.
+    #[inline]
+    pub fn is_added(&self) -> bool {
+        self.component_ticks
+            .is_added(self.last_change_tick, self.change_tick)
+    }
+
+    /// Returns `true` if this value was added or mutably dereferenced after the system last ran.
+    #[inline]
+    pub fn is_changed(&self) -> bool {
+        self.component_ticks
+            .is_changed(self.last_change_tick, self.change_tick)
+    }
+
+    /// Returns the change tick recording the previous time this component was changed.
+    ///
+    /// See [`DetectChanges::last_changed`].
+    #[inline]
+    pub fn last_changed(&self) -> u32 {
+        self.component_ticks.changed
+    }
+
+    /// Consumes `self` and returns the shared reference to the contained value.
+    #[inline]
+    pub fn into_inner(self) -> &'a T {
+        self.value
+    }
+}
+
+impl<'a, T> Deref for Ref<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+
+impl<'a, T> AsRef<T> for Ref<'a, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.value
+    }
+}
+
+impl_debug!(Ref<'a, T>,);
+
 /// Unique mutable borrow of a reflected component or resource
 #[cfg(feature = "bevy_reflect")]
 pub struct ReflectMut<'a> {
@@ -250,7 +367,9 @@ impl<'a> MutUntyped<'a> {
     }
 }
 
-impl DetectChanges for MutUntyped<'_> {
+impl<'a> DetectChanges for MutUntyped<'a> {
+    type Inner = PtrMut<'a>;
+
     fn is_added(&self) -> bool {
         self.ticks
             .component_ticks
@@ -264,13 +383,15 @@ impl DetectChanges for MutUntyped<'_> {
     }
 
     fn set_changed(&mut self) {
-        self.ticks
-            .component_ticks
-            .set_changed(self.ticks.change_tick);
+        self.ticks.set_changed();
     }
 
     fn last_changed(&self) -> u32 {
-        self.ticks.last_change_tick
+        self.ticks.component_ticks.changed
+    }
+
+    fn bypass_change_detection(&mut self) -> &mut Self::Inner {
+        &mut self.value
     }
 }
 
@@ -286,16 +407,21 @@ impl std::fmt::Debug for MutUntyped<'_> {
 mod tests {
     use crate::{
         self as bevy_ecs,
-        change_detection::{CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE},
-        component::Component,
-        query::ChangeTrackers,
-        system::{IntoSystem, Query, System},
+        change_detection::Ref,
+        change_detection::{DetectChanges, CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE},
+        component::{ColumnChangeTick, Component},
+        query::{ChangeTrackers, Changed},
+        system::{IntoSystem, Query, Res, ResMut, System, SystemState},
         world::World,
     };
 
     #[derive(Component)]
     struct C;
 
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct SparseC;
+
     #[test]
     fn change_expiration() {
         fn change_detected(query: Query<ChangeTrackers<C>>) -> bool {
@@ -331,6 +457,207 @@ mod tests {
         assert!(!change_expired_system.run((), &mut world));
     }
 
+    #[test]
+    fn ref_change_flags() {
+        #[derive(Component)]
+        struct Transform(usize);
+
+        type Flags = Vec<(usize, bool, bool, Option<bool>)>;
+
+        fn change_flags(query: Query<(Ref<Transform>, Option<Ref<SparseC>>)>) -> Flags {
+            let mut flags = query
+                .iter()
+                .map(|(transform, sparse)| {
+                    (
+                        transform.0,
+                        transform.is_added(),
+                        transform.is_changed(),
+                        sparse.map(|sparse| sparse.is_changed()),
+                    )
+                })
+                .collect::<Vec<_>>();
+            flags.sort();
+            flags
+        }
+
+        let mut world = World::new();
+        let entities = (0..3)
+            .map(|i| world.spawn().insert(Transform(i)).id())
+            .collect::<Vec<_>>();
+        let sparse = world.spawn().insert_bundle((Transform(3), SparseC)).id();
+
+        let mut system = IntoSystem::into_system(change_flags);
+        system.initialize(&mut world);
+        assert_eq!(
+            system.run((), &mut world),
+            vec![
+                (0, true, true, None),
+                (1, true, true, None),
+                (2, true, true, None),
+                (3, true, true, Some(true)),
+            ]
+        );
+
+        world.increment_change_tick();
+        world.get_mut::<Transform>(entities[1]).unwrap().0 = 10;
+        world.get_mut::<SparseC>(sparse).unwrap().set_changed();
+        assert_eq!(
+            system.run((), &mut world),
+            vec![
+                (0, false, false, None),
+                (2, false, false, None),
+                (3, false, false, Some(true)),
+                (10, false, true, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn resource_last_changed() {
+        struct A(usize);
+        struct B;
+        #[derive(Default)]
+        struct Cache {
+            a_tick: u32,
+            b_tick: u32,
+            a_rebuilds: usize,
+            b_rebuilds: usize,
+        }
+
+        fn rebuild_cache(a: Res<A>, b: Res<B>, mut cache: ResMut<Cache>) {
+            if a.last_changed() != cache.a_tick {
+                cache.a_tick = a.last_changed();
+                cache.a_rebuilds += 1;
+            }
+            if b.last_changed() != cache.b_tick {
+                cache.b_tick = b.last_changed();
+                cache.b_rebuilds += 1;
+            }
+        }
+
+        let mut world = World::new();
+        world.insert_resource(A(0));
+        world.insert_resource(B);
+        world.init_resource::<Cache>();
+        let mut system = IntoSystem::into_system(rebuild_cache);
+        system.initialize(&mut world);
+
+        system.run((), &mut world);
+        system.run((), &mut world);
+        let cache = world.resource::<Cache>();
+        assert_eq!((cache.a_rebuilds, cache.b_rebuilds), (1, 1));
+
+        world.resource_mut::<A>().0 += 1;
+        system.run((), &mut world);
+        system.run((), &mut world);
+        let cache = world.resource::<Cache>();
+        assert_eq!((cache.a_rebuilds, cache.b_rebuilds), (2, 1));
+    }
+
+    #[test]
+    fn set_changed_and_bypass_change_detection() {
+        #[derive(Component)]
+        struct V(usize);
+
+        #[derive(Clone, Copy)]
+        enum Mode {
+            SetChanged,
+            Bypass,
+        }
+
+        fn write(mut query: Query<&mut V>, mode: Res<Mode>) {
+            for mut v in query.iter_mut() {
+                match *mode {
+                    Mode::SetChanged => v.set_changed(),
+                    Mode::Bypass => v.bypass_change_detection().0 += 1,
+                }
+            }
+        }
+
+        fn count_changed(query: Query<(), Changed<V>>) -> usize {
+            query.iter().count()
+        }
+
+        let mut world = World::new();
+        let entity = world.spawn().insert(V(0)).id();
+        world.insert_resource(Mode::Bypass);
+        let mut write = IntoSystem::into_system(write);
+        let mut count_changed = IntoSystem::into_system(count_changed);
+        write.initialize(&mut world);
+        count_changed.initialize(&mut world);
+
+        // The insertion is seen once.
+        assert_eq!(count_changed.run((), &mut world), 1);
+        assert_eq!(count_changed.run((), &mut world), 0);
+
+        write.run((), &mut world);
+        assert_eq!(count_changed.run((), &mut world), 0);
+        assert_eq!(world.get::<V>(entity).unwrap().0, 1);
+
+        *world.resource_mut::<Mode>() = Mode::SetChanged;
+        write.run((), &mut world);
+        assert_eq!(count_changed.run((), &mut world), 1);
+        assert_eq!(world.get::<V>(entity).unwrap().0, 1);
+    }
+
+    #[test]
+    fn as_ref_does_not_trigger_change() {
+        #[derive(Component)]
+        struct Transform(f32);
+
+        fn length(transform: &Transform) -> f32 {
+            transform.0.abs()
+        }
+
+        fn measure(mut query: Query<&mut Transform>, mut total: ResMut<f32>) {
+            for transform in query.iter_mut() {
+                *total += length(transform.as_ref());
+            }
+        }
+
+        fn count_changed(query: Query<(), Changed<Transform>>) -> usize {
+            query.iter().count()
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0.0f32);
+        world.spawn().insert(Transform(-2.0));
+        let mut measure = IntoSystem::into_system(measure);
+        let mut count_changed = IntoSystem::into_system(count_changed);
+        measure.initialize(&mut world);
+        count_changed.initialize(&mut world);
+        assert_eq!(count_changed.run((), &mut world), 1);
+
+        measure.run((), &mut world);
+        assert_eq!(*world.resource::<f32>(), 2.0);
+        assert_eq!(count_changed.run((), &mut world), 0);
+    }
+
+    #[test]
+    fn change_tick_range() {
+        let mut world = World::new();
+        let entity = world.spawn().insert(C).id();
+        world.clear_trackers();
+        world.clear_trackers();
+        world.get_mut::<C>(entity).unwrap().set_changed();
+        let changed = world
+            .entity(entity)
+            .get_change_ticks::<C>()
+            .unwrap()
+            .changed_tick();
+        world.clear_trackers();
+
+        let mut system_state = SystemState::<Query<ChangeTrackers<C>>>::new(&mut world);
+        let query = system_state.get(&world);
+        let tracker = query.single();
+        // The range excludes its start, and includes its end.
+        assert!(tracker.is_changed_between(changed - 1, changed));
+        assert!(tracker.is_changed_between(changed - 1, changed + 1));
+        assert!(!tracker.is_changed_between(changed, changed + 1));
+        assert!(!tracker.is_changed_between(changed - 2, changed - 1));
+        assert!(!tracker.is_changed_between(changed - 1, changed - 1));
+    }
+
     #[test]
     fn change_tick_wraparound() {
         fn change_detected(query: Query<ChangeTrackers<C>>) -> bool {
@@ -382,4 +709,38 @@ mod tests {
             assert!(ticks_since_change == MAX_CHANGE_AGE);
         }
     }
+
+    #[test]
+    fn column_change_tick_keeps_newest() {
+        let mut column_tick = ColumnChangeTick::default();
+        column_tick.set(u32::MAX - 1);
+
+        // newer, even though it wrapped around
+        column_tick.record(2);
+        assert_eq!(column_tick.get(), 2);
+
+        // a system that started earlier and is still running records an older tick
+        column_tick.record(u32::MAX);
+        assert_eq!(column_tick.get(), 2);
+
+        column_tick.record(3);
+        assert_eq!(column_tick.get(), 3);
+    }
+
+    #[test]
+    fn column_change_tick_scan() {
+        let mut column_tick = ColumnChangeTick::default();
+        column_tick.set(1);
+
+        let change_tick = 1 + 2 * CHECK_TICK_THRESHOLD;
+        assert!(column_tick.is_changed(0, change_tick));
+        column_tick.check_tick(change_tick);
+        assert_eq!(
+            change_tick.wrapping_sub(column_tick.get()),
+            CHECK_TICK_THRESHOLD
+        );
+        // clamping only ever makes the column look newer
+        assert!(column_tick.is_changed(0, change_tick));
+        assert!(!column_tick.is_changed(change_tick - 1, change_tick));
+    }
 }
@@ -1,7 +1,7 @@
 //! Types for declaring and storing [`Component`]s.
 
 use crate::{
-    change_detection::MAX_CHANGE_AGE,
+    change_detection::{CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE},
     storage::{SparseSetIndex, Storages},
     system::Resource,
 };
@@ -12,6 +12,7 @@ use std::{
     any::{Any, TypeId},
     borrow::Cow,
     mem::needs_drop,
+    sync::atomic::{AtomicU32, Ordering},
 };
 
 /// A data type that can be used to store data for an [entity].
@@ -81,6 +82,13 @@ use std::{
 /// struct ComponentA;
 /// ```
 ///
+/// Adding or removing a sparse set component still changes the archetype of the entity, but unlike
+/// a table component it does not move the entity's other components to another table. This makes
+/// it a good fit for markers that are frequently added and removed. Queries iterate sparse set and
+/// table components together transparently, at a small cost per entity.
+///
+/// The `add_remove` benchmark compares both storage types.
+///
 /// [`Table`]: crate::storage::Table
 /// [`SparseSet`]: crate::storage::SparseSet
 ///
@@ -109,6 +117,11 @@ use std::{
 ///
 /// [orphan rule]: https://doc.rust-lang.org/book/ch10-02-traits.html#implementing-a-trait-on-a-type
 /// [newtype pattern]: https://doc.rust-lang.org/book/ch19-03-advanced-traits.html#using-the-newtype-pattern-to-implement-external-traits-on-external-types
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a `Component`",
+    label = "invalid `Component`",
+    note = "consider annotating `{Self}` with `#[derive(Component)]`"
+)]
 pub trait Component: Send + Sync + 'static {
     type Storage: ComponentStorage;
 }
@@ -557,6 +570,32 @@ impl ComponentTicks {
         ticks_since_system > ticks_since_change
     }
 
+    #[inline]
+    /// Returns `true` if the component was added or mutably dereferenced after the tick `start`,
+    /// and at or before the tick `end`.
+    ///
+    /// Like [`ComponentTicks::is_changed`], the ticks are compared by their age relative to the
+    /// current world tick `change_tick`, so this works even with wraparound.
+    pub fn is_changed_between(&self, start: u32, end: u32, change_tick: u32) -> bool {
+        let ticks_since_change = change_tick.wrapping_sub(self.changed).min(MAX_CHANGE_AGE);
+        let ticks_since_start = change_tick.wrapping_sub(start).min(MAX_CHANGE_AGE);
+        let ticks_since_end = change_tick.wrapping_sub(end).min(MAX_CHANGE_AGE);
+
+        ticks_since_start > ticks_since_change && ticks_since_change >= ticks_since_end
+    }
+
+    /// Returns the change tick at which the component was added.
+    #[inline]
+    pub fn added_tick(&self) -> u32 {
+        self.added
+    }
+
+    /// Returns the change tick at which the component was last mutably dereferenced (or added).
+    #[inline]
+    pub fn changed_tick(&self) -> u32 {
+        self.changed
+    }
+
     pub(crate) fn new(change_tick: u32) -> Self {
         Self {
             added: change_tick,
@@ -589,6 +628,34 @@ impl ComponentTicks {
     }
 }
 
+/// Records when a resource was removed, until it is inserted again.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct ResourceRemoval {
+    /// The change tick at which the resource was removed.
+    pub(crate) removed: u32,
+    /// The change tick at which the removed value was added.
+    pub(crate) added: u32,
+}
+
+impl ResourceRemoval {
+    /// Returns `true` if the resource was removed after the system last ran.
+    #[inline]
+    pub(crate) fn is_removed(&self, last_change_tick: u32, change_tick: u32) -> bool {
+        // Like `ComponentTicks::is_added`, this compares ages to handle wraparound.
+        let ticks_since_removal = change_tick.wrapping_sub(self.removed).min(MAX_CHANGE_AGE);
+        let ticks_since_system = change_tick
+            .wrapping_sub(last_change_tick)
+            .min(MAX_CHANGE_AGE);
+
+        ticks_since_system > ticks_since_removal
+    }
+
+    pub(crate) fn check_ticks(&mut self, change_tick: u32) {
+        check_tick(&mut self.removed, change_tick);
+        check_tick(&mut self.added, change_tick);
+    }
+}
+
 fn check_tick(last_change_tick: &mut u32, change_tick: u32) {
     let age = change_tick.wrapping_sub(*last_change_tick);
     // This comparison assumes that `age` has not overflowed `u32::MAX` before, which will be true
@@ -597,3 +664,64 @@ fn check_tick(last_change_tick: &mut u32, change_tick: u32) {
         *last_change_tick = change_tick.wrapping_sub(MAX_CHANGE_AGE);
     }
 }
+
+/// Records the most recent change tick of all the values stored in a [`Column`](crate::storage::Column).
+///
+/// This is never older than the [`ComponentTicks`] of any value in the column, which lets
+/// [`Added`](crate::query::Added) and [`Changed`](crate::query::Changed) filters skip entire
+/// tables and archetypes in which nothing changed since the system last ran.
+#[derive(Debug, Default)]
+pub struct ColumnChangeTick {
+    changed: AtomicU32,
+}
+
+impl ColumnChangeTick {
+    /// Returns `true` if any value in the column may have been added or mutably dereferenced
+    /// after the system last ran.
+    #[inline]
+    pub fn is_changed(&self, last_change_tick: u32, change_tick: u32) -> bool {
+        ComponentTicks::new(self.get()).is_changed(last_change_tick, change_tick)
+    }
+
+    #[inline]
+    pub(crate) fn get(&self) -> u32 {
+        self.changed.load(Ordering::Relaxed)
+    }
+
+    /// Overwrites the recorded tick. Only valid if no value in the column is newer than `change_tick`.
+    #[inline]
+    pub(crate) fn set(&mut self, change_tick: u32) {
+        *self.changed.get_mut() = change_tick;
+    }
+
+    /// Records a change at `change_tick`, unless a newer change has already been recorded.
+    ///
+    /// `change_tick` must be a recent world tick, e.g. the change tick of a running system.
+    #[inline]
+    pub(crate) fn record(&self, change_tick: u32) {
+        let mut current = self.get();
+        // Systems running in parallel may have recorded a slightly newer tick, which must be kept.
+        // Column ticks are never older than `3 * CHECK_TICK_THRESHOLD` (see `check_tick`), so the
+        // wrapping difference of two of them always fits in an `i32`.
+        while (current.wrapping_sub(change_tick) as i32) < 0 {
+            match self.changed.compare_exchange_weak(
+                current,
+                change_tick,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    pub(crate) fn check_tick(&mut self, change_tick: u32) {
+        // Clamping to a newer tick only makes the column look changed to systems that have not run
+        // in a very long time, and keeps `record` from ever mistaking an old tick for a newer one.
+        let changed = self.changed.get_mut();
+        if change_tick.wrapping_sub(*changed) > CHECK_TICK_THRESHOLD {
+            *changed = change_tick.wrapping_sub(CHECK_TICK_THRESHOLD);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate as bevy_ecs;
+use crate::component::Component;
+
+/// A marker component for entities that are kept alive but excluded from most systems.
+///
+/// Queries implicitly skip entities with a `Disabled` component, as if they had a
+/// `Without<Disabled>` filter. This lets an entity be "soft despawned": it keeps its components
+/// and can be re-enabled by removing `Disabled`, but systems stop seeing it in the meantime.
+///
+/// Since this filter is implicit, it can be surprising. A query sees disabled entities if it
+/// mentions `Disabled` explicitly:
+///
+/// - `Query<&A, With<Disabled>>` and `Query<(&A, &Disabled)>` only see disabled entities.
+/// - `Query<(&A, Option<&Disabled>)>` sees both enabled and disabled entities.
+///
+/// A [`QueryState`](crate::query::QueryState) can also opt out with
+/// [`QueryState::include_disabled`](crate::query::QueryState::include_disabled).
+///
+/// Direct access by entity, such as [`World::get`](crate::world::World::get), is not affected.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.spawn().insert(Health(10));
+/// let disabled = world.spawn().insert_bundle((Health(20), Disabled)).id();
+///
+/// let mut query = world.query::<&Health>();
+/// assert_eq!(query.iter(&world).count(), 1);
+///
+/// let mut query = world.query::<(&Health, Option<&Disabled>)>();
+/// assert_eq!(query.iter(&world).count(), 2);
+///
+/// world.entity_mut(disabled).remove::<Disabled>();
+/// let mut query = world.query::<&Health>();
+/// assert_eq!(query.iter(&world).count(), 2);
+/// ```
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Disabled;
@@ -33,11 +33,19 @@
 //! [`EntityMut::insert`]: crate::world::EntityMut::insert
 //! [`EntityMut::insert_bundle`]: crate::world::EntityMut::insert_bundle
 //! [`EntityMut::remove`]: crate::world::EntityMut::remove
+mod disabled;
 mod map_entities;
+mod relationship;
+mod respawn;
 mod serde;
+mod spawn_order;
 
 pub use self::serde::*;
+pub use disabled::*;
 pub use map_entities::*;
+pub use relationship::*;
+pub use respawn::*;
+pub use spawn_order::*;
 
 use crate::{archetype::ArchetypeId, storage::SparseSetIndex};
 use std::{
@@ -294,6 +302,12 @@ pub struct Entities {
     free_cursor: AtomicI64,
     /// Stores the number of free entities for [`len`](Entities::len)
     len: u32,
+    /// When `true`, freed IDs are never added to the freelist, so every new entity gets the next
+    /// unused ID. See [`World::with_deterministic_allocation`](crate::world::World::with_deterministic_allocation).
+    pub(crate) sequential: bool,
+    /// The IDs freed while [`sequential`](Entities::sequential) is set. They are never reused by
+    /// [`alloc`](Entities::alloc), but can be allocated again by [`alloc_at`](Entities::alloc_at).
+    retired: Vec<u32>,
 }
 
 impl Entities {
@@ -410,6 +424,10 @@ impl Entities {
             *self.free_cursor.get_mut() = new_free_cursor;
             self.len += 1;
             None
+        } else if let Some(index) = self.retired.iter().position(|item| *item == entity.id) {
+            self.retired.swap_remove(index);
+            self.len += 1;
+            None
         } else {
             Some(mem::replace(
                 &mut self.meta[entity.id as usize].location,
@@ -441,6 +459,10 @@ impl Entities {
             *self.free_cursor.get_mut() = new_free_cursor;
             self.len += 1;
             AllocAtWithoutReplacement::DidNotExist
+        } else if let Some(index) = self.retired.iter().position(|item| *item == entity.id) {
+            self.retired.swap_remove(index);
+            self.len += 1;
+            AllocAtWithoutReplacement::DidNotExist
         } else {
             let current_meta = &mut self.meta[entity.id as usize];
             if current_meta.location.archetype_id == ArchetypeId::INVALID {
@@ -470,10 +492,14 @@ impl Entities {
 
         let loc = mem::replace(&mut meta.location, EntityMeta::EMPTY.location);
 
-        self.pending.push(entity.id);
+        if self.sequential {
+            self.retired.push(entity.id);
+        } else {
+            self.pending.push(entity.id);
 
-        let new_free_cursor = self.pending.len() as i64;
-        *self.free_cursor.get_mut() = new_free_cursor;
+            let new_free_cursor = self.pending.len() as i64;
+            *self.free_cursor.get_mut() = new_free_cursor;
+        }
         self.len -= 1;
         Some(loc)
     }
@@ -500,6 +526,7 @@ impl Entities {
     pub fn clear(&mut self) {
         self.meta.clear();
         self.pending.clear();
+        self.retired.clear();
         *self.free_cursor.get_mut() = 0;
         self.len = 0;
     }
@@ -634,7 +661,11 @@ impl EntityMeta {
 }
 
 /// A location of an entity in an archetype.
-#[derive(Copy, Clone, Debug)]
+///
+/// A location is only valid until the next structural change of the [`World`](crate::world::World):
+/// it changes when the entity moves to another archetype, and its index can change when another
+/// entity of its archetype is removed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct EntityLocation {
     /// The archetype index
     pub archetype_id: ArchetypeId,
@@ -0,0 +1,198 @@
+use crate::{
+    component::{Component, TableStorage},
+    entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+};
+use std::{fmt, marker::PhantomData};
+
+/// A kind of link from one entity to others, such as a turret to its targets or a quest to its
+/// objectives.
+///
+/// A relationship type is only used as a key: the links themselves are stored in the
+/// [`Relations<R>`] component of the source entity, which entities can have one of per
+/// relationship type.
+pub trait Relationship: Send + Sync + 'static {}
+
+/// The entities the entity with this component is related to by the relationship `R`.
+///
+/// This generalizes the `Children` component of `bevy_hierarchy` to arbitrary typed links.
+/// Unlike the hierarchy, there is no back link: the related entities don't know about the
+/// relationship, and despawning one of them does not remove it from the [`Relations`] that
+/// refer to it.
+///
+/// Request it in queries with [`RelatedTo<R>`].
+///
+/// ```
+/// # use bevy_ecs::{entity::{RelatedTo, Relations, Relationship}, prelude::*};
+/// #[derive(Component)]
+/// struct Turret;
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// struct Targets;
+/// impl Relationship for Targets {}
+///
+/// fn fire(turrets: Query<(&Turret, RelatedTo<Targets>)>, mut targets: Query<&mut Health>) {
+///     for (_, related) in turrets.iter() {
+///         for target in related.iter() {
+///             if let Ok(mut health) = targets.get_mut(target) {
+///                 health.0 = health.0.saturating_sub(1);
+///             }
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(fire);
+/// ```
+pub struct Relations<R: Relationship> {
+    entities: Vec<Entity>,
+    marker: PhantomData<fn() -> R>,
+}
+
+/// Query data fetching the [`Relations<R>`] of an entity, for use as `Query<RelatedTo<R>>`.
+pub type RelatedTo<R> = &'static Relations<R>;
+
+impl<R: Relationship> Component for Relations<R> {
+    type Storage = TableStorage;
+}
+
+impl<R: Relationship> Relations<R> {
+    /// Creates a new [`Relations`] with the given related entities.
+    pub fn new(entities: impl IntoIterator<Item = Entity>) -> Self {
+        Relations {
+            entities: entities.into_iter().collect(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Relates `entity`, if it isn't already.
+    pub fn add(&mut self, entity: Entity) {
+        if !self.contains(entity) {
+            self.entities.push(entity);
+        }
+    }
+
+    /// Removes `entity` from the related entities, and returns whether it was related.
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let len = self.entities.len();
+        self.entities.retain(|related| *related != entity);
+        self.entities.len() != len
+    }
+
+    /// Returns `true` if `entity` is related.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    /// Returns an iterator over the related entities, in the order they were added.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Entity> + ExactSizeIterator + '_ {
+        self.entities.iter().copied()
+    }
+
+    /// Returns the related entities, in the order they were added.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Returns the number of related entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entities are related.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+impl<R: Relationship> Default for Relations<R> {
+    fn default() -> Self {
+        Relations {
+            entities: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Relationship> Clone for Relations<R> {
+    fn clone(&self) -> Self {
+        Relations {
+            entities: self.entities.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Relationship> fmt::Debug for Relations<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Relations")
+            .field(&std::any::type_name::<R>())
+            .field(&self.entities)
+            .finish()
+    }
+}
+
+impl<R: Relationship> MapEntities for Relations<R> {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        for entity in &mut self.entities {
+            *entity = entity_map.get(*entity)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RelatedTo, Relations, Relationship};
+    use crate::{self as bevy_ecs, component::Component, entity::Entity, world::World};
+
+    #[derive(Component)]
+    struct Quest(&'static str);
+
+    #[derive(Component)]
+    struct Objective(&'static str);
+
+    struct Objectives;
+    impl Relationship for Objectives {}
+
+    struct Prerequisites;
+    impl Relationship for Prerequisites {}
+
+    #[test]
+    fn traverse_related_entities() {
+        let mut world = World::new();
+        let gather = world.spawn().insert(Objective("gather")).id();
+        let deliver = world.spawn().insert(Objective("deliver")).id();
+        let intro = world.spawn().insert(Quest("intro")).id();
+        let main = world
+            .spawn()
+            .insert(Quest("main"))
+            .insert(Relations::<Objectives>::new([gather, deliver]))
+            .insert(Relations::<Prerequisites>::new([intro]))
+            .id();
+
+        let mut quests = world.query::<(Entity, &Quest, RelatedTo<Objectives>)>();
+        let mut objectives = world.query::<&Objective>();
+        let traversed = quests
+            .iter(&world)
+            .map(|(entity, quest, related)| {
+                let names = related
+                    .iter()
+                    .map(|objective| objectives.get(&world, objective).unwrap().0)
+                    .collect::<Vec<_>>();
+                (entity, quest.0, names)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(traversed, vec![(main, "main", vec!["gather", "deliver"])]);
+
+        // Each relationship type is stored separately.
+        let mut prerequisites = world.query::<RelatedTo<Prerequisites>>();
+        assert_eq!(prerequisites.single(&world).entities(), &[intro]);
+
+        let mut relations = world.get_mut::<Relations<Objectives>>(main).unwrap();
+        relations.add(gather);
+        assert_eq!(relations.len(), 2);
+        assert!(relations.remove(gather));
+        assert!(!relations.contains(gather));
+        assert_eq!(relations.entities(), &[deliver]);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{
+    component::Component,
+    entity::Entity,
+    system::{Command, Commands},
+    world::World,
+};
+use bevy_ecs_macros::all_tuples;
+use bevy_utils::tracing::warn;
+use std::marker::PhantomData;
+
+/// A tuple of [`Component`]s that [`World::respawn_keeping`] carries over from a despawned entity
+/// to the entity replacing it.
+///
+/// Each component is moved on its own, so the components that the despawned entity doesn't have
+/// are simply skipped.
+pub trait KeepComponents: Send + Sync + 'static {
+    /// Moves the components of this set that `from` has to `to`.
+    fn move_components(world: &mut World, from: Entity, to: Entity);
+}
+
+macro_rules! impl_keep_components {
+    ($($name: ident),*) => {
+        impl<$($name: Component),*> KeepComponents for ($($name,)*) {
+            #[allow(unused_variables)]
+            fn move_components(world: &mut World, from: Entity, to: Entity) {
+                $(
+                    if let Some(component) = world.entity_mut(from).remove::<$name>() {
+                        world.entity_mut(to).insert(component);
+                    }
+                )*
+            }
+        }
+    };
+}
+
+all_tuples!(impl_keep_components, 0, 15, C);
+
+impl World {
+    /// Despawns `entity` and spawns a new entity with the components of `K` that `entity` had,
+    /// returning the new entity. The other components of `entity` are dropped.
+    ///
+    /// Returns [`None`] and spawns nothing if `entity` doesn't exist.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Score(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn().insert_bundle((Score(10), Health(0))).id();
+    ///
+    /// let respawned = world.respawn_keeping::<(Score,)>(player).unwrap();
+    /// assert!(world.get_entity(player).is_none());
+    /// assert_eq!(world.get::<Score>(respawned).unwrap().0, 10);
+    /// assert!(world.get::<Health>(respawned).is_none());
+    /// ```
+    pub fn respawn_keeping<K: KeepComponents>(&mut self, entity: Entity) -> Option<Entity> {
+        self.get_entity(entity)?;
+        let respawned = self.spawn().id();
+        K::move_components(self, entity, respawned);
+        self.despawn(entity);
+        Some(respawned)
+    }
+}
+
+impl<'w, 's> Commands<'w, 's> {
+    /// Despawns `entity` and spawns a new entity with the components of `K` that `entity` had,
+    /// like [`World::respawn_keeping`].
+    ///
+    /// The new entity is reserved right away and returned, and gets its components when the
+    /// commands are applied.
+    pub fn respawn_keeping<K: KeepComponents>(&mut self, entity: Entity) -> Entity {
+        let respawned = self.spawn().id();
+        self.add(RespawnKeeping::<K> {
+            entity,
+            respawned,
+            marker: PhantomData,
+        });
+        respawned
+    }
+}
+
+/// A [`Command`] that despawns an entity, carrying over the components of `K` it has to a
+/// reserved entity.
+#[derive(Debug)]
+pub struct RespawnKeeping<K> {
+    pub entity: Entity,
+    pub respawned: Entity,
+    pub marker: PhantomData<K>,
+}
+
+impl<K: KeepComponents> Command for RespawnKeeping<K> {
+    fn write(self, world: &mut World) {
+        if world.get_entity(self.entity).is_some() {
+            K::move_components(world, self.entity, self.respawned);
+            world.despawn(self.entity);
+        } else {
+            warn!(
+                "error[B0003]: Could not respawn entity {:?} because it doesn't exist in this World.",
+                self.entity
+            );
+            world.despawn(self.respawned);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        system::{CommandQueue, Commands},
+        world::World,
+    };
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Name(&'static str);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct Score(u32);
+
+    #[derive(Component)]
+    struct Health;
+
+    #[test]
+    fn respawn_keeping_components() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let player = world.spawn().insert_bundle((Name("player"), Health)).id();
+
+        // The player has no `Score`, so only its `Name` is carried over.
+        let respawned = Commands::new(&mut queue, &world).respawn_keeping::<(Name, Score)>(player);
+        assert_ne!(respawned, player);
+        queue.apply(&mut world);
+
+        assert!(world.get_entity(player).is_none());
+        assert_eq!(world.get::<Name>(respawned), Some(&Name("player")));
+        assert_eq!(world.get::<Score>(respawned), None);
+        assert!(world.get::<Health>(respawned).is_none());
+
+        world.entity_mut(respawned).insert(Score(3));
+        let again = world.respawn_keeping::<(Name, Score)>(respawned).unwrap();
+        assert_eq!(world.get::<Name>(again), Some(&Name("player")));
+        assert_eq!(world.get::<Score>(again), Some(&Score(3)));
+        assert_eq!(world.entities().len(), 1);
+        assert_eq!(world.respawn_keeping::<(Name,)>(respawned), None);
+    }
+}
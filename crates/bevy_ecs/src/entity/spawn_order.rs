@@ -0,0 +1,152 @@
+use crate as bevy_ecs;
+use crate::{
+    component::Component,
+    entity::Entity,
+    system::{Command, Commands, EntityCommands},
+    world::{EntityMut, World},
+};
+
+/// A component recording when an entity was spawned, relative to the other entities of its
+/// [`World`] that have one.
+///
+/// Queries iterate entities in archetype order, which is not the order they were spawned in. For
+/// systems that want to process entities in spawn order, spawn them with
+/// [`World::spawn_ordered`] or [`Commands::spawn_ordered`], and iterate them with
+/// [`Query::iter_spawn_order`](crate::system::Query::iter_spawn_order).
+///
+/// Each world keeps its own counter, which only increases. Entities spawned with commands get
+/// their `SpawnOrder` when the commands are applied, in the order they were queued.
+///
+/// ```
+/// # use bevy_ecs::{entity::SpawnOrder, prelude::*};
+/// #[derive(Component)]
+/// struct Name(&'static str);
+///
+/// #[derive(Component)]
+/// struct Boss;
+///
+/// let mut world = World::new();
+/// world.spawn_ordered().insert_bundle((Name("boss"), Boss));
+/// world.spawn_ordered().insert(Name("minion"));
+///
+/// let mut query = world.query::<(&SpawnOrder, &Name)>();
+/// let mut names: Vec<_> = query.iter(&world).collect();
+/// names.sort_by_key(|(order, _)| **order);
+/// assert_eq!(names[0].1 .0, "boss");
+/// assert_eq!(names[1].1 .0, "minion");
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpawnOrder(u64);
+
+impl SpawnOrder {
+    /// Returns the position of this entity in its world's spawn order.
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(Default)]
+struct SpawnOrderCounter(u64);
+
+impl World {
+    /// Spawns a new [`Entity`] with a [`SpawnOrder`] component and returns a corresponding
+    /// [`EntityMut`], which can be used to add components to the entity.
+    pub fn spawn_ordered(&mut self) -> EntityMut<'_> {
+        let order = self.next_spawn_order();
+        let mut entity_mut = self.spawn();
+        entity_mut.insert(order);
+        entity_mut
+    }
+
+    fn next_spawn_order(&mut self) -> SpawnOrder {
+        let mut counter = self.get_resource_or_insert_with(SpawnOrderCounter::default);
+        let order = SpawnOrder(counter.0);
+        counter.0 += 1;
+        order
+    }
+}
+
+impl<'w, 's> Commands<'w, 's> {
+    /// Creates a new entity with a [`SpawnOrder`] component and returns an [`EntityCommands`]
+    /// builder for it.
+    ///
+    /// The [`SpawnOrder`] is inserted when the commands are applied.
+    pub fn spawn_ordered<'a>(&'a mut self) -> EntityCommands<'w, 's, 'a> {
+        let entity = self.spawn().id();
+        self.add(InsertSpawnOrder { entity });
+        self.entity(entity)
+    }
+}
+
+/// A [`Command`] that inserts the next [`SpawnOrder`] of the world on an entity.
+#[derive(Debug)]
+pub struct InsertSpawnOrder {
+    pub entity: Entity,
+}
+
+impl Command for InsertSpawnOrder {
+    fn write(self, world: &mut World) {
+        let order = world.next_spawn_order();
+        if let Some(mut entity) = world.get_entity_mut(self.entity) {
+            entity.insert(order);
+        } else {
+            panic!("error[B0003]: Could not add a component (of type `{}`) to entity {:?} because it doesn't exist in this World.", std::any::type_name::<SpawnOrder>(), self.entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpawnOrder;
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        schedule::{Stage, SystemStage},
+        system::{Commands, Query, Res, ResMut},
+        world::World,
+    };
+
+    #[derive(Component)]
+    struct Index(usize);
+
+    #[derive(Component)]
+    struct Marker;
+
+    struct Frame(usize);
+
+    #[test]
+    fn iter_spawn_order_across_frames() {
+        fn spawn(mut commands: Commands, frame: Res<Frame>) {
+            let index = frame.0 * 2;
+            // Alternate archetypes, so that archetype order isn't spawn order.
+            commands
+                .spawn_ordered()
+                .insert_bundle((Index(index), Marker));
+            commands.spawn_ordered().insert(Index(index + 1));
+        }
+
+        fn check(query: Query<(&SpawnOrder, &Index)>, mut frame: ResMut<Frame>) {
+            let indices = query
+                .iter_spawn_order()
+                .map(|(_, index)| index.0)
+                .collect::<Vec<_>>();
+            assert_eq!(indices, (0..indices.len()).collect::<Vec<_>>());
+            frame.0 += 1;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Frame(0));
+        let mut spawn_stage = SystemStage::single_threaded().with_system(spawn);
+        let mut check_stage = SystemStage::single_threaded().with_system(check);
+        for _ in 0..4 {
+            spawn_stage.run(&mut world);
+            check_stage.run(&mut world);
+        }
+        assert_eq!(world.resource::<Frame>().0, 4);
+
+        // Entities spawned directly share the counter.
+        let entity = world.spawn_ordered().insert(Index(8)).id();
+        assert_eq!(world.get::<SpawnOrder>(entity).unwrap().get(), 8);
+        check_stage.run(&mut world);
+    }
+}
@@ -0,0 +1,174 @@
+//! Change detection for groups of components.
+//!
+//! Implement [`ComponentGroup`] for a marker type to declare a group of component types, and add
+//! the [`update_group`] system for it. Entities with any member of the group then get a
+//! [`Group`] component, changed whenever any member is, so that a single
+//! `Changed<Group<G>>` filter replaces a long `Or` of `Changed<T>` filters, for example to find
+//! the entities whose replicated components changed.
+
+use crate::{
+    change_detection::DetectChanges,
+    component::{Component, TableStorage},
+    entity::Entity,
+    query::{Changed, Or, WorldQuery},
+    system::{Commands, Query},
+};
+use bevy_ecs_macros::all_tuples;
+use std::marker::PhantomData;
+
+/// A group of component types whose changes are detected together, with the [`Group`]
+/// component.
+///
+/// ```
+/// # use bevy_ecs::{group::{ComponentGroup, Group}, prelude::*};
+/// #[derive(Component)]
+/// struct Position(f32);
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// struct Replicated;
+///
+/// impl ComponentGroup for Replicated {
+///     type Members = (Position, Health);
+/// }
+///
+/// fn send_deltas(query: Query<Entity, Changed<Group<Replicated>>>) {
+///     for entity in query.iter() {
+///         println!("{:?} needs to be sent again", entity);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(send_deltas);
+/// ```
+pub trait ComponentGroup: Send + Sync + 'static {
+    /// The components of the group, as a tuple.
+    type Members: GroupMembers;
+}
+
+/// A tuple of component types, the members of a [`ComponentGroup`].
+pub trait GroupMembers: Send + Sync + 'static {
+    /// The filter matching entities with a member that changed.
+    type Changed: WorldQuery;
+}
+
+macro_rules! impl_group_members {
+    ($($name: ident),*) => {
+        impl<$($name: Component),*> GroupMembers for ($($name,)*) {
+            type Changed = Or<($(Changed<$name>,)*)>;
+        }
+    };
+}
+
+all_tuples!(impl_group_members, 1, 15, C);
+
+/// A component that [`update_group::<G>`] changes whenever a member of the [`ComponentGroup`]
+/// `G` of the same entity is added or changed.
+///
+/// Whenever a member changed since [`update_group::<G>`] last ran, that system marks the group as
+/// changed, at its own change tick rather than the tick of the member change. So
+/// `Changed<Group<G>>` and `Added<Group<G>>` filters only see member changes made before that
+/// run, and see them as changes made by [`update_group::<G>`].
+pub struct Group<G>(PhantomData<fn() -> G>);
+
+impl<G: ComponentGroup> Component for Group<G> {
+    type Storage = TableStorage;
+}
+
+impl<G> Default for Group<G> {
+    fn default() -> Self {
+        Group(PhantomData)
+    }
+}
+
+impl<G> std::fmt::Debug for Group<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Group")
+            .field(&std::any::type_name::<G>())
+            .finish()
+    }
+}
+
+/// The query of [`update_group`], over the entities with a changed member of `G`.
+type ChangedMembers<'w, 's, G> = Query<
+    'w,
+    's,
+    (Entity, Option<&'static mut Group<G>>),
+    <<G as ComponentGroup>::Members as GroupMembers>::Changed,
+>;
+
+/// Changes the [`Group<G>`] of every entity with a member of the [`ComponentGroup`] `G` that was
+/// added or changed since the last run, inserting it if the entity doesn't have one yet.
+///
+/// This should run after the systems that change the members, and before the systems filtering on
+/// `Changed<Group<G>>`. Inserted [`Group<G>`] components are only visible once the stage's
+/// commands are applied.
+pub fn update_group<G: ComponentGroup>(mut commands: Commands, mut query: ChangedMembers<G>) {
+    for (entity, group) in query.iter_mut() {
+        if let Some(mut group) = group {
+            group.set_changed();
+        } else {
+            commands.entity(entity).insert(Group::<G>::default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_group, ComponentGroup, Group};
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        entity::Entity,
+        query::Changed,
+        schedule::{Stage, SystemStage},
+        system::{Query, SystemState},
+        world::World,
+    };
+
+    #[derive(Component)]
+    struct Position(u32);
+    #[derive(Component)]
+    struct Health(u32);
+    #[derive(Component)]
+    struct Local;
+
+    struct Replicated;
+
+    impl ComponentGroup for Replicated {
+        type Members = (Position, Health);
+    }
+
+    #[test]
+    fn member_change_flags_group() {
+        let mut world = World::new();
+        let both = world.spawn().insert(Position(0)).insert(Health(10)).id();
+        let position = world.spawn().insert(Position(0)).id();
+        world.spawn().insert(Local);
+
+        let mut stage = SystemStage::single_threaded().with_system(update_group::<Replicated>);
+        let mut changed = SystemState::<Query<Entity, Changed<Group<Replicated>>>>::new(&mut world);
+        let mut changed_entities = |world: &World| {
+            let mut entities = changed.get(world).iter().collect::<Vec<_>>();
+            entities.sort();
+            entities
+        };
+
+        // Adding members adds the group.
+        stage.run(&mut world);
+        assert_eq!(changed_entities(&world), vec![both, position]);
+
+        stage.run(&mut world);
+        assert_eq!(changed_entities(&world), vec![]);
+
+        // Changing a single member changes the group.
+        world.get_mut::<Health>(both).unwrap().0 -= 1;
+        stage.run(&mut world);
+        assert_eq!(changed_entities(&world), vec![both]);
+        assert_eq!(world.get::<Health>(both).unwrap().0, 9);
+
+        world.get_mut::<Position>(position).unwrap().0 += 1;
+        world.get_mut::<Position>(both).unwrap().0 += 1;
+        stage.run(&mut world);
+        assert_eq!(changed_entities(&world), vec![both, position]);
+        assert_eq!(world.get::<Position>(position).unwrap().0, 1);
+    }
+}
@@ -10,12 +10,15 @@ pub mod change_detection;
 pub mod component;
 pub mod entity;
 pub mod event;
+pub mod group;
 pub mod query;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
 pub mod schedule;
 pub mod storage;
 pub mod system;
+pub mod task;
+pub mod tracked;
 pub mod world;
 
 pub use bevy_ptr as ptr;
@@ -28,9 +31,9 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         bundle::Bundle,
-        change_detection::DetectChanges,
+        change_detection::{DetectChanges, Ref},
         component::Component,
-        entity::Entity,
+        entity::{Disabled, Entity},
         event::{EventReader, EventWriter, Events},
         query::{Added, AnyOf, ChangeTrackers, Changed, Or, QueryState, With, Without},
         schedule::{
@@ -40,8 +43,8 @@ pub mod prelude {
         },
         system::{
             Commands, In, IntoChainSystem, IntoExclusiveSystem, IntoSystem, Local, NonSend,
-            NonSendMut, ParallelCommands, ParamSet, Query, RemovedComponents, Res, ResMut, System,
-            SystemParamFunction,
+            NonSendMut, ParallelCommands, ParamSet, Query, RemovedComponents, RemovedResource, Res,
+            ResMut, System, SystemParamFunction,
         },
         world::{FromWorld, Mut, World},
     };
@@ -418,6 +421,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn par_iter_combinations_mut() {
+        ComputeTaskPool::init(TaskPool::default);
+        #[derive(Component)]
+        struct Partners(Vec<usize>);
+
+        let mut world = World::new();
+        let count = 41;
+        for i in 0..count {
+            world.spawn().insert_bundle((A(i), Partners(Vec::new())));
+        }
+        // Filtered out.
+        world
+            .spawn()
+            .insert_bundle((A(count), Partners(Vec::new()), B(0)));
+
+        let visited = AtomicUsize::new(0);
+        world
+            .query_filtered::<(&A, &mut Partners), Without<B>>()
+            .par_iter_combinations_mut(
+                &mut world,
+                4,
+                |[(a, mut a_partners), (b, mut b_partners)]| {
+                    a_partners.0.push(b.0);
+                    b_partners.0.push(a.0);
+                    visited.fetch_add(1, Ordering::Relaxed);
+                },
+            );
+
+        assert_eq!(visited.load(Ordering::Relaxed), count * (count - 1) / 2);
+        for (a, partners) in world.query::<(&A, &Partners)>().iter(&world) {
+            if a.0 == count {
+                assert!(partners.0.is_empty());
+                continue;
+            }
+            // Each entity met every other entity exactly once.
+            let mut partners = partners.0.clone();
+            partners.sort_unstable();
+            let expected = (0..count).filter(|i| *i != a.0).collect::<Vec<_>>();
+            assert_eq!(partners, expected);
+        }
+    }
+
     #[test]
     fn query_missing_component() {
         let mut world = World::new();
@@ -1001,6 +1047,57 @@ mod tests {
         assert_eq!(get_changed(&mut world), vec![e1]);
     }
 
+    #[test]
+    fn changed_query_skips_unchanged_storage() {
+        let mut world = World::default();
+        let e1 = world.spawn().insert(A(0)).id();
+        let e2 = world.spawn().insert_bundle((A(0), B(0))).id();
+        let e3 = world.spawn().insert_bundle((A(0), SparseStored(0))).id();
+
+        fn get_filtered<F: WorldQuery>(world: &mut World) -> Vec<Entity> {
+            let mut query = world.query_filtered::<Entity, F>();
+            let mut for_each = Vec::new();
+            query.for_each(world, |entity| for_each.push(entity));
+            let iter = query.iter(world).collect::<Vec<Entity>>();
+            assert_eq!(iter, for_each);
+            iter
+        }
+
+        world.clear_trackers();
+        assert!(get_filtered::<Changed<A>>(&mut world).is_empty());
+        assert!(get_filtered::<Changed<SparseStored>>(&mut world).is_empty());
+
+        // only the (A, B) table has changes
+        world.get_mut::<A>(e2).unwrap().0 = 1;
+        assert_eq!(get_filtered::<Changed<A>>(&mut world), vec![e2]);
+        assert_eq!(
+            get_filtered::<Or<(Changed<A>, Changed<SparseStored>)>>(&mut world),
+            vec![e2]
+        );
+
+        // moving a changed entity into an empty table keeps it changed
+        world.entity_mut(e2).insert(C);
+        assert_eq!(get_filtered::<Changed<A>>(&mut world), vec![e2]);
+
+        // moving an unchanged entity keeps it unchanged
+        world.clear_trackers();
+        world.entity_mut(e1).insert(C);
+        assert!(get_filtered::<Changed<A>>(&mut world).is_empty());
+
+        // moving a changed entity into a table without changes
+        world.get_mut::<A>(e3).unwrap().0 = 1;
+        world.entity_mut(e3).remove::<SparseStored>();
+        world.entity_mut(e3).insert_bundle((B(0), C));
+        assert_eq!(get_filtered::<Changed<A>>(&mut world), vec![e3]);
+        assert_eq!(get_filtered::<Added<B>>(&mut world), vec![e3]);
+
+        world.clear_trackers();
+        world.entity_mut(e1).insert(SparseStored(1));
+        assert_eq!(get_filtered::<Changed<SparseStored>>(&mut world), vec![e1]);
+        assert_eq!(get_filtered::<Added<SparseStored>>(&mut world), vec![e1]);
+        assert!(get_filtered::<Changed<A>>(&mut world).is_empty());
+    }
+
     #[test]
     fn resource() {
         let mut world = World::default();
@@ -1333,6 +1430,23 @@ mod tests {
         assert_eq!(*world.resource::<i32>(), 1);
     }
 
+    #[test]
+    fn resource_scope_spawn() {
+        let mut world = World::default();
+        world.insert_resource::<Vec<Entity>>(Vec::new());
+        world.resource_scope(|world: &mut World, mut spawned: Mut<Vec<Entity>>| {
+            for i in 0..3 {
+                spawned.push(world.spawn().insert(A(i)).id());
+            }
+        });
+
+        let spawned = world.resource::<Vec<Entity>>().clone();
+        assert_eq!(spawned.len(), 3);
+        for (i, entity) in spawned.into_iter().enumerate() {
+            assert_eq!(world.get::<A>(entity), Some(&A(i)));
+        }
+    }
+
     #[test]
     fn insert_overwrite_drop() {
         let (dropck1, dropped1) = DropCk::new_pair();
@@ -227,6 +227,18 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.without.insert(index.sparse_set_index());
     }
 
+    /// Returns `true` if this only matches combinations where the element given by `index` is
+    /// present.
+    pub fn has_with(&self, index: T) -> bool {
+        self.with.contains(index.sparse_set_index())
+    }
+
+    /// Returns `true` if this only matches combinations where the element given by `index` is
+    /// not present.
+    pub fn has_without(&self, index: T) -> bool {
+        self.without.contains(index.sparse_set_index())
+    }
+
     pub fn extend_intersect_filter(&mut self, other: &FilteredAccess<T>) {
         self.without.intersect_with(&other.without);
         self.with.intersect_with(&other.with);
@@ -1,7 +1,9 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId},
-    change_detection::Ticks,
-    component::{Component, ComponentId, ComponentStorage, ComponentTicks, StorageType},
+    change_detection::{Ref, Ticks},
+    component::{
+        ColumnChangeTick, Component, ComponentId, ComponentStorage, ComponentTicks, StorageType,
+    },
     entity::Entity,
     query::{debug_checked_unreachable, Access, FilteredAccess},
     storage::{ComponentSparseSet, Table, Tables},
@@ -30,6 +32,8 @@ use std::{cell::UnsafeCell, marker::PhantomData};
 /// - `(WQ1, WQ2, ...)`: Queries all contained world queries allowing to query for more than one thing.
 ///     This is the `And` operator for filters. See [`Or`].
 /// - `ChangeTrackers<C>`: See the docs of [`ChangeTrackers`].
+/// - [`Ref<C>`](Ref): Queries immutably for the component `C`, along with whether it was added or
+///   changed since the system last ran.
 /// - [`Entity`]: Using the entity type as a world query will grant access to the entity that is
 ///     being queried for. See [`Entity`].
 ///
@@ -262,6 +266,47 @@ use std::{cell::UnsafeCell, marker::PhantomData};
 /// # bevy_ecs::system::assert_is_system(my_system);
 /// ```
 ///
+/// ## Read-only projections
+///
+/// Since the fields of the generated item struct keep their visibility, a derived query with
+/// private fields can expose a read-only view of a component through methods on its item. This
+/// lets a library hand out only part of a component, while the query still records read access
+/// to the whole underlying component, so the scheduler treats it exactly like `&Transform`.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// mod physics {
+///     # use bevy_ecs::prelude::*;
+///     use bevy_ecs::query::WorldQuery;
+///
+///     #[derive(Component)]
+///     pub struct Transform {
+///         pub translation: [f32; 3],
+///         pub scale: f32,
+///     }
+///
+///     /// Reads the translation of a [`Transform`], and nothing else.
+///     #[derive(WorldQuery)]
+///     pub struct TransformReader {
+///         transform: &'static Transform,
+///     }
+///
+///     impl<'w> TransformReaderItem<'w> {
+///         pub fn translation(&self) -> [f32; 3] {
+///             self.transform.translation
+///         }
+///     }
+/// }
+///
+/// fn my_system(query: Query<physics::TransformReader>) {
+///     for transform in query.iter() {
+///         println!("{:?}", transform.translation());
+///     }
+/// }
+///
+/// # bevy_ecs::system::assert_is_system(my_system);
+/// ```
+///
 /// ## Ignored fields
 ///
 /// The macro also supports `ignore` attribute for struct members. Fields marked with this attribute
@@ -319,6 +364,11 @@ use std::{cell::UnsafeCell, marker::PhantomData};
 /// # Safety
 ///
 /// component access of `ROQueryFetch<Self>` should be a subset of `QueryFetch<Self>`
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not valid to request as data in a `Query`",
+    label = "invalid `Query` data",
+    note = "query data is a reference to a component, such as `&T` or `&mut T`, or a tuple of query data"
+)]
 pub unsafe trait WorldQuery: for<'w> WorldQueryGats<'w, _State = Self::State> {
     type ReadOnly: ReadOnlyWorldQuery<State = Self::State>;
     type State: FetchState;
@@ -453,6 +503,18 @@ pub unsafe trait Fetch<'world>: Sized {
         true
     }
 
+    /// Returns `false` if no entity in the current [`Table`] or [`Archetype`] can pass
+    /// [`Fetch::table_filter_fetch`] or [`Fetch::archetype_filter_fetch`], which lets query
+    /// iteration skip it entirely. Returning `true` is always correct.
+    ///
+    /// # Safety
+    ///
+    /// Must always be called _after_ [`Fetch::set_table`] or [`Fetch::set_archetype`].
+    #[inline]
+    unsafe fn may_pass_filter(&self) -> bool {
+        true
+    }
+
     // This does not have a default body of `{}` because 99% of cases need to add accesses
     // and forgetting to do so would be unsound.
     fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>);
@@ -749,6 +811,183 @@ unsafe impl<'w, T: Component> Fetch<'w> for ReadFetch<'w, T> {
     }
 }
 
+/// SAFETY: `ROQueryFetch<Self>` is the same as `QueryFetch<Self>`
+unsafe impl<'a, T: Component> WorldQuery for Ref<'a, T> {
+    type ReadOnly = Self;
+    type State = ComponentIdState<T>;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: QueryItem<'wlong, Self>) -> QueryItem<'wshort, Self> {
+        item
+    }
+}
+
+/// The [`Fetch`] of [`Ref`].
+#[doc(hidden)]
+pub struct RefFetch<'w, T> {
+    // T::Storage = TableStorage
+    table_components: Option<ThinSlicePtr<'w, UnsafeCell<T>>>,
+    table_ticks: Option<ThinSlicePtr<'w, UnsafeCell<ComponentTicks>>>,
+    entity_table_rows: Option<ThinSlicePtr<'w, usize>>,
+    // T::Storage = SparseStorage
+    entities: Option<ThinSlicePtr<'w, Entity>>,
+    sparse_set: Option<&'w ComponentSparseSet>,
+
+    last_change_tick: u32,
+    change_tick: u32,
+}
+
+impl<T> Clone for RefFetch<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            table_components: self.table_components,
+            table_ticks: self.table_ticks,
+            entity_table_rows: self.entity_table_rows,
+            entities: self.entities,
+            sparse_set: self.sparse_set,
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+        }
+    }
+}
+
+/// SAFETY: access is read only
+unsafe impl<'a, T: Component> ReadOnlyWorldQuery for Ref<'a, T> {}
+
+impl<'w, 'a, T: Component> WorldQueryGats<'w> for Ref<'a, T> {
+    type Fetch = RefFetch<'w, T>;
+    type _State = ComponentIdState<T>;
+}
+
+// SAFETY: component access and archetype component access are properly updated to reflect that T is
+// read
+unsafe impl<'w, T: Component> Fetch<'w> for RefFetch<'w, T> {
+    type Item = Ref<'w, T>;
+    type State = ComponentIdState<T>;
+
+    const IS_DENSE: bool = {
+        match T::Storage::STORAGE_TYPE {
+            StorageType::Table => true,
+            StorageType::SparseSet => false,
+        }
+    };
+
+    const IS_ARCHETYPAL: bool = true;
+
+    unsafe fn init(
+        world: &'w World,
+        state: &ComponentIdState<T>,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> RefFetch<'w, T> {
+        RefFetch {
+            table_components: None,
+            table_ticks: None,
+            entity_table_rows: None,
+            entities: None,
+            sparse_set: (T::Storage::STORAGE_TYPE == StorageType::SparseSet).then(|| {
+                world
+                    .storages()
+                    .sparse_sets
+                    .get(state.component_id)
+                    .unwrap()
+            }),
+            last_change_tick,
+            change_tick,
+        }
+    }
+
+    #[inline]
+    unsafe fn set_archetype(
+        &mut self,
+        state: &Self::State,
+        archetype: &'w Archetype,
+        tables: &'w Tables,
+    ) {
+        match T::Storage::STORAGE_TYPE {
+            StorageType::Table => {
+                self.entity_table_rows = Some(archetype.entity_table_rows().into());
+                let column = tables[archetype.table_id()]
+                    .get_column(state.component_id)
+                    .unwrap();
+                self.table_components = Some(column.get_data_slice().into());
+                self.table_ticks = Some(column.get_ticks_slice().into());
+            }
+            StorageType::SparseSet => self.entities = Some(archetype.entities().into()),
+        }
+    }
+
+    #[inline]
+    unsafe fn set_table(&mut self, state: &Self::State, table: &'w Table) {
+        let column = table.get_column(state.component_id).unwrap();
+        self.table_components = Some(column.get_data_slice().into());
+        self.table_ticks = Some(column.get_ticks_slice().into());
+    }
+
+    #[inline]
+    unsafe fn archetype_fetch(&mut self, archetype_index: usize) -> Self::Item {
+        match T::Storage::STORAGE_TYPE {
+            StorageType::Table => {
+                let entity_table_rows = self
+                    .entity_table_rows
+                    .unwrap_or_else(|| debug_checked_unreachable());
+                let table_row = *entity_table_rows.get(archetype_index);
+                self.table_fetch(table_row)
+            }
+            StorageType::SparseSet => {
+                let (entities, sparse_set) = self
+                    .entities
+                    .zip(self.sparse_set)
+                    .unwrap_or_else(|| debug_checked_unreachable());
+                let entity = *entities.get(archetype_index);
+                let (component, component_ticks) = sparse_set
+                    .get_with_ticks(entity)
+                    .unwrap_or_else(|| debug_checked_unreachable());
+                Ref {
+                    value: component.deref::<T>(),
+                    component_ticks: component_ticks.deref(),
+                    last_change_tick: self.last_change_tick,
+                    change_tick: self.change_tick,
+                }
+            }
+        }
+    }
+
+    #[inline]
+    unsafe fn table_fetch(&mut self, table_row: usize) -> Self::Item {
+        let (table_components, table_ticks) = self
+            .table_components
+            .zip(self.table_ticks)
+            .unwrap_or_else(|| debug_checked_unreachable());
+        Ref {
+            value: table_components.get(table_row).deref(),
+            component_ticks: table_ticks.get(table_row).deref(),
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+        }
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        assert!(
+            !access.access().has_write(state.component_id),
+            "Ref<{}> conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+                std::any::type_name::<T>(),
+        );
+        access.add_read(state.component_id);
+    }
+
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        if let Some(archetype_component_id) =
+            archetype.get_archetype_component_id(state.component_id)
+        {
+            access.add_read(archetype_component_id);
+        }
+    }
+}
+
 /// SAFETY: access of `&T` is a subset of `&mut T`
 unsafe impl<'w, T: Component> WorldQuery for &'w mut T {
     type ReadOnly = &'w T;
@@ -769,6 +1008,8 @@ pub struct WriteFetch<'w, T> {
     // T::Storage = SparseStorage
     entities: Option<ThinSlicePtr<'w, Entity>>,
     sparse_set: Option<&'w ComponentSparseSet>,
+    // the change tick of the current table's column or of the sparse set
+    column_tick: Option<&'w ColumnChangeTick>,
 
     last_change_tick: u32,
     change_tick: u32,
@@ -782,6 +1023,7 @@ impl<T> Clone for WriteFetch<'_, T> {
             entities: self.entities,
             entity_table_rows: self.entity_table_rows,
             sparse_set: self.sparse_set,
+            column_tick: self.column_tick,
             last_change_tick: self.last_change_tick,
             change_tick: self.change_tick,
         }
@@ -826,6 +1068,7 @@ unsafe impl<'w, T: Component> Fetch<'w> for WriteFetch<'w, T> {
                     .unwrap()
             }),
             table_ticks: None,
+            column_tick: None,
             last_change_tick,
             change_tick,
         }
@@ -846,8 +1089,12 @@ unsafe impl<'w, T: Component> Fetch<'w> for WriteFetch<'w, T> {
                     .unwrap();
                 self.table_components = Some(column.get_data_slice().into());
                 self.table_ticks = Some(column.get_ticks_slice().into());
+                self.column_tick = Some(column.change_tick());
+            }
+            StorageType::SparseSet => {
+                self.entities = Some(archetype.entities().into());
+                self.column_tick = self.sparse_set.map(ComponentSparseSet::change_tick);
             }
-            StorageType::SparseSet => self.entities = Some(archetype.entities().into()),
         }
     }
 
@@ -856,6 +1103,7 @@ unsafe impl<'w, T: Component> Fetch<'w> for WriteFetch<'w, T> {
         let column = table.get_column(state.component_id).unwrap();
         self.table_components = Some(column.get_data_slice().into());
         self.table_ticks = Some(column.get_ticks_slice().into());
+        self.column_tick = Some(column.change_tick());
     }
 
     #[inline]
@@ -871,6 +1119,7 @@ unsafe impl<'w, T: Component> Fetch<'w> for WriteFetch<'w, T> {
                     value: table_components.get(table_row).deref_mut(),
                     ticks: Ticks {
                         component_ticks: table_ticks.get(table_row).deref_mut(),
+                        column_tick: self.column_tick,
                         change_tick: self.change_tick,
                         last_change_tick: self.last_change_tick,
                     },
@@ -889,6 +1138,7 @@ unsafe impl<'w, T: Component> Fetch<'w> for WriteFetch<'w, T> {
                     value: component.assert_unique().deref_mut(),
                     ticks: Ticks {
                         component_ticks: component_ticks.deref_mut(),
+                        column_tick: self.column_tick,
                         change_tick: self.change_tick,
                         last_change_tick: self.last_change_tick,
                     },
@@ -907,6 +1157,7 @@ unsafe impl<'w, T: Component> Fetch<'w> for WriteFetch<'w, T> {
             value: table_components.get(table_row).deref_mut(),
             ticks: Ticks {
                 component_ticks: table_ticks.get(table_row).deref_mut(),
+                column_tick: self.column_tick,
                 change_tick: self.change_tick,
                 last_change_tick: self.last_change_tick,
             },
@@ -1127,6 +1378,38 @@ impl<T: Component> ChangeTrackers<T> {
         self.component_ticks
             .is_changed(self.last_change_tick, self.change_tick)
     }
+
+    /// Returns true if this component was changed after the tick `start`, and at or before the
+    /// tick `end`.
+    ///
+    /// This is finer than [`Changed`](crate::query::Changed), which always covers the ticks since
+    /// the last execution of the system. For example, a system sending deltas over the network
+    /// can remember the tick of each acknowledged update, and only send the components that
+    /// changed after it:
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Transform;
+    /// struct Acknowledged {
+    ///     tick: u32,
+    ///     up_to: u32,
+    /// }
+    ///
+    /// fn send_deltas(query: Query<(Entity, ChangeTrackers<Transform>)>, ack: Res<Acknowledged>) {
+    ///     for (entity, _) in query
+    ///         .iter()
+    ///         .filter(|(_, tracker)| tracker.is_changed_between(ack.tick, ack.up_to))
+    ///     {
+    ///         println!("sending {:?}", entity);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(send_deltas);
+    /// ```
+    pub fn is_changed_between(&self, start: u32, end: u32) -> bool {
+        self.component_ticks
+            .is_changed_between(start, end, self.change_tick)
+    }
 }
 
 // SAFETY: `ROQueryFetch<Self>` is the same as `QueryFetch<Self>`
@@ -1408,6 +1691,12 @@ macro_rules! impl_tuple_fetch {
                 true $(&& $name.archetype_filter_fetch(archetype_index))*
             }
 
+            #[inline]
+            unsafe fn may_pass_filter(&self) -> bool {
+                let ($($name,)*) = self;
+                true $(&& $name.may_pass_filter())*
+            }
+
             fn update_component_access(state: &Self::State, _access: &mut FilteredAccess<ComponentId>) {
                 let ($($name,)*) = state;
                 $($name::update_component_access($name, _access);)*
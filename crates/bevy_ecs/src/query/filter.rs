@@ -1,6 +1,8 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId},
-    component::{Component, ComponentId, ComponentStorage, ComponentTicks, StorageType},
+    component::{
+        ColumnChangeTick, Component, ComponentId, ComponentStorage, ComponentTicks, StorageType,
+    },
     entity::Entity,
     query::{
         debug_checked_unreachable, Access, Fetch, FetchState, FilteredAccess, QueryFetch,
@@ -429,6 +431,12 @@ macro_rules! impl_query_filter_tuple {
                 self.archetype_fetch(archetype_index)
             }
 
+            #[inline]
+            unsafe fn may_pass_filter(&self) -> bool {
+                let ($($filter,)*) = &self.0;
+                false $(|| ($filter.matches && $filter.fetch.may_pass_filter()))*
+            }
+
             fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
                 let ($($filter,)*) = &state.0;
 
@@ -508,6 +516,7 @@ macro_rules! impl_tick_filter {
             marker: PhantomData<T>,
             entities: Option<ThinSlicePtr<'w, Entity>>,
             sparse_set: Option<&'w ComponentSparseSet>,
+            column_tick: Option<&'w ColumnChangeTick>,
             last_change_tick: u32,
             change_tick: u32,
         }
@@ -559,6 +568,7 @@ macro_rules! impl_tick_filter {
                     entity_table_rows: None,
                     sparse_set: (T::Storage::STORAGE_TYPE == StorageType::SparseSet)
                         .then(|| world.storages().sparse_sets.get(state.component_id).unwrap()),
+                    column_tick: None,
                     marker: PhantomData,
                     last_change_tick,
                     change_tick,
@@ -575,17 +585,23 @@ macro_rules! impl_tick_filter {
             const IS_ARCHETYPAL:  bool = false;
 
             unsafe fn set_table(&mut self, state: &Self::State, table: &'w Table) {
-                self.table_ticks = Some(table.get_column(state.component_id).unwrap().get_ticks_slice().into());
+                let column = table.get_column(state.component_id).unwrap();
+                self.table_ticks = Some(column.get_ticks_slice().into());
+                self.column_tick = Some(column.change_tick());
             }
 
             unsafe fn set_archetype(&mut self, state: &Self::State, archetype: &'w Archetype, tables: &'w Tables) {
                 match T::Storage::STORAGE_TYPE {
                     StorageType::Table => {
                         self.entity_table_rows = Some(archetype.entity_table_rows().into());
-                        let table = &tables[archetype.table_id()];
-                        self.table_ticks = Some(table.get_column(state.component_id).unwrap().get_ticks_slice().into());
+                        let column = tables[archetype.table_id()].get_column(state.component_id).unwrap();
+                        self.table_ticks = Some(column.get_ticks_slice().into());
+                        self.column_tick = Some(column.change_tick());
+                    }
+                    StorageType::SparseSet => {
+                        self.entities = Some(archetype.entities().into());
+                        self.column_tick = self.sparse_set.map(ComponentSparseSet::change_tick);
                     }
-                    StorageType::SparseSet => self.entities = Some(archetype.entities().into()),
                 }
             }
 
@@ -623,6 +639,15 @@ macro_rules! impl_tick_filter {
                 self.archetype_fetch(archetype_index)
             }
 
+            #[inline]
+            unsafe fn may_pass_filter(&self) -> bool {
+                // values are always marked as changed when they are added, so the column's change
+                // tick bounds both checks
+                self.column_tick
+                    .unwrap_or_else(|| debug_checked_unreachable())
+                    .is_changed(self.last_change_tick, self.change_tick)
+            }
+
             #[inline]
             fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
                 if access.access().has_write(state.component_id) {
@@ -655,6 +680,7 @@ macro_rules! impl_tick_filter {
                     marker: self.marker.clone(),
                     entities: self.entities.clone(),
                     sparse_set: self.sparse_set.clone(),
+                    column_tick: self.column_tick.clone(),
                     last_change_tick: self.last_change_tick.clone(),
                     change_tick: self.change_tick.clone(),
                 }
@@ -702,7 +728,7 @@ impl_tick_filter!(
 
 impl_tick_filter!(
     /// A filter on a component that only retains results added or mutably dereferenced after the system last ran.
-    ///  
+    ///
     /// A common use for this filter is avoiding redundant work when values have not changed.
     ///
     /// **Note** that simply *mutably dereferencing* a component is considered a change ([`DerefMut`](std::ops::DerefMut)).
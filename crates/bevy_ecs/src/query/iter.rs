@@ -5,10 +5,186 @@ use crate::{
     query::{ArchetypeFilter, Fetch, QueryState, WorldQuery},
     storage::{TableId, Tables},
 };
-use std::{borrow::Borrow, iter::FusedIterator, marker::PhantomData, mem::MaybeUninit};
+use std::{borrow::Borrow, iter::FusedIterator, marker::PhantomData, mem::MaybeUninit, ops::Range};
 
 use super::{QueryFetch, QueryItem, ReadOnlyWorldQuery};
 
+/// The query results of a [`Query`](crate::system::Query) within a single archetype.
+///
+/// This struct is created by the [`Query::for_each_archetype`](crate::system::Query::for_each_archetype)
+/// method. It is an [`Iterator`] over the query results of the entities in
+/// [`entities`](Self::entities), in the same order.
+pub struct ArchetypeChunk<'w, Q: WorldQuery, F: WorldQuery> {
+    archetype_id: ArchetypeId,
+    entities: &'w [Entity],
+    fetch: QueryFetch<'w, Q>,
+    filter: QueryFetch<'w, F>,
+    offset: usize,
+    index: usize,
+}
+
+impl<'w, Q: WorldQuery, F: WorldQuery> ArchetypeChunk<'w, Q, F> {
+    /// # Safety
+    ///
+    /// `fetch` and `filter` must have been set to the archetype identified by `archetype_id`, whose
+    /// entities are `entities`, and no other fetch may alias the mutable data of that archetype.
+    pub(crate) unsafe fn new(
+        archetype_id: ArchetypeId,
+        entities: &'w [Entity],
+        fetch: QueryFetch<'w, Q>,
+        filter: QueryFetch<'w, F>,
+    ) -> Self {
+        Self::new_batch(archetype_id, entities, 0, fetch, filter)
+    }
+
+    /// # Safety
+    ///
+    /// `fetch` and `filter` must have been set to the archetype identified by `archetype_id`,
+    /// `entities` must be the entities of that archetype starting at index `offset`, and no other
+    /// fetch may alias the mutable data of those entities.
+    pub(crate) unsafe fn new_batch(
+        archetype_id: ArchetypeId,
+        entities: &'w [Entity],
+        offset: usize,
+        fetch: QueryFetch<'w, Q>,
+        filter: QueryFetch<'w, F>,
+    ) -> Self {
+        ArchetypeChunk {
+            archetype_id,
+            entities,
+            fetch,
+            filter,
+            offset,
+            index: 0,
+        }
+    }
+
+    /// Returns the id of the archetype of this chunk.
+    #[inline]
+    pub fn archetype_id(&self) -> ArchetypeId {
+        self.archetype_id
+    }
+
+    /// Returns every entity of the archetype, or of the batch for a chunk created by
+    /// [`QueryBatch::iter`].
+    ///
+    /// Entities rejected by a non-archetypal filter such as [`Changed`](crate::query::Changed)
+    /// are included here, but are skipped when iterating.
+    #[inline]
+    pub fn entities(&self) -> &'w [Entity] {
+        self.entities
+    }
+}
+
+impl<'w, Q: WorldQuery, F: WorldQuery> Iterator for ArchetypeChunk<'w, Q, F> {
+    type Item = QueryItem<'w, Q>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.entities.len() {
+            let index = self.offset + self.index;
+            self.index += 1;
+            // SAFETY: `index` is in bounds of the archetype that `fetch` and `filter` were set to,
+            // and each index is only fetched once.
+            unsafe {
+                if self.filter.archetype_filter_fetch(index) {
+                    return Some(self.fetch.archetype_fetch(index));
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.entities.len() - self.index))
+    }
+}
+
+impl<'w, Q: WorldQuery, F: WorldQuery> FusedIterator for ArchetypeChunk<'w, Q, F> {}
+
+/// A batch of consecutive entities matched by a read-only [`Query`](crate::system::Query),
+/// created by [`Query::batches`](crate::system::Query::batches).
+///
+/// Batches only borrow the [`World`] immutably and can be sent to other threads, so scoped tasks
+/// can each iterate over a batch with [`QueryBatch::iter`], for example to reduce query results
+/// in parallel. The tasks must be joined before the borrow of the query ends, which a task pool
+/// [`scope`](bevy_tasks::TaskPool::scope) does.
+pub struct QueryBatch<'w, 's, Q: ReadOnlyWorldQuery, F: ReadOnlyWorldQuery> {
+    world: &'w World,
+    query_state: &'s QueryState<Q, F>,
+    archetype_id: ArchetypeId,
+    range: Range<usize>,
+    last_change_tick: u32,
+    change_tick: u32,
+}
+
+impl<'w, 's, Q: ReadOnlyWorldQuery, F: ReadOnlyWorldQuery> QueryBatch<'w, 's, Q, F> {
+    /// # Safety
+    ///
+    /// `archetype_id` must be matched by `query_state`, `range` must be in bounds of its entities
+    /// and `world` must be the world of `query_state`.
+    pub(crate) unsafe fn new(
+        world: &'w World,
+        query_state: &'s QueryState<Q, F>,
+        archetype_id: ArchetypeId,
+        range: Range<usize>,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        QueryBatch {
+            world,
+            query_state,
+            archetype_id,
+            range,
+            last_change_tick,
+            change_tick,
+        }
+    }
+
+    /// Returns the id of the archetype of the entities of this batch.
+    #[inline]
+    pub fn archetype_id(&self) -> ArchetypeId {
+        self.archetype_id
+    }
+
+    /// Returns the entities of this batch.
+    ///
+    /// Entities rejected by a non-archetypal filter such as [`Changed`](crate::query::Changed)
+    /// are included here, but are skipped when iterating.
+    #[inline]
+    pub fn entities(&self) -> &'w [Entity] {
+        &self.world.archetypes[self.archetype_id].entities()[self.range.clone()]
+    }
+
+    /// Returns an [`Iterator`] over the query results of the entities of this batch.
+    pub fn iter(&self) -> ArchetypeChunk<'w, Q, F> {
+        // SAFETY: the archetype and range were checked on creation, and read-only queries don't
+        // alias mutable data.
+        unsafe {
+            self.query_state.archetype_batch_unchecked_manual(
+                self.world,
+                self.archetype_id,
+                self.range.clone(),
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+}
+
+impl<'w, 's, Q: ReadOnlyWorldQuery, F: ReadOnlyWorldQuery> Clone for QueryBatch<'w, 's, Q, F> {
+    fn clone(&self) -> Self {
+        QueryBatch {
+            world: self.world,
+            query_state: self.query_state,
+            archetype_id: self.archetype_id,
+            range: self.range.clone(),
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+        }
+    }
+}
+
 /// An [`Iterator`] over query results of a [`Query`](crate::system::Query).
 ///
 /// This struct is created by the [`Query::iter`](crate::system::Query::iter) and
@@ -496,7 +672,12 @@ where
                     // `fetch_state`/`filter_state` are the states that `fetch/filter` were initialized with
                     self.fetch.set_table(&query_state.fetch_state, table);
                     self.filter.set_table(&query_state.filter_state, table);
-                    self.current_len = table.len();
+                    // SAFETY: set_table was called prior.
+                    self.current_len = if self.filter.may_pass_filter() {
+                        table.len()
+                    } else {
+                        0
+                    };
                     self.current_index = 0;
                     continue;
                 }
@@ -526,7 +707,12 @@ where
                         .set_archetype(&query_state.fetch_state, archetype, tables);
                     self.filter
                         .set_archetype(&query_state.filter_state, archetype, tables);
-                    self.current_len = archetype.len();
+                    // SAFETY: set_archetype was called prior.
+                    self.current_len = if self.filter.may_pass_filter() {
+                        archetype.len()
+                    } else {
+                        0
+                    };
                     self.current_index = 0;
                     continue;
                 }
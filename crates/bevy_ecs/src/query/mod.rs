@@ -20,8 +20,8 @@ pub(crate) unsafe fn debug_checked_unreachable() -> ! {
 #[cfg(test)]
 mod tests {
     use super::WorldQuery;
-    use crate::prelude::{AnyOf, Entity, Or, With, Without};
-    use crate::system::{IntoSystem, Query, System};
+    use crate::prelude::{AnyOf, Disabled, Entity, Or, With, Without};
+    use crate::system::{IntoSystem, Query, ResMut, System};
     use crate::{self as bevy_ecs, component::Component, world::World};
     use std::collections::HashSet;
 
@@ -481,6 +481,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn optional_data_with_filter() {
+        let mut world = World::new();
+        let moving = world.spawn().insert_bundle((A(1), B(1))).id();
+        let still = world.spawn().insert_bundle((A(2),)).id();
+        world.spawn().insert_bundle((A(3), B(3), C(3)));
+
+        // Filters can be part of the data tuple, and yield `()`.
+        let mut query = world.query::<(Entity, &A, Option<&B>, Without<C>)>();
+        let values = query.iter(&world).collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![(moving, &A(1), Some(&B(1)), ()), (still, &A(2), None, ())]
+        );
+
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+        let c = world.init_component::<C>();
+        let access = &query.component_access;
+        assert!(access.access().has_read(a));
+        // The optional component is read, even though it isn't required.
+        assert!(access.access().has_read(b));
+        assert!(!access.has_with(b));
+        assert_eq!(access.access().writes().count(), 0);
+        // The filter only restricts the matched entities.
+        assert!(!access.access().has_read(c));
+        assert!(access.has_without(c));
+    }
+
     #[test]
     #[should_panic = "&mut bevy_ecs::query::tests::A conflicts with a previous access in this query."]
     fn self_conflicting_worldquery() {
@@ -495,6 +524,96 @@ mod tests {
         world.query::<SelfConflicting>();
     }
 
+    #[test]
+    fn for_each_archetype() {
+        let mut world = World::new();
+        let mut expected = world
+            .spawn_batch((0..3).map(|i| (A(i),)))
+            .collect::<Vec<_>>();
+        expected.extend(world.spawn_batch((3..5).map(|i| (A(i), B(i)))));
+        world.spawn().insert(B(5));
+        // Leaves an empty archetype behind, which is skipped.
+        let empty = world.spawn().insert_bundle((A(6), Sparse(6))).id();
+        world.despawn(empty);
+
+        fn double(mut query: Query<(Entity, &mut A)>, mut seen: ResMut<Vec<Vec<Entity>>>) {
+            query.for_each_archetype(|chunk| {
+                let entities = chunk.entities();
+                let mut visited = Vec::new();
+                for (entity, mut a) in chunk {
+                    a.0 *= 2;
+                    visited.push(entity);
+                }
+                assert_eq!(visited, entities);
+                seen.push(visited);
+            });
+        }
+
+        world.init_resource::<Vec<Vec<Entity>>>();
+        let mut system = IntoSystem::into_system(double);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+
+        // Two non-empty archetypes match: `(A,)` and `(A, B)`.
+        let seen = world.resource::<Vec<Vec<Entity>>>();
+        assert_eq!(seen.len(), 2);
+        let mut visited = seen.iter().flatten().copied().collect::<Vec<_>>();
+        visited.sort();
+        expected.sort();
+        assert_eq!(visited, expected);
+
+        let mut values = world
+            .query::<&A>()
+            .iter(&world)
+            .map(|a| a.0)
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn read_only_projection() {
+        mod projection {
+            use crate::{self as bevy_ecs, component::Component, query::WorldQuery};
+
+            #[derive(Component)]
+            pub struct Stats {
+                pub health: u32,
+                pub secret: u32,
+            }
+
+            #[derive(WorldQuery)]
+            pub struct HealthReader {
+                stats: &'static Stats,
+            }
+
+            impl<'w> HealthReaderItem<'w> {
+                pub fn health(&self) -> u32 {
+                    self.stats.health
+                }
+            }
+        }
+
+        let mut world = World::new();
+        world.spawn().insert(projection::Stats {
+            health: 10,
+            secret: 42,
+        });
+        let mut query = world.query::<projection::HealthReader>();
+        let health = query
+            .iter(&world)
+            .map(|reader| reader.health())
+            .collect::<Vec<_>>();
+        assert_eq!(health, vec![10]);
+        // The rest of the component is only reachable by querying it directly.
+        let stats = world.query::<&projection::Stats>().single(&world);
+        assert_eq!(stats.secret, 42);
+
+        let stats_id = world.init_component::<projection::Stats>();
+        assert!(query.component_access.access().has_read(stats_id));
+        assert!(!query.component_access.access().has_write(stats_id));
+    }
+
     #[test]
     fn derived_worldqueries() {
         let mut world = World::new();
@@ -698,4 +817,75 @@ mod tests {
             system.run((), &mut world);
         }
     }
+
+    #[test]
+    fn query_excludes_disabled() {
+        let mut world = World::new();
+        let enabled = world.spawn().insert(A(1)).id();
+        let disabled = world.spawn().insert_bundle((A(2), Disabled)).id();
+        let disabled_sparse = world
+            .spawn()
+            .insert_bundle((A(3), Sparse(3), Disabled))
+            .id();
+
+        let mut query = world.query::<&A>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![&A(1)]);
+        assert!(query.get(&world, enabled).is_ok());
+        assert!(query.get(&world, disabled).is_err());
+        assert!(query.get(&world, disabled_sparse).is_err());
+        assert_eq!(world.query::<Entity>().iter(&world).count(), 1);
+        assert_eq!(world.get::<A>(disabled), Some(&A(2)));
+
+        world.entity_mut(disabled).remove::<Disabled>();
+        assert!(query.get(&world, disabled).is_ok());
+
+        fn system(query: Query<&A>) {
+            assert_eq!(query.iter().count(), 2);
+        }
+        let mut system = IntoSystem::into_system(system);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+    }
+
+    #[test]
+    fn query_includes_disabled_explicitly() {
+        let mut world = World::new();
+        world.spawn().insert(A(1));
+        world.spawn().insert_bundle((A(2), Disabled));
+
+        let values = world
+            .query_filtered::<&A, With<Disabled>>()
+            .iter(&world)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![&A(2)]);
+
+        let values = world
+            .query::<(&A, &Disabled)>()
+            .iter(&world)
+            .map(|(a, _)| a)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![&A(2)]);
+
+        let mut values = world
+            .query::<(&A, Option<&Disabled>)>()
+            .iter(&world)
+            .map(|(a, disabled)| (a.0, disabled.is_some()))
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![(1, false), (2, true)]);
+
+        let mut query = world.query::<&A>();
+        assert_eq!(query.iter(&world).count(), 1);
+        query.include_disabled(&world);
+        assert_eq!(query.iter(&world).count(), 2);
+        world.spawn().insert_bundle((A(3), B(3), Disabled));
+        assert_eq!(query.iter(&world).count(), 3);
+
+        fn system(query: Query<&A, With<Disabled>>) {
+            assert_eq!(query.iter().count(), 2);
+        }
+        let mut system = IntoSystem::into_system(system);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+    }
 }
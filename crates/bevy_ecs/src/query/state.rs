@@ -1,24 +1,31 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId, ArchetypeGeneration, ArchetypeId},
     component::ComponentId,
-    entity::Entity,
+    entity::{Disabled, Entity},
     prelude::FromWorld,
     query::{
-        Access, Fetch, FetchState, FilteredAccess, NopFetch, QueryCombinationIter, QueryIter,
-        WorldQuery,
+        Access, ArchetypeChunk, Fetch, FetchState, FilteredAccess, NopFetch, QueryCombinationIter,
+        QueryIter, WorldQuery,
     },
     storage::TableId,
+    system::Query,
     world::{World, WorldId},
 };
 use bevy_tasks::ComputeTaskPool;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::Instrument;
 use fixedbitset::FixedBitSet;
-use std::{borrow::Borrow, fmt};
+use std::{borrow::Borrow, fmt, ops::Range};
 
 use super::{QueryFetch, QueryItem, QueryManyIter, ROQueryFetch, ROQueryItem};
 
 /// Provides scoped access to a [`World`] state according to a given [`WorldQuery`] and query filter.
+///
+/// The set of archetypes and tables matching the query is cached: it is only updated with the
+/// archetypes created since the last call to [`update_archetypes`](Self::update_archetypes).
+/// Archetype-level filters such as [`With`](crate::query::With), [`Without`](crate::query::Without)
+/// and [`Or`](crate::query::Or) of those are fully resolved by this cache and cost nothing per
+/// entity, so a `QueryState` should be reused rather than recreated every time it is iterated.
 pub struct QueryState<Q: WorldQuery, F: WorldQuery = ()> {
     world_id: WorldId,
     pub(crate) archetype_generation: ArchetypeGeneration,
@@ -32,6 +39,8 @@ pub struct QueryState<Q: WorldQuery, F: WorldQuery = ()> {
     pub(crate) matched_archetype_ids: Vec<ArchetypeId>,
     pub(crate) fetch_state: Q::State,
     pub(crate) filter_state: F::State,
+    /// The id of [`Disabled`], if archetypes containing it are excluded from the query.
+    disabled_id: Option<ComponentId>,
 }
 
 impl<Q: WorldQuery, F: WorldQuery> FromWorld for QueryState<Q, F> {
@@ -62,6 +71,16 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
         // properly considered in a global "cross-query" context (both within systems and across systems).
         component_access.extend(&filter_component_access);
 
+        // Disabled entities are excluded unless the query explicitly mentions `Disabled`.
+        let disabled_id = world.init_component::<Disabled>();
+        let disabled_id = if component_access.access().has_read(disabled_id)
+            || component_access.has_with(disabled_id)
+        {
+            None
+        } else {
+            Some(disabled_id)
+        };
+
         let mut state = Self {
             world_id: world.id(),
             archetype_generation: ArchetypeGeneration::initial(),
@@ -73,11 +92,30 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             archetype_component_access: Default::default(),
+            disabled_id,
         };
         state.update_archetypes(world);
         state
     }
 
+    /// Makes this query match entities with a [`Disabled`] component, which are otherwise
+    /// excluded unless the query mentions `Disabled` explicitly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `world.id()` does not equal the current [`QueryState`] internal id.
+    pub fn include_disabled(&mut self, world: &World) {
+        if self.disabled_id.take().is_some() {
+            self.archetype_generation = ArchetypeGeneration::initial();
+            self.matched_tables.clear();
+            self.matched_archetypes.clear();
+            self.matched_table_ids.clear();
+            self.matched_archetype_ids.clear();
+            self.archetype_component_access = Default::default();
+        }
+        self.update_archetypes(world);
+    }
+
     /// Checks if the query is empty for the given [`World`], where the last change and current tick are given.
     #[inline]
     pub fn is_empty(&self, world: &World, last_change_tick: u32, change_tick: u32) -> bool {
@@ -124,6 +162,7 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
             && self
                 .filter_state
                 .matches_component_set(&|id| archetype.contains(id))
+            && !matches!(self.disabled_id, Some(id) if archetype.contains(id))
         {
             QueryFetch::<'static, Q>::update_archetype_component_access(
                 &self.fetch_state,
@@ -150,6 +189,43 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
         }
     }
 
+    /// Returns a [`Query`] over the given [`World`], with the same API as the queries of
+    /// systems.
+    ///
+    /// This is meant for exclusive systems: the unique borrow of `world` rules out conflicting
+    /// accesses, so no access check is needed and the query can mutate components directly.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// fn heal_all(world: &mut World) {
+    ///     let mut state = world.query::<&mut Health>();
+    ///     let mut query = state.query_mut(world);
+    ///     for mut health in query.iter_mut() {
+    ///         health.0 += 1;
+    ///     }
+    /// }
+    /// # let mut world = World::new();
+    /// # let entity = world.spawn().insert(Health(1)).id();
+    /// # heal_all(&mut world);
+    /// # assert_eq!(world.get::<Health>(entity).unwrap().0, 2);
+    /// ```
+    #[inline]
+    pub fn query_mut<'w, 's>(&'s mut self, world: &'w mut World) -> Query<'w, 's, Q, F> {
+        self.update_archetypes(world);
+        // SAFETY: query has unique world access
+        unsafe {
+            Query::new(
+                world,
+                self,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
     /// Gets the query result for the given [`World`] and [`Entity`].
     ///
     /// This can only be called for read-only queries, see [`Self::get_mut`] for write-queries.
@@ -642,6 +718,56 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
         QueryIter::new(world, self, last_change_tick, change_tick)
     }
 
+    /// Returns the query result at index `n` in iteration order, where the last change and the
+    /// current change tick are given.
+    ///
+    /// Unless `F` filters entities individually, such as with [`Changed`](crate::query::Changed),
+    /// this skips whole tables or archetypes by their length, without fetching their items.
+    ///
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `self.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`] is unsound.
+    pub(crate) unsafe fn nth_unchecked_manual<'w, QF: Fetch<'w, State = Q::State>>(
+        &self,
+        world: &'w World,
+        mut n: usize,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Option<QF::Item> {
+        if !<QueryFetch<'static, F>>::IS_ARCHETYPAL {
+            return self
+                .iter_unchecked_manual::<QF>(world, last_change_tick, change_tick)
+                .nth(n);
+        }
+        // Walk the tables or archetypes in the same order as `QueryIter`.
+        let entity = if QF::IS_DENSE && <QueryFetch<'static, F>>::IS_DENSE {
+            self.matched_table_ids.iter().find_map(|id| {
+                let entities = world.storages.tables[*id].entities();
+                if let Some(entity) = entities.get(n) {
+                    Some(*entity)
+                } else {
+                    n -= entities.len();
+                    None
+                }
+            })
+        } else {
+            self.matched_archetype_ids.iter().find_map(|id| {
+                let entities = world.archetypes[*id].entities();
+                if let Some(entity) = entities.get(n) {
+                    Some(*entity)
+                } else {
+                    n -= entities.len();
+                    None
+                }
+            })
+        }?;
+        self.get_unchecked_manual::<QF>(world, entity, last_change_tick, change_tick)
+            .ok()
+    }
+
     /// Returns an [`Iterator`] for the given [`World`] and list of [`Entity`]'s, where the last change and
     /// the current change tick are given.
     ///
@@ -806,6 +932,37 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
         }
     }
 
+    /// Runs `func` on each unordered pair of query results in parallel, with mutable access to
+    /// both results of the pair.
+    ///
+    /// See [`Query::par_iter_combinations_mut`](crate::system::Query::par_iter_combinations_mut)
+    /// for how the pairs are split between threads.
+    ///
+    /// # Panics
+    /// The [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    #[inline]
+    pub fn par_iter_combinations_mut<
+        FN: for<'x> Fn([QueryItem<'x, Q>; 2]) + Send + Sync + Clone,
+    >(
+        &mut self,
+        world: &mut World,
+        batch_size: usize,
+        func: FN,
+    ) {
+        // SAFETY: query has unique world access
+        unsafe {
+            self.update_archetypes(world);
+            self.par_iter_combinations_unchecked_manual::<FN>(
+                world,
+                batch_size,
+                func,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            );
+        }
+    }
+
     /// Runs `func` on each query result in parallel.
     ///
     /// This can only be called for read-only queries.
@@ -893,8 +1050,11 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
             let tables = &world.storages().tables;
             for table_id in &self.matched_table_ids {
                 let table = &tables[*table_id];
-                fetch.set_table(&self.fetch_state, table);
                 filter.set_table(&self.filter_state, table);
+                if !filter.may_pass_filter() {
+                    continue;
+                }
+                fetch.set_table(&self.fetch_state, table);
 
                 for table_index in 0..table.len() {
                     if !filter.table_filter_fetch(table_index) {
@@ -909,8 +1069,11 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
             let tables = &world.storages().tables;
             for archetype_id in &self.matched_archetype_ids {
                 let archetype = &archetypes[*archetype_id];
-                fetch.set_archetype(&self.fetch_state, archetype, tables);
                 filter.set_archetype(&self.filter_state, archetype, tables);
+                if !filter.may_pass_filter() {
+                    continue;
+                }
+                fetch.set_archetype(&self.fetch_state, archetype, tables);
 
                 for archetype_index in 0..archetype.len() {
                     if !filter.archetype_filter_fetch(archetype_index) {
@@ -922,6 +1085,96 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
         }
     }
 
+    /// Runs `func` on an [`ArchetypeChunk`] for each non-empty archetype matched by the query, where
+    /// the last change and the current change tick are given.
+    ///
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `self.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`] is unsound.
+    pub(crate) unsafe fn for_each_archetype_unchecked_manual<
+        'w,
+        FN: FnMut(ArchetypeChunk<'w, Q, F>),
+    >(
+        &self,
+        world: &'w World,
+        mut func: FN,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) {
+        let archetypes = &world.archetypes;
+        let tables = &world.storages().tables;
+        for archetype_id in &self.matched_archetype_ids {
+            let archetype = &archetypes[*archetype_id];
+            if archetype.is_empty() {
+                continue;
+            }
+            let mut filter = <QueryFetch<F> as Fetch>::init(
+                world,
+                &self.filter_state,
+                last_change_tick,
+                change_tick,
+            );
+            filter.set_archetype(&self.filter_state, archetype, tables);
+            if !filter.may_pass_filter() {
+                continue;
+            }
+            let mut fetch = <QueryFetch<Q> as Fetch>::init(
+                world,
+                &self.fetch_state,
+                last_change_tick,
+                change_tick,
+            );
+            fetch.set_archetype(&self.fetch_state, archetype, tables);
+            func(ArchetypeChunk::new(
+                *archetype_id,
+                archetype.entities(),
+                fetch,
+                filter,
+            ));
+        }
+    }
+
+    /// Returns an [`ArchetypeChunk`] over the query results of the entities of the archetype
+    /// `archetype_id` in `range`, where the last change and the current change tick are given.
+    ///
+    /// # Safety
+    ///
+    /// `archetype_id` must be matched by the query and `range` must be in bounds of its entities.
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components of those entities.
+    /// This does not validate that `world.id()` matches `self.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`] is unsound.
+    pub(crate) unsafe fn archetype_batch_unchecked_manual<'w>(
+        &self,
+        world: &'w World,
+        archetype_id: ArchetypeId,
+        range: Range<usize>,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> ArchetypeChunk<'w, Q, F> {
+        let tables = &world.storages().tables;
+        let archetype = &world.archetypes[archetype_id];
+        let mut filter = <QueryFetch<F> as Fetch>::init(
+            world,
+            &self.filter_state,
+            last_change_tick,
+            change_tick,
+        );
+        filter.set_archetype(&self.filter_state, archetype, tables);
+        let mut fetch =
+            <QueryFetch<Q> as Fetch>::init(world, &self.fetch_state, last_change_tick, change_tick);
+        fetch.set_archetype(&self.fetch_state, archetype, tables);
+        let entities = if filter.may_pass_filter() {
+            &archetype.entities()[range.clone()]
+        } else {
+            &[]
+        };
+        ArchetypeChunk::new_batch(archetype_id, entities, range.start, fetch, filter)
+    }
+
     /// Runs `func` on each query result in parallel for the given [`World`], where the last change and
     /// the current change tick are given. This is faster than the equivalent
     /// iter() method, but cannot be chained like a normal [`Iterator`].
@@ -972,6 +1225,9 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
                             let table = &tables[*table_id];
                             fetch.set_table(&self.fetch_state, table);
                             filter.set_table(&self.filter_state, table);
+                            if !filter.may_pass_filter() {
+                                return;
+                            }
                             for table_index in offset..offset + len {
                                 if !filter.table_filter_fetch(table_index) {
                                     continue;
@@ -1014,6 +1270,9 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
                             let archetype = &world.archetypes[*archetype_id];
                             fetch.set_archetype(&self.fetch_state, archetype, tables);
                             filter.set_archetype(&self.filter_state, archetype, tables);
+                            if !filter.may_pass_filter() {
+                                return;
+                            }
 
                             for archetype_index in offset..offset + len {
                                 if !filter.archetype_filter_fetch(archetype_index) {
@@ -1041,6 +1300,101 @@ impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F> {
         });
     }
 
+    /// Runs `func` on each unordered pair of query results in parallel for the given [`World`],
+    /// where the last change and the current change tick are given.
+    ///
+    /// The pairs are scheduled as a round-robin tournament: each round pairs every matched
+    /// entity with at most one other, and every unordered pair is in exactly one round. The
+    /// pairs of a round are split in batches of `batch_size` run in parallel, and the rounds run one
+    /// after the other, so no entity is ever used by two tasks at once.
+    ///
+    /// # Panics
+    /// The [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    ///
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `self.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`] is unsound.
+    pub(crate) unsafe fn par_iter_combinations_unchecked_manual<
+        'w,
+        FN: for<'x> Fn([QueryItem<'x, Q>; 2]) + Send + Sync + Clone,
+    >(
+        &self,
+        world: &'w World,
+        batch_size: usize,
+        func: FN,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) {
+        let mut entities = Vec::new();
+        for archetype_id in &self.matched_archetype_ids {
+            for entity in world.archetypes[*archetype_id].entities() {
+                if self
+                    .get_unchecked_manual::<NopFetch<Q::State>>(
+                        world,
+                        *entity,
+                        last_change_tick,
+                        change_tick,
+                    )
+                    .is_ok()
+                {
+                    entities.push(*entity);
+                }
+            }
+        }
+
+        // With an odd number of entities, the entity paired with the extra, missing one sits out
+        // the round.
+        let players = entities.len() + entities.len() % 2;
+        let rounds = players.saturating_sub(1);
+        let mut pairs = Vec::with_capacity(players / 2);
+        for round in 0..rounds {
+            // The first entity stays in place while the others rotate.
+            let position = |seat: usize| {
+                if seat == 0 {
+                    0
+                } else {
+                    1 + (seat - 1 + round) % rounds
+                }
+            };
+            pairs.clear();
+            pairs.extend((0..players / 2).filter_map(|seat| {
+                let a = entities.get(position(seat))?;
+                let b = entities.get(position(players - 1 - seat))?;
+                Some((*a, *b))
+            }));
+            ComputeTaskPool::get().scope(|scope| {
+                for batch in pairs.chunks(batch_size.max(1)) {
+                    let func = func.clone();
+                    scope.spawn(async move {
+                        for (a, b) in batch {
+                            // SAFETY: the pairs of a round have no entity in common, and the
+                            // entities were matched above.
+                            let a = self.get_unchecked_manual::<QueryFetch<'w, Q>>(
+                                world,
+                                *a,
+                                last_change_tick,
+                                change_tick,
+                            );
+                            let b = self.get_unchecked_manual::<QueryFetch<'w, Q>>(
+                                world,
+                                *b,
+                                last_change_tick,
+                                change_tick,
+                            );
+                            if let (Ok(a), Ok(b)) = (a, b) {
+                                func([a, b]);
+                            }
+                        }
+                    });
+                }
+            });
+        }
+    }
+
     /// Runs `func` on each query result for the given [`World`] and list of [`Entity`]'s, where the last change and
     /// the current change tick are given. This is faster than the equivalent
     /// iter() method, but cannot be chained like a normal [`Iterator`].
@@ -0,0 +1,122 @@
+use crate::{archetype::ArchetypeComponentId, change_detection::DetectChanges, world::World};
+use bevy_utils::HashMap;
+
+/// A resource counting, per component and resource type, how often an access to it kept a system
+/// of a [`ParallelExecutor`](super::ParallelExecutor) from starting while other systems ran.
+///
+/// A system that is ready to run but conflicts with running systems waits for them to finish,
+/// which serializes the stage. Each time this happens, every type the waiting system conflicts
+/// on is counted once: a type that shows up at the top of the report is a good candidate for
+/// being split into smaller resources or components.
+///
+/// Recording is opt-in: executors only record contention while this resource exists, so insert
+/// it to start recording and remove it to stop. Unlike
+/// [`SystemExecutionOrder`](super::SystemExecutionOrder), the counts add up over frames until
+/// [`ContentionReport::clear`] is called.
+///
+/// Stages that run their systems on a single thread never record contention.
+#[derive(Debug, Default)]
+pub struct ContentionReport {
+    counts: HashMap<String, usize>,
+}
+
+impl ContentionReport {
+    /// Returns how many times an access to the component or resource type `name` kept a system
+    /// from starting.
+    ///
+    /// `name` is the full type name, as given by [`std::any::type_name`].
+    pub fn count(&self, name: &str) -> usize {
+        self.counts.get(name).copied().unwrap_or(0)
+    }
+
+    /// Returns the `n` most contended types with their counts, the most contended first.
+    pub fn top(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut counts = self
+            .counts
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect::<Vec<_>>();
+        counts.sort_unstable_by(|(a_name, a_count), (b_name, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+        });
+        counts.truncate(n);
+        counts
+    }
+
+    /// Returns `true` if no contention was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Forgets the recorded contention.
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+
+    /// Adds `conflicts`, the number of times each archetype component blocked a system, to the
+    /// [`ContentionReport`] of `world`, if it has one.
+    pub(crate) fn record(world: &mut World, conflicts: &HashMap<ArchetypeComponentId, usize>) {
+        if conflicts.is_empty() || !world.contains_resource::<ContentionReport>() {
+            return;
+        }
+        let mut counts = HashMap::<String, usize>::default();
+        for archetype in world.archetypes().iter() {
+            for component_id in archetype.components() {
+                let count = archetype
+                    .get_archetype_component_id(component_id)
+                    .and_then(|id| conflicts.get(&id));
+                if let (Some(count), Some(info)) =
+                    (count, world.components().get_info(component_id))
+                {
+                    *counts.entry(info.name().to_string()).or_default() += count;
+                }
+            }
+        }
+        let mut report = world.resource_mut::<ContentionReport>();
+        let report = report.bypass_change_detection();
+        for (name, count) in counts {
+            *report.counts.entry(name).or_default() += count;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContentionReport;
+    use crate::{prelude::*, schedule::SystemStage};
+
+    struct Hot(u32);
+    struct Cold;
+
+    fn first(mut hot: ResMut<Hot>, _cold: Res<Cold>) {
+        hot.0 += 1;
+    }
+
+    fn second(mut hot: ResMut<Hot>, _cold: Res<Cold>) {
+        hot.0 += 1;
+    }
+
+    #[test]
+    fn report_contended_resource() {
+        let mut world = World::new();
+        world.insert_resource(Hot(0));
+        world.insert_resource(Cold);
+        world.init_resource::<ContentionReport>();
+
+        let mut stage = SystemStage::parallel()
+            .with_system(first)
+            .with_system(second);
+        for _ in 0..2 {
+            stage.run(&mut world);
+        }
+
+        let report = world.resource::<ContentionReport>();
+        let hot = std::any::type_name::<Hot>();
+        // One of the systems waits for the other once per frame, and the counts add up.
+        assert_eq!(report.count(hot), 2);
+        assert_eq!(report.top(1), vec![(hot, 2)]);
+        // Shared reads don't block.
+        assert_eq!(report.count(std::any::type_name::<Cold>()), 0);
+        assert_eq!(world.resource::<Hot>().0, 4);
+    }
+}
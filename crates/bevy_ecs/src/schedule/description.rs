@@ -0,0 +1,254 @@
+use crate::{
+    schedule::{Schedule, Stage, StageLabel, SystemContainer, SystemStage},
+    world::World,
+};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// A description of the stages and systems of a [`Schedule`], returned by
+/// [`Schedule::describe`].
+///
+/// It only contains names and plain data, so that it can be shown or compared by external
+/// tools. With the `serialize` feature, it can be serialized with [`serde`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ScheduleDescription {
+    /// The stages of the schedule, in execution order.
+    pub stages: Vec<StageDescription>,
+}
+
+/// A description of one stage of a [`Schedule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct StageDescription {
+    /// The label of the stage, as formatted by [`Debug`].
+    pub label: String,
+    /// The systems of the stage, if it is a [`SystemStage`], in execution order.
+    pub systems: Vec<SystemDescription>,
+    /// The ordering constraints between the systems of the stage, as `(before, after)` pairs of
+    /// [`SystemDescription::id`]s.
+    pub edges: Vec<(usize, usize)>,
+    /// The description of the stage, if it is a nested [`Schedule`].
+    pub schedule: Option<ScheduleDescription>,
+}
+
+/// When a system runs within its [`SystemStage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum SystemKind {
+    /// An exclusive system run at the start of the stage.
+    ExclusiveAtStart,
+    /// A parallel system.
+    Parallel,
+    /// An exclusive system run after the parallel systems, before their commands are applied.
+    ExclusiveBeforeCommands,
+    /// An exclusive system run at the end of the stage.
+    ExclusiveAtEnd,
+}
+
+/// A description of one system of a [`SystemStage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct SystemDescription {
+    /// The position of the system in its stage, which identifies it in
+    /// [`StageDescription::edges`].
+    pub id: usize,
+    /// The name of the system.
+    pub name: String,
+    /// When the system runs within its stage.
+    pub kind: SystemKind,
+    /// The labels of the system, as formatted by [`Debug`].
+    pub labels: Vec<String>,
+    /// The labels of the systems this system runs before, as formatted by [`Debug`].
+    pub before: Vec<String>,
+    /// The labels of the systems this system runs after, as formatted by [`Debug`].
+    pub after: Vec<String>,
+    /// Whether the system reads all the data of the world.
+    pub reads_all: bool,
+    /// The names of the component and resource types the system only reads.
+    pub reads: Vec<String>,
+    /// The names of the component and resource types the system writes.
+    pub writes: Vec<String>,
+    /// Whether the system declares no write, so that it never blocks another system.
+    pub read_only: bool,
+}
+
+impl Schedule {
+    /// Returns a description of the stages of this schedule, with the systems of each
+    /// [`SystemStage`] and their data access, resolving type names with `world`.
+    ///
+    /// Systems are only sorted, and their ordering edges and data access known, once their
+    /// stage has run, so this should be called after the schedule ran at least once on `world`.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// fn physics() {}
+    /// fn render() {}
+    ///
+    /// let mut world = World::new();
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_stage(
+    ///     "update",
+    ///     SystemStage::parallel()
+    ///         .with_system(render.after(physics))
+    ///         .with_system(physics),
+    /// );
+    /// schedule.run_once(&mut world);
+    ///
+    /// let description = schedule.describe(&world);
+    /// let systems = &description.stages[0].systems;
+    /// assert!(systems[0].name.ends_with("physics"));
+    /// assert_eq!(description.stages[0].edges, vec![(0, 1)]);
+    /// ```
+    pub fn describe(&self, world: &World) -> ScheduleDescription {
+        ScheduleDescription {
+            stages: self
+                .iter_stages()
+                .map(|(label, stage)| describe_stage(label, stage, world))
+                .collect(),
+        }
+    }
+}
+
+fn describe_stage(label: &dyn StageLabel, stage: &dyn Stage, world: &World) -> StageDescription {
+    let mut description = StageDescription {
+        label: format!("{:?}", label),
+        systems: Vec::new(),
+        edges: Vec::new(),
+        schedule: None,
+    };
+    if let Some(stage) = stage.downcast_ref::<SystemStage>() {
+        describe_systems(
+            &mut description,
+            SystemKind::ExclusiveAtStart,
+            stage.exclusive_at_start_systems(),
+            world,
+        );
+        describe_systems(
+            &mut description,
+            SystemKind::Parallel,
+            stage.parallel_systems(),
+            world,
+        );
+        describe_systems(
+            &mut description,
+            SystemKind::ExclusiveBeforeCommands,
+            stage.exclusive_before_commands_systems(),
+            world,
+        );
+        describe_systems(
+            &mut description,
+            SystemKind::ExclusiveAtEnd,
+            stage.exclusive_at_end_systems(),
+            world,
+        );
+    } else if let Some(schedule) = stage.downcast_ref::<Schedule>() {
+        description.schedule = Some(schedule.describe(world));
+    }
+    description
+}
+
+/// Appends the descriptions of `containers` to the systems of `stage`, along with their edges.
+fn describe_systems(
+    stage: &mut StageDescription,
+    kind: SystemKind,
+    containers: &[impl SystemContainer],
+    world: &World,
+) {
+    let offset = stage.systems.len();
+    let names = |ids: &mut dyn Iterator<Item = _>| {
+        ids.filter_map(|id| world.components().get_info(id))
+            .map(|info| info.name().to_string())
+            .collect()
+    };
+    let labels = |labels: &[_]| labels.iter().map(|label| format!("{:?}", label)).collect();
+    for (index, container) in containers.iter().enumerate() {
+        let access = container.component_access();
+        stage.systems.push(SystemDescription {
+            id: offset + index,
+            name: container.name().into_owned(),
+            kind,
+            labels: labels(container.labels()),
+            before: labels(container.before()),
+            after: labels(container.after()),
+            reads_all: access.is_some_and(|access| access.has_read_all()),
+            reads: access.map_or_else(Vec::new, |access| names(&mut access.reads())),
+            writes: access.map_or_else(Vec::new, |access| names(&mut access.writes())),
+            read_only: access.is_some_and(|access| access.writes().next().is_none()),
+        });
+        stage.edges.extend(
+            container
+                .dependencies()
+                .iter()
+                .map(|dependency| (offset + dependency, offset + index)),
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serialize"))]
+mod tests {
+    use super::{ScheduleDescription, SystemKind};
+    use crate::{
+        prelude::*,
+        schedule::{Schedule, SystemStage},
+    };
+
+    struct Score(u32);
+
+    fn physics() {}
+    fn score(mut score: ResMut<Score>) {
+        score.0 += 1;
+    }
+    fn render(_score: Res<Score>) {}
+    fn flush(_world: &mut World) {}
+
+    #[test]
+    fn serialize_description() {
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        let mut schedule = Schedule::default();
+        schedule.add_stage(
+            "update",
+            SystemStage::parallel()
+                .with_system(render.after("score"))
+                .with_system(score.label("score").after(physics))
+                .with_system(physics),
+        );
+        schedule.add_stage(
+            "last",
+            SystemStage::single_threaded().with_system(flush.exclusive_system().at_end()),
+        );
+        schedule.run_once(&mut world);
+
+        let description = schedule.describe(&world);
+        let serialized = ron::to_string(&description).unwrap();
+        let deserialized: ScheduleDescription = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, description);
+
+        let stages = deserialized
+            .stages
+            .iter()
+            .map(|stage| stage.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(stages, vec!["\"update\"", "\"last\""]);
+
+        let update = &deserialized.stages[0];
+        let names = update
+            .systems
+            .iter()
+            .map(|system| system.name.rsplit("::").next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["physics", "score", "render"]);
+        assert_eq!(update.edges, vec![(0, 1), (1, 2)]);
+        assert!(update.systems[1].labels.contains(&"\"score\"".to_string()));
+        let score_type = std::any::type_name::<Score>().to_string();
+        assert_eq!(update.systems[1].writes, vec![score_type.clone()]);
+        assert_eq!(update.systems[2].reads, vec![score_type]);
+        assert!(!update.systems[1].read_only);
+        assert!(update.systems[2].read_only);
+
+        let last = &deserialized.stages[1].systems[0];
+        assert_eq!(last.kind, SystemKind::ExclusiveAtEnd);
+        assert!(last.name.ends_with("flush"));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{change_detection::DetectChanges, world::World};
+use std::borrow::Cow;
+
+/// A resource recording the names of the systems run by [`SystemStage`](super::SystemStage)s, in
+/// the order they finished.
+///
+/// Recording is opt-in: stages only record systems while this resource exists, so insert it to
+/// start recording and remove it to stop. The recorded systems are cleared at the first system
+/// run after [`World::clear_trackers`], which usually happens once per frame, so the resource
+/// holds the systems of the current frame.
+///
+/// This is mainly useful in tests, to check that labels and ordering constraints give the
+/// intended order. Systems that run in parallel may be recorded in any order relative to each
+/// other, but a system is always recorded after the systems it is ordered after.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, schedule::SystemExecutionOrder};
+/// fn physics() {}
+/// fn render() {}
+///
+/// let mut world = World::new();
+/// world.init_resource::<SystemExecutionOrder>();
+///
+/// let mut stage = SystemStage::parallel()
+///     .with_system(render.after(physics))
+///     .with_system(physics);
+/// stage.run(&mut world);
+///
+/// let order = world.resource::<SystemExecutionOrder>();
+/// assert!(order.ran_before("physics", "render"));
+/// ```
+#[derive(Debug, Default)]
+pub struct SystemExecutionOrder {
+    systems: Vec<Cow<'static, str>>,
+    last_change_tick: u32,
+}
+
+impl SystemExecutionOrder {
+    /// Returns the names of the systems recorded this frame, in the order they finished.
+    pub fn systems(&self) -> &[Cow<'static, str>] {
+        &self.systems
+    }
+
+    /// Returns the position of the first recorded run of the system `name`.
+    ///
+    /// `name` can either be the full name of the system, or its last path segment.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.systems.iter().position(|system| {
+            system == name
+                || system
+                    .strip_suffix(name)
+                    .is_some_and(|prefix| prefix.ends_with("::"))
+        })
+    }
+
+    /// Returns `true` if both systems were recorded, and `before` first finished before `after`
+    /// did.
+    ///
+    /// See [`SystemExecutionOrder::position`] for how systems are named.
+    pub fn ran_before(&self, before: &str, after: &str) -> bool {
+        match (self.position(before), self.position(after)) {
+            (Some(before), Some(after)) => before < after,
+            _ => false,
+        }
+    }
+
+    /// Forgets the recorded systems.
+    pub fn clear(&mut self) {
+        self.systems.clear();
+    }
+
+    /// Records `systems` in the [`SystemExecutionOrder`] of `world`, if it has one.
+    pub(crate) fn record(world: &mut World, systems: impl IntoIterator<Item = Cow<'static, str>>) {
+        let last_change_tick = world.last_change_tick();
+        if let Some(mut order) = world.get_resource_mut::<SystemExecutionOrder>() {
+            let order = order.bypass_change_detection();
+            if order.last_change_tick != last_change_tick {
+                order.last_change_tick = last_change_tick;
+                order.systems.clear();
+            }
+            order.systems.extend(systems);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SystemExecutionOrder;
+    use crate::{
+        prelude::*,
+        schedule::{Schedule, SystemStage},
+    };
+
+    fn first() {}
+    fn second() {}
+    fn third() {}
+    fn unordered() {}
+    fn exclusive(_world: &mut World) {}
+
+    #[test]
+    fn record_ordered_systems() {
+        let mut world = World::new();
+        // Nothing is recorded until the resource exists.
+        let mut stage = SystemStage::parallel().with_system(unordered);
+        stage.run(&mut world);
+        world.init_resource::<SystemExecutionOrder>();
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage(
+            "update",
+            SystemStage::parallel()
+                .with_system(third.after(second))
+                .with_system(unordered)
+                .with_system(second.after(first))
+                .with_system(first),
+        );
+        schedule.add_stage(
+            "last",
+            SystemStage::single_threaded().with_system(exclusive.exclusive_system()),
+        );
+
+        for _ in 0..2 {
+            schedule.run(&mut world);
+            let order = world.resource::<SystemExecutionOrder>();
+            assert_eq!(order.systems().len(), 5);
+            assert!(order.ran_before("first", "second"));
+            assert!(order.ran_before("second", "third"));
+            assert!(order.ran_before("third", "exclusive"));
+            assert!(order.position("unordered").is_some());
+            // Only whole path segments match.
+            assert!(order.position("ordered").is_none());
+            world.clear_trackers();
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{schedule::ParallelSystemContainer, world::World};
+use crate::{
+    schedule::{ParallelSystemContainer, SystemExecutionOrder},
+    world::World,
+};
 use downcast_rs::{impl_downcast, Downcast};
 
 pub trait ParallelSystemExecutor: Downcast + Send + Sync {
@@ -17,12 +20,16 @@ impl ParallelSystemExecutor for SingleThreadedExecutor {
     fn rebuild_cached_data(&mut self, _: &[ParallelSystemContainer]) {}
 
     fn run_systems(&mut self, systems: &mut [ParallelSystemContainer], world: &mut World) {
+        let record_order = world.contains_resource::<SystemExecutionOrder>();
         for system in systems {
             if system.should_run() {
                 #[cfg(feature = "trace")]
                 let _system_span =
                     bevy_utils::tracing::info_span!("system", name = &*system.name()).entered();
                 system.system_mut().run((), world);
+                if record_order {
+                    SystemExecutionOrder::record(world, [system.name()]);
+                }
             }
         }
     }
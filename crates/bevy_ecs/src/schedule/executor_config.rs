@@ -0,0 +1,229 @@
+use crate::{change_detection::DetectChanges, world::World};
+use bevy_tasks::{TaskPool, TaskPoolBuilder};
+use std::sync::Arc;
+
+/// A resource setting the number of worker threads used by [`ParallelExecutor`]s.
+///
+/// Without this resource, parallel executors run systems on the global
+/// [`ComputeTaskPool`](bevy_tasks::ComputeTaskPool). With it, they all run them on a single task
+/// pool of the world with [`ExecutorConfig::worker_threads`] threads, so the thread count can be tuned
+/// without recompiling, for example with fewer threads on battery. A count of `0` stands for
+/// all the cores, and counts are clamped to the number of cores.
+///
+/// The thread count given on creation is used as soon as the resource is inserted.
+/// [`ExecutorConfig::set_worker_threads`] changes it at runtime, but only once
+/// [`ExecutorConfig::apply`] is called between two frames, which `App::update` does. The task
+/// pool is rebuilt once, by the first executor that runs after the change, when the work of the
+/// previous pool finished, so a change never interrupts a frame.
+///
+/// Parallel query iteration, such as [`Query::par_for_each`](crate::system::Query::par_for_each),
+/// still uses the global [`ComputeTaskPool`](bevy_tasks::ComputeTaskPool).
+///
+/// [`ParallelExecutor`]: super::ParallelExecutor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutorConfig {
+    worker_threads: usize,
+    pending: Option<usize>,
+    active: Option<usize>,
+}
+
+impl Default for ExecutorConfig {
+    fn default() -> Self {
+        Self::with_worker_threads(0)
+    }
+}
+
+impl ExecutorConfig {
+    /// Creates an [`ExecutorConfig`] using `worker_threads` threads, or all the cores if it is
+    /// `0`.
+    pub fn with_worker_threads(worker_threads: usize) -> Self {
+        ExecutorConfig {
+            worker_threads: Self::clamp(worker_threads),
+            pending: None,
+            active: None,
+        }
+    }
+
+    /// Returns the number of worker threads executors use.
+    pub fn worker_threads(&self) -> usize {
+        self.worker_threads
+    }
+
+    /// Requests executors to use `worker_threads` threads, or all the cores if it is `0`, once
+    /// [`ExecutorConfig::apply`] is called.
+    pub fn set_worker_threads(&mut self, worker_threads: usize) {
+        self.pending = Some(Self::clamp(worker_threads));
+    }
+
+    /// Returns the number of worker threads requested by [`ExecutorConfig::set_worker_threads`]
+    /// that isn't applied yet, if any.
+    pub fn pending_worker_threads(&self) -> Option<usize> {
+        self.pending
+    }
+
+    /// Applies the number of worker threads requested by [`ExecutorConfig::set_worker_threads`].
+    ///
+    /// This should be called between frames.
+    pub fn apply(&mut self) {
+        if let Some(worker_threads) = self.pending.take() {
+            self.worker_threads = worker_threads;
+        }
+    }
+
+    /// Returns the number of threads of the task pool parallel executors last ran systems on, or
+    /// `None` if they didn't run since the resource was inserted.
+    pub fn active_worker_threads(&self) -> Option<usize> {
+        self.active
+    }
+
+    fn clamp(worker_threads: usize) -> usize {
+        let cores = bevy_tasks::logical_core_count();
+        if worker_threads == 0 {
+            cores
+        } else {
+            worker_threads.min(cores)
+        }
+    }
+
+    /// Records that an executor ran systems on `worker_threads` threads, in the
+    /// [`ExecutorConfig`] of `world`, if it has one.
+    pub(crate) fn record_active(world: &mut World, worker_threads: usize) {
+        if let Some(mut config) = world.get_resource_mut::<ExecutorConfig>() {
+            config.bypass_change_detection().active = Some(worker_threads);
+        }
+    }
+}
+
+/// The task pool shared by the parallel executors of a world with an [`ExecutorConfig`], with its
+/// thread count.
+pub(crate) struct ExecutorTaskPool {
+    worker_threads: usize,
+    task_pool: Arc<TaskPool>,
+}
+
+impl ExecutorTaskPool {
+    /// Returns the task pool parallel executors run systems on in `world`, building it if the
+    /// thread count of its [`ExecutorConfig`] changed, or `None` if it has no [`ExecutorConfig`].
+    pub(crate) fn get(world: &mut World) -> Option<Arc<TaskPool>> {
+        let worker_threads = match world.get_resource::<ExecutorConfig>() {
+            Some(config) => config.worker_threads(),
+            None => {
+                // Don't keep the threads of a removed configuration alive.
+                if world.contains_resource::<ExecutorTaskPool>() {
+                    world.remove_resource::<ExecutorTaskPool>();
+                }
+                return None;
+            }
+        };
+        match world.get_resource::<ExecutorTaskPool>() {
+            Some(pool) if pool.worker_threads == worker_threads => Some(pool.task_pool.clone()),
+            _ => {
+                let task_pool = Arc::new(
+                    TaskPoolBuilder::new()
+                        .num_threads(worker_threads)
+                        .thread_name("Executor".to_string())
+                        .build(),
+                );
+                world.insert_resource(ExecutorTaskPool {
+                    worker_threads,
+                    task_pool: task_pool.clone(),
+                });
+                Some(task_pool)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExecutorConfig, ExecutorTaskPool};
+    use crate::{
+        prelude::*,
+        schedule::{Stage, SystemStage},
+    };
+
+    #[derive(Default)]
+    struct Frames(u32);
+    #[derive(Default)]
+    struct Work(u32);
+
+    fn count_frames(mut frames: ResMut<Frames>) {
+        frames.0 += 1;
+    }
+
+    fn work(mut work: ResMut<Work>) {
+        work.0 += 1;
+    }
+
+    fn reduce_threads(frames: Res<Frames>, mut config: ResMut<ExecutorConfig>) {
+        if frames.0 == 1 {
+            config.set_worker_threads(1);
+        }
+    }
+
+    #[test]
+    fn change_worker_threads_between_frames() {
+        let threads = 2.min(bevy_tasks::logical_core_count());
+        let mut world = World::new();
+        world.init_resource::<Frames>();
+        world.init_resource::<Work>();
+        world.insert_resource(ExecutorConfig::with_worker_threads(threads));
+        // `0` stands for all the cores.
+        assert_eq!(
+            ExecutorConfig::default().worker_threads(),
+            bevy_tasks::logical_core_count()
+        );
+
+        let mut stage = SystemStage::parallel()
+            .with_system(count_frames)
+            .with_system(work)
+            .with_system(reduce_threads.after(count_frames));
+        stage.run(&mut world);
+        let config = world.resource::<ExecutorConfig>();
+        assert_eq!(config.active_worker_threads(), Some(threads));
+        assert_eq!(config.pending_worker_threads(), Some(1));
+
+        // The change waits for the next frame.
+        stage.run(&mut world);
+        assert_eq!(
+            world.resource::<ExecutorConfig>().active_worker_threads(),
+            Some(threads)
+        );
+
+        world.resource_mut::<ExecutorConfig>().apply();
+        stage.run(&mut world);
+        let config = world.resource::<ExecutorConfig>();
+        assert_eq!(config.worker_threads(), 1);
+        assert_eq!(config.active_worker_threads(), Some(1));
+        // No work was dropped while the task pool was replaced.
+        assert_eq!(world.resource::<Frames>().0, 3);
+        assert_eq!(world.resource::<Work>().0, 3);
+    }
+
+    #[test]
+    fn share_task_pool_between_stages() {
+        let mut world = World::new();
+        world.init_resource::<Frames>();
+        world.init_resource::<Work>();
+        world.insert_resource(ExecutorConfig::with_worker_threads(2));
+        let mut first = SystemStage::parallel()
+            .with_system(count_frames)
+            .with_system(work);
+        let mut second = SystemStage::parallel()
+            .with_system(count_frames)
+            .with_system(work);
+
+        first.run(&mut world);
+        let task_pool = world.resource::<ExecutorTaskPool>().task_pool.clone();
+        second.run(&mut world);
+        first.run(&mut world);
+        let shared = &world.resource::<ExecutorTaskPool>().task_pool;
+        assert!(std::sync::Arc::ptr_eq(&task_pool, shared));
+        // The executors don't keep the task pool between runs.
+        assert_eq!(std::sync::Arc::strong_count(shared), 2);
+
+        world.remove_resource::<ExecutorConfig>();
+        first.run(&mut world);
+        assert!(!world.contains_resource::<ExecutorTaskPool>());
+    }
+}
@@ -1,13 +1,17 @@
 use crate::{
     archetype::ArchetypeComponentId,
     query::Access,
-    schedule::{ParallelSystemContainer, ParallelSystemExecutor},
+    schedule::{
+        ContentionReport, ExecutorConfig, ExecutorTaskPool, ParallelSystemContainer,
+        ParallelSystemExecutor, SingleThreadedExecutor, SystemExecutionOrder,
+    },
     world::World,
 };
 use async_channel::{Receiver, Sender};
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool};
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::Instrument;
+use bevy_utils::HashMap;
 use fixedbitset::FixedBitSet;
 
 #[cfg(test)]
@@ -29,8 +33,17 @@ struct SystemSchedulingMetadata {
     archetype_component_access: Access<ArchetypeComponentId>,
     /// Whether or not this system is send-able
     is_send: bool,
+    /// Whether this system declares no write, in which case it is started after the others.
+    is_read_only: bool,
 }
 
+/// Runs systems in parallel on the [`ComputeTaskPool`], while respecting their ordering
+/// constraints and data access.
+///
+/// On wasm, if the task pool has no threads of its own, or if
+/// [`single_threaded`](ParallelExecutor::with_single_threaded) mode is forced, systems are run one
+/// after the other on the current thread instead, in the same order as the
+/// [`SingleThreadedExecutor`]. This lets the same stages run everywhere.
 pub struct ParallelExecutor {
     /// Cached metadata of every system.
     system_metadata: Vec<SystemSchedulingMetadata>,
@@ -50,6 +63,16 @@ pub struct ParallelExecutor {
     active_archetype_component_access: Access<ArchetypeComponentId>,
     /// Scratch space to avoid reallocating a vector when updating dependency counters.
     dependants_scratch: Vec<usize>,
+    /// Indices of the systems that finished this iteration, in order, if they are recorded in a
+    /// [`SystemExecutionOrder`].
+    finished_order: Option<Vec<usize>>,
+    /// How many times each archetype component kept a system from starting this iteration, if
+    /// they are recorded in a [`ContentionReport`].
+    contention: Option<HashMap<ArchetypeComponentId, usize>>,
+    /// Systems that were already kept from starting this iteration.
+    contended: FixedBitSet,
+    /// Whether systems are always run one after the other on the current thread.
+    single_threaded: bool,
     #[cfg(test)]
     events_sender: Option<Sender<SchedulingEvent>>,
 }
@@ -67,6 +90,10 @@ impl Default for ParallelExecutor {
             should_run: Default::default(),
             active_archetype_component_access: Default::default(),
             dependants_scratch: Default::default(),
+            finished_order: None,
+            contention: None,
+            contended: Default::default(),
+            single_threaded: false,
             #[cfg(test)]
             events_sender: None,
         }
@@ -79,6 +106,7 @@ impl ParallelSystemExecutor for ParallelExecutor {
         self.queued.grow(systems.len());
         self.running.grow(systems.len());
         self.should_run.grow(systems.len());
+        self.contended.grow(systems.len());
 
         // Construct scheduling data for systems.
         for container in systems.iter() {
@@ -92,6 +120,7 @@ impl ParallelSystemExecutor for ParallelExecutor {
                 dependencies_total,
                 dependencies_now: 0,
                 is_send: system.is_send(),
+                is_read_only: false,
                 archetype_component_access: Default::default(),
             });
         }
@@ -111,11 +140,33 @@ impl ParallelSystemExecutor for ParallelExecutor {
             self.events_sender = Some(sender);
         }
 
+        // The task pool of the world, shared by all the executors, if it has an `ExecutorConfig`.
+        let own_task_pool = ExecutorTaskPool::get(world);
+        // Systems may still use the global task pool, for parallel iteration.
+        let global_task_pool = ComputeTaskPool::init(TaskPool::default);
+        let task_pool = match &own_task_pool {
+            Some(task_pool) => &**task_pool,
+            None => global_task_pool,
+        };
+        // On wasm, the task pool runs everything on the current thread anyway.
+        if self.single_threaded || cfg!(target_arch = "wasm32") || task_pool.thread_num() == 0 {
+            SingleThreadedExecutor.run_systems(systems, world);
+            return;
+        }
+        if own_task_pool.is_some() {
+            ExecutorConfig::record_active(world, task_pool.thread_num());
+        }
+
         {
             #[cfg(feature = "trace")]
             let _span = bevy_utils::tracing::info_span!("update_archetypes").entered();
             for (index, container) in systems.iter_mut().enumerate() {
+                // Systems that don't run this time may not be initialized yet.
+                if !container.should_run() {
+                    continue;
+                }
                 let meta = &mut self.system_metadata[index];
+                meta.is_read_only = container.is_read_only();
                 let system = container.system_mut();
                 system.update_archetype_component_access(world);
                 meta.archetype_component_access
@@ -123,7 +174,19 @@ impl ParallelSystemExecutor for ParallelExecutor {
             }
         }
 
-        ComputeTaskPool::init(TaskPool::default).scope(|scope| {
+        if world.contains_resource::<SystemExecutionOrder>() {
+            self.finished_order.get_or_insert_with(Vec::new).clear();
+        } else {
+            self.finished_order = None;
+        }
+        if world.contains_resource::<ContentionReport>() {
+            self.contention.get_or_insert_with(HashMap::default).clear();
+            self.contended.clear();
+        } else {
+            self.contention = None;
+        }
+
+        task_pool.scope(|scope| {
             self.prepare_systems(scope, systems, world);
             let parallel_executor = async {
                 // All systems have been ran if there are no queued or running systems.
@@ -154,10 +217,37 @@ impl ParallelSystemExecutor for ParallelExecutor {
             let parallel_executor = parallel_executor.instrument(span);
             scope.spawn(parallel_executor);
         });
+
+        if let Some(finished_order) = &self.finished_order {
+            SystemExecutionOrder::record(
+                world,
+                finished_order.iter().map(|index| systems[*index].name()),
+            );
+        }
+        if let Some(contention) = &self.contention {
+            ContentionReport::record(world, contention);
+        }
     }
 }
 
 impl ParallelExecutor {
+    /// Sets whether systems are always run one after the other on the current thread, even if
+    /// the [`ComputeTaskPool`] has several threads.
+    ///
+    /// This is useful on targets where threads may be unavailable, or to rule out parallelism
+    /// when debugging.
+    pub fn with_single_threaded(mut self, single_threaded: bool) -> Self {
+        self.single_threaded = single_threaded;
+        self
+    }
+
+    /// Returns whether systems are always run one after the other on the current thread.
+    ///
+    /// They are also run that way on wasm, or if the [`ComputeTaskPool`] has no threads.
+    pub fn is_single_threaded(&self) -> bool {
+        self.single_threaded
+    }
+
     /// Populates `should_run` bitset, spawns tasks for systems that should run this iteration,
     /// queues systems with no dependencies to run (or skip) at next opportunity.
     fn prepare_systems<'scope>(
@@ -230,10 +320,19 @@ impl ParallelExecutor {
     /// Starts all non-conflicting queued systems, moves them from `queued` to `running`,
     /// adds their access information to active access information;
     /// processes queued systems that shouldn't run this iteration as completed immediately.
+    ///
+    /// Read-only systems are started after the others, so that they don't hold back a writer
+    /// they conflict with and only fill the slots that remain.
     async fn process_queued_systems(&mut self) {
         #[cfg(test)]
         let mut started_systems = 0;
-        for index in self.queued.ones() {
+        let (queued, metadata) = (&self.queued, &self.system_metadata);
+        let read_only_last = [false, true].into_iter().flat_map(|read_only| {
+            queued
+                .ones()
+                .filter(move |index| metadata[*index].is_read_only == read_only)
+        });
+        for index in read_only_last {
             // If the system shouldn't actually run this iteration, process it as completed
             // immediately; otherwise, check for conflicts and signal its task to start.
             let system_metadata = &self.system_metadata[index];
@@ -256,6 +355,16 @@ impl ParallelExecutor {
                 // Add this system's access information to the active access information.
                 self.active_archetype_component_access
                     .extend(&system_metadata.archetype_component_access);
+            } else if let Some(contention) = &mut self.contention {
+                // Only count a waiting system once, however many times it is checked.
+                if !self.contended.put(index) {
+                    for conflict in system_metadata
+                        .archetype_component_access
+                        .get_conflicts(&self.active_archetype_component_access)
+                    {
+                        *contention.entry(conflict).or_default() += 1;
+                    }
+                }
             }
         }
         #[cfg(test)]
@@ -277,6 +386,9 @@ impl ParallelExecutor {
         }
         self.running.set(index, false);
         self.dependants_scratch.extend(&system_data.dependants);
+        if let Some(finished_order) = &mut self.finished_order {
+            finished_order.push(index);
+        }
     }
 
     /// Discards active access information and builds it again using currently
@@ -336,6 +448,49 @@ mod tests {
         events
     }
 
+    #[test]
+    fn forced_single_threaded() {
+        use crate::schedule::{
+            ParallelExecutor, ParallelSystemDescriptorCoercion, SystemExecutionOrder,
+        };
+
+        fn first(mut value: ResMut<usize>) {
+            assert_eq!(*value, 0);
+            *value += 1;
+        }
+        fn second(mut value: ResMut<usize>) {
+            assert_eq!(*value, 1);
+            *value *= 10;
+        }
+        fn third(value: Res<usize>, query: Query<&W<usize>>) {
+            assert_eq!(*value, 10);
+            assert_eq!(query.iter().count(), 1);
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0usize);
+        world.init_resource::<SystemExecutionOrder>();
+        world.spawn().insert(W(0usize));
+        let mut stage = SystemStage::new(Box::new(
+            ParallelExecutor::default().with_single_threaded(true),
+        ))
+        .with_system(third.after(second))
+        .with_system(second.after(first))
+        .with_system(first);
+        assert!(stage
+            .get_executor::<ParallelExecutor>()
+            .unwrap()
+            .is_single_threaded());
+        stage.run(&mut world);
+
+        assert_eq!(*world.resource::<usize>(), 10);
+        let order = world.resource::<SystemExecutionOrder>();
+        assert!(order.ran_before("first", "second"));
+        assert!(order.ran_before("second", "third"));
+        // Nothing was scheduled on the task pool.
+        assert!(receive_events(&world).is_empty());
+    }
+
     #[test]
     fn trivial() {
         let mut world = World::new();
@@ -477,4 +632,97 @@ mod tests {
         stage.set_executor(Box::new(SingleThreadedExecutor::default()));
         stage.run(&mut world);
     }
+
+    #[test]
+    fn lazily_initialized_non_send_resource() {
+        use crate::schedule::{ParallelSystemDescriptorCoercion, ShouldRun};
+        use std::thread;
+        let mut world = World::new();
+        world.insert_non_send_resource(thread::current().id());
+        world.insert_resource(false);
+        fn non_send(thread_id: NonSend<thread::ThreadId>) {
+            assert_eq!(thread::current().id(), *thread_id);
+        }
+        fn enabled(enabled: Res<bool>) -> ShouldRun {
+            if *enabled {
+                ShouldRun::Yes
+            } else {
+                ShouldRun::No
+            }
+        }
+        fn empty() {}
+        let mut stage = SystemStage::parallel()
+            .with_system(non_send.with_run_criteria(enabled))
+            .with_system(non_send.with_run_criteria(enabled))
+            .with_system(empty);
+        stage.run(&mut world);
+        assert_eq!(receive_events(&world), vec![StartedSystems(1)]);
+
+        // The systems are only known to be non-send once they are initialized.
+        *world.resource_mut::<bool>() = true;
+        stage.run(&mut world);
+        assert_eq!(
+            receive_events(&world),
+            vec![StartedSystems(2), StartedSystems(1)]
+        );
+    }
+
+    #[test]
+    fn read_only_systems() {
+        use crate::schedule::{ParallelSystemDescriptorCoercion, SystemExecutionOrder};
+
+        let mut world = World::new();
+        world.spawn().insert_bundle((W(0usize), W(0u32)));
+        world.init_resource::<SystemExecutionOrder>();
+        fn read_usize(query: Query<&W<usize>>) {
+            assert_eq!(query.iter().count(), 1);
+        }
+        fn read_usize_too(_: Query<&W<usize>>) {}
+        fn write_u32(mut query: Query<&mut W<u32>>) {
+            for mut value in query.iter_mut() {
+                value.0 += 1;
+            }
+        }
+        fn write_usize(mut query: Query<&mut W<usize>>) {
+            for mut value in query.iter_mut() {
+                value.0 += 1;
+            }
+        }
+
+        // Read-only systems run alongside writers of other data and alongside readers.
+        let mut stage = SystemStage::parallel()
+            .with_system(read_usize.read_only())
+            .with_system(write_u32)
+            .with_system(read_usize_too);
+        stage.run(&mut world);
+        assert_eq!(receive_events(&world), vec![StartedSystems(3)]);
+
+        // A writer queued after a read-only system it conflicts with still starts first.
+        world.resource_mut::<SystemExecutionOrder>().clear();
+        let mut stage = SystemStage::parallel()
+            .with_system(read_usize)
+            .with_system(write_usize);
+        stage.run(&mut world);
+        assert_eq!(
+            receive_events(&world),
+            vec![StartedSystems(1), StartedSystems(1)]
+        );
+        let order = world.resource::<SystemExecutionOrder>();
+        assert!(order.ran_before("write_usize", "read_usize"));
+        assert_eq!(world.query::<&W<usize>>().single(&world).0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "is marked as read-only, but it writes")]
+    fn read_only_system_with_writes() {
+        use crate::schedule::ParallelSystemDescriptorCoercion;
+
+        let mut world = World::new();
+        world.insert_resource(0usize);
+        fn write(mut value: ResMut<usize>) {
+            *value += 1;
+        }
+        let mut stage = SystemStage::parallel().with_system(write.read_only());
+        stage.run(&mut world);
+    }
 }
@@ -80,6 +80,10 @@ where
 }
 
 /// Generates a topological order for the given graph.
+///
+/// The order only depends on the graph, not on the iteration order of its maps: nodes and their
+/// dependencies are visited by increasing index, so nodes that aren't ordered relative to each
+/// other keep their relative index order.
 pub fn topological_order<Labels: Clone>(
     graph: &HashMap<usize, HashMap<usize, Labels>>,
 ) -> Result<Vec<usize>, DependencyGraphError<Labels>> {
@@ -96,7 +100,9 @@ pub fn topological_order<Labels: Clone>(
             return false;
         }
         current.push(*node);
-        for dependency in graph.get(node).unwrap().keys() {
+        let mut dependencies = graph.get(node).unwrap().keys().collect::<Vec<_>>();
+        dependencies.sort_unstable();
+        for dependency in dependencies {
             if check_if_cycles_and_visit(dependency, graph, sorted, unvisited, current) {
                 return true;
             }
@@ -109,8 +115,10 @@ pub fn topological_order<Labels: Clone>(
     let mut current = Vec::with_capacity(graph.len());
     let mut unvisited = HashSet::with_capacity_and_hasher(graph.len(), Default::default());
     unvisited.extend(graph.keys().cloned());
-    while let Some(node) = unvisited.iter().next().cloned() {
-        if check_if_cycles_and_visit(&node, graph, &mut sorted, &mut unvisited, &mut current) {
+    let mut nodes = graph.keys().collect::<Vec<_>>();
+    nodes.sort_unstable();
+    for node in nodes {
+        if check_if_cycles_and_visit(node, graph, &mut sorted, &mut unvisited, &mut current) {
             let mut cycle = Vec::new();
             let last_window = [*current.last().unwrap(), current[0]];
             let mut windows = current
@@ -3,7 +3,11 @@
 //! When using Bevy ECS, systems are usually not run directly, but are inserted into a
 //!  [`Stage`], which then lives within a [`Schedule`].
 
+mod contention;
+mod description;
+mod execution_order;
 mod executor;
+mod executor_config;
 mod executor_parallel;
 pub mod graph_utils;
 mod label;
@@ -14,7 +18,11 @@ mod system_container;
 mod system_descriptor;
 mod system_set;
 
+pub use contention::*;
+pub use description::*;
+pub use execution_order::*;
 pub use executor::*;
+pub use executor_config::*;
 pub use executor_parallel::*;
 pub use graph_utils::GraphNode;
 pub use label::*;
@@ -25,10 +33,10 @@ pub use system_container::*;
 pub use system_descriptor::*;
 pub use system_set::*;
 
-use std::fmt::Debug;
+use std::{borrow::Cow, fmt::Debug};
 
 use crate::{system::IntoSystem, world::World};
-use bevy_utils::HashMap;
+use bevy_utils::{tracing::warn, HashMap};
 
 /// A container of [`Stage`]s set to be run in a linear order.
 ///
@@ -36,11 +44,18 @@ use bevy_utils::HashMap;
 /// In this way, the properties of the child schedule can be set differently from the parent.
 /// For example, it can be set to run only once during app execution, while the parent schedule
 /// runs indefinitely.
+///
+/// A schedule can also own named sub-schedules, which don't run as part of its stages. Instead,
+/// while the schedule runs, its systems can run them on demand, any number of times, with
+/// [`World::run_schedule`] or [`Commands::run_schedule`](crate::system::Commands::run_schedule).
+/// This is useful for passes like render extraction or sub-stepped simulations, which need their
+/// own stages and command flushes but operate on the same [`World`].
 #[derive(Default)]
 pub struct Schedule {
     stages: HashMap<BoxedStageLabel, Box<dyn Stage>>,
     stage_order: Vec<BoxedStageLabel>,
     run_criteria: BoxedRunCriteria,
+    sub_schedules: HashMap<BoxedStageLabel, Schedule>,
 }
 
 impl Schedule {
@@ -75,6 +90,13 @@ impl Schedule {
         self
     }
 
+    /// Similar to [`add_sub_schedule`](Self::add_sub_schedule), but it also returns itself.
+    #[must_use]
+    pub fn with_sub_schedule(mut self, label: impl StageLabel, schedule: Schedule) -> Self {
+        self.add_sub_schedule(label, schedule);
+        self
+    }
+
     #[must_use]
     pub fn with_run_criteria<S: IntoSystem<(), ShouldRun, P>, P>(mut self, system: S) -> Self {
         self.set_run_criteria(system);
@@ -218,6 +240,71 @@ impl Schedule {
         self
     }
 
+    /// Adds the given `system` as a finalizer of the stage identified by `stage_label`, which
+    /// runs once at the end of each run of the stage.
+    ///
+    /// See [`SystemStage::add_stage_finalizer`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # fn cleanup() {}
+    /// # let mut schedule = Schedule::default();
+    /// # schedule.add_stage("my_stage", SystemStage::parallel());
+    /// #
+    /// schedule.add_stage_finalizer("my_stage", cleanup);
+    /// ```
+    pub fn add_stage_finalizer<Params>(
+        &mut self,
+        stage_label: impl StageLabel,
+        system: impl IntoSystem<(), (), Params>,
+    ) -> &mut Self {
+        #[cold]
+        fn stage_not_found(stage_label: &dyn Debug) -> ! {
+            panic!(
+                "Stage '{:?}' does not exist or is not a SystemStage",
+                stage_label
+            )
+        }
+
+        let stage = self
+            .get_stage_mut::<SystemStage>(&stage_label)
+            .unwrap_or_else(move || stage_not_found(&stage_label));
+        stage.add_stage_finalizer(system);
+        self
+    }
+
+    /// Adds a system built by `build_system` to each stage identified by `stage_labels`.
+    ///
+    /// A system can only be added to a single stage, so `build_system` is called once per stage
+    /// to build an identical copy of the system, along with its labels, ordering constraints and
+    /// run criteria. Each copy has its own state, such as [`Local`](crate::system::Local)s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # fn handle_input() {}
+    /// # let mut schedule = Schedule::default();
+    /// # schedule.add_stage("playing", SystemStage::parallel());
+    /// # schedule.add_stage("paused", SystemStage::parallel());
+    /// #
+    /// schedule.add_system_to_stages(["playing", "paused"], || handle_input.label("input"));
+    /// ```
+    pub fn add_system_to_stages<Params, S: IntoSystemDescriptor<Params>>(
+        &mut self,
+        stage_labels: impl IntoIterator<Item = impl StageLabel>,
+        build_system: impl Fn() -> S,
+    ) -> &mut Self {
+        for stage_label in stage_labels {
+            self.add_system_to_stage(stage_label, build_system());
+        }
+        self
+    }
+
     /// Adds the given `system_set` to the stage identified by `stage_label`.
     ///
     /// # Example
@@ -330,14 +417,141 @@ impl Schedule {
             .and_then(|stage| stage.downcast_mut::<T>())
     }
 
+    /// Adds a sub-schedule identified by `label`, which the systems of this schedule can run on
+    /// demand with [`World::run_schedule`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # fn physics_step() {}
+    /// fn run_physics(world: &mut World) {
+    ///     for _ in 0..4 {
+    ///         world.run_schedule("physics");
+    ///     }
+    /// }
+    ///
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_sub_schedule(
+    ///     "physics",
+    ///     Schedule::default().with_stage("step", SystemStage::parallel().with_system(physics_step)),
+    /// );
+    /// schedule.add_stage("update", SystemStage::single(run_physics.exclusive_system()));
+    /// schedule.run_once(&mut World::new());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a sub-schedule with the same label has already been added.
+    pub fn add_sub_schedule(&mut self, label: impl StageLabel, schedule: Schedule) -> &mut Self {
+        let label: Box<dyn StageLabel> = Box::new(label);
+        let prev = self.sub_schedules.insert(label.clone(), schedule);
+        assert!(prev.is_none(), "Sub-schedule already exists: {:?}.", label);
+        self
+    }
+
     /// Executes each [`Stage`] contained in the schedule, one at a time.
     pub fn run_once(&mut self, world: &mut World) {
+        let sub_schedule_labels = self.lend_sub_schedules(world);
         for label in &self.stage_order {
             #[cfg(feature = "trace")]
             let _stage_span = bevy_utils::tracing::info_span!("stage", name = ?label).entered();
             let stage = self.stages.get_mut(label).unwrap();
             stage.run(world);
         }
+        self.reclaim_sub_schedules(world, sub_schedule_labels);
+    }
+
+    /// Moves the sub-schedules into the [`SubSchedules`] resource, so they can be run by systems,
+    /// and returns their labels.
+    fn lend_sub_schedules(&mut self, world: &mut World) -> Vec<BoxedStageLabel> {
+        if self.sub_schedules.is_empty() {
+            return Vec::new();
+        }
+        let mut sub_schedules = world.get_resource_or_insert_with(SubSchedules::default);
+        self.sub_schedules
+            .drain()
+            .map(|(label, schedule)| {
+                let prev = sub_schedules.schedules.insert(label.clone(), schedule);
+                assert!(
+                    prev.is_none(),
+                    "Sub-schedule {:?} is already provided by another running schedule.",
+                    label
+                );
+                label
+            })
+            .collect()
+    }
+
+    fn reclaim_sub_schedules(&mut self, world: &mut World, labels: Vec<BoxedStageLabel>) {
+        if labels.is_empty() {
+            return;
+        }
+        let mut sub_schedules = world.resource_mut::<SubSchedules>();
+        for label in labels {
+            let schedule = sub_schedules
+                .schedules
+                .remove(&*label)
+                .expect("sub-schedules are put back after running");
+            self.sub_schedules.insert(label, schedule);
+        }
+    }
+
+    /// Returns a report of the execution order ambiguities found in every [`SystemStage`] of the
+    /// schedule, including those of nested schedules, or `None` if there are none.
+    ///
+    /// Each stage's report is preceded by the label of the stage.
+    /// See [`ReportExecutionOrderAmbiguities`] for more details.
+    pub fn ambiguity_report(&mut self, world: &mut World) -> Option<String> {
+        let mut report = String::new();
+        for label in &self.stage_order {
+            let stage = self.stages.get_mut(label).unwrap();
+            let stage_report = if let Some(stage) = stage.downcast_mut::<SystemStage>() {
+                stage.ambiguity_report(world)
+            } else if let Some(schedule) = stage.downcast_mut::<Schedule>() {
+                schedule.ambiguity_report(world)
+            } else {
+                None
+            };
+            if let Some(stage_report) = stage_report {
+                report.push_str(&format!("Stage {:?}:\n{}", label, stage_report));
+            }
+        }
+        if report.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+
+    /// Returns the names of the systems of every [`SystemStage`] of the schedule, including those
+    /// of nested schedules, that have never run, along with the label of their stage.
+    ///
+    /// See [`SystemStage::unreachable_systems`] for more details.
+    pub fn unreachable_systems(&self) -> Vec<(&dyn StageLabel, Cow<'static, str>)> {
+        let mut systems = Vec::new();
+        for (label, stage) in self.iter_stages() {
+            if let Some(stage) = stage.downcast_ref::<SystemStage>() {
+                systems.extend(
+                    stage
+                        .unreachable_systems()
+                        .into_iter()
+                        .map(|name| (label, name)),
+                );
+            } else if let Some(schedule) = stage.downcast_ref::<Schedule>() {
+                systems.extend(schedule.unreachable_systems());
+            }
+        }
+        systems
+    }
+
+    /// Logs a warning for each system of the schedule that has never run.
+    /// See [`Schedule::unreachable_systems`] for more details.
+    pub fn warn_unreachable_systems(&self) {
+        for (label, name) in self.unreachable_systems() {
+            warn!("System {} of stage {:?} has never run", name, label);
+        }
     }
 
     /// Iterates over all of schedule's stages and their labels, in execution order.
@@ -348,6 +562,47 @@ impl Schedule {
     }
 }
 
+/// The sub-schedules of the [`Schedule`]s that are currently running, which can be run with
+/// [`World::run_schedule`].
+///
+/// See [`Schedule::add_sub_schedule`] for more details.
+#[derive(Default)]
+pub struct SubSchedules {
+    schedules: HashMap<BoxedStageLabel, Schedule>,
+}
+
+impl World {
+    /// Runs the sub-schedule identified by `label` once on this [`World`].
+    ///
+    /// The sub-schedule must have been added to a running [`Schedule`] with
+    /// [`Schedule::add_sub_schedule`], so this is meant to be called from exclusive systems and
+    /// commands. See [`Schedule::add_sub_schedule`] for an example.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no running schedule has a sub-schedule with this label, or if the sub-schedule
+    /// is already running.
+    pub fn run_schedule(&mut self, label: impl StageLabel) {
+        self.run_schedule_dyn(&label);
+    }
+
+    pub(crate) fn run_schedule_dyn(&mut self, label: &dyn StageLabel) {
+        let (label, mut schedule) = self
+            .get_resource_mut::<SubSchedules>()
+            .and_then(|mut sub_schedules| sub_schedules.schedules.remove_entry(label))
+            .unwrap_or_else(|| {
+                panic!(
+                    "Sub-schedule {:?} does not exist or is already running.",
+                    label
+                )
+            });
+        schedule.run(self);
+        self.resource_mut::<SubSchedules>()
+            .schedules
+            .insert(label, schedule);
+    }
+}
+
 impl Stage for Schedule {
     fn run(&mut self, world: &mut World) {
         loop {
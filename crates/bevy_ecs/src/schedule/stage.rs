@@ -8,8 +8,9 @@ use crate::{
         ExclusiveSystemContainer, GraphNode, InsertionPoint, ParallelExecutor,
         ParallelSystemContainer, ParallelSystemExecutor, RunCriteriaContainer,
         RunCriteriaDescriptor, RunCriteriaDescriptorOrLabel, RunCriteriaInner, ShouldRun,
-        SingleThreadedExecutor, SystemContainer, SystemDescriptor, SystemSet,
+        SingleThreadedExecutor, SystemContainer, SystemDescriptor, SystemExecutionOrder, SystemSet,
     },
+    system::BoxedSystem,
     world::{World, WorldId},
 };
 use bevy_utils::{
@@ -18,7 +19,7 @@ use bevy_utils::{
 };
 use downcast_rs::{impl_downcast, Downcast};
 use fixedbitset::FixedBitSet;
-use std::fmt::Debug;
+use std::{borrow::Cow, fmt::Debug};
 
 use super::IntoSystemDescriptor;
 
@@ -48,11 +49,24 @@ impl_downcast!(Stage);
 /// by the ambiguity checker by adding `.before` or `.after` to one of the conflicting systems
 /// referencing the other system to force a specific ordering.
 ///
+/// Intentional ambiguities can be silenced by placing both systems in the same ambiguity set
+/// with `.in_ambiguity_set`; systems that share an ambiguity set are never reported against each
+/// other.
+///
 /// The checker may report a system more times than the amount of constraints it would actually need
 /// to have unambiguous order with regards to a group of already-constrained systems.
+///
+/// The same report can be retrieved on demand with [`SystemStage::ambiguity_report`] or
+/// [`Schedule::ambiguity_report`](super::Schedule::ambiguity_report).
 #[derive(Default)]
 pub struct ReportExecutionOrderAmbiguities;
 
+/// When this resource is present in the `App`'s `Resources`,
+/// each `SystemStage` will panic with the report described in [`ReportExecutionOrderAmbiguities`]
+/// if any of its systems have an ambiguous execution order.
+#[derive(Default)]
+pub struct DenyExecutionOrderAmbiguities;
+
 /// Stores and executes systems. Execution order is not defined unless explicitly specified;
 /// see `SystemDescriptor` documentation.
 pub struct SystemStage {
@@ -73,6 +87,8 @@ pub struct SystemStage {
     exclusive_at_end: Vec<ExclusiveSystemContainer>,
     /// Topologically sorted parallel systems.
     parallel: Vec<ParallelSystemContainer>,
+    /// Systems run once at the end of each run of the stage, in registration order.
+    finalizers: Vec<BoxedSystem<(), ()>>,
     /// Determines if the stage was modified and needs to rebuild its graphs and orders.
     systems_modified: bool,
     /// Determines if the stage's executor was changed.
@@ -87,6 +103,8 @@ pub struct SystemStage {
     uninitialized_at_end: Vec<usize>,
     /// Newly inserted systems that will be initialized at the next opportunity.
     uninitialized_parallel: Vec<usize>,
+    /// Newly inserted finalizers that will be initialized at the next opportunity.
+    uninitialized_finalizers: Vec<usize>,
     /// Saves the value of the World change_tick during the last tick check
     last_tick_check: u32,
     /// If true, buffers will be automatically applied at the end of the stage. If false, buffers must be manually applied.
@@ -106,12 +124,14 @@ impl SystemStage {
             exclusive_before_commands: Default::default(),
             exclusive_at_end: Default::default(),
             parallel: vec![],
+            finalizers: vec![],
             systems_modified: true,
             executor_modified: true,
             uninitialized_parallel: vec![],
             uninitialized_at_start: vec![],
             uninitialized_before_commands: vec![],
             uninitialized_at_end: vec![],
+            uninitialized_finalizers: vec![],
             last_tick_check: Default::default(),
             apply_buffers: true,
             must_read_resource: None,
@@ -219,16 +239,73 @@ impl SystemStage {
         }
     }
 
-    pub fn apply_buffers(&mut self, world: &mut World) {
-        for container in &mut self.parallel {
-            let system = container.system_mut();
+    /// Adds a stage finalizer: a system that runs once at the end of each run of the stage, after
+    /// every other system of the stage and after their commands are applied.
+    ///
+    /// Finalizers are meant for per-stage cleanup, such as clearing event buffers or resetting
+    /// frame-scoped resources. They ignore labels and ordering constraints, and run one after the
+    /// other in registration order, each with its commands applied before the next one runs. If
+    /// run criteria make the stage loop, they still only run once, after the last iteration.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Default)]
+    /// # struct FrameScratch(Vec<u32>);
+    /// fn clear_scratch(mut scratch: ResMut<FrameScratch>) {
+    ///     scratch.0.clear();
+    /// }
+    ///
+    /// let mut stage = SystemStage::parallel();
+    /// stage.add_stage_finalizer(clear_scratch);
+    /// ```
+    pub fn add_stage_finalizer<Params>(
+        &mut self,
+        system: impl IntoSystem<(), (), Params>,
+    ) -> &mut Self {
+        self.uninitialized_finalizers.push(self.finalizers.len());
+        self.finalizers
+            .push(Box::new(IntoSystem::into_system(system)));
+        self
+    }
+
+    /// Adds a stage finalizer. See [`add_stage_finalizer`](Self::add_stage_finalizer).
+    #[must_use]
+    pub fn with_stage_finalizer<Params>(mut self, system: impl IntoSystem<(), (), Params>) -> Self {
+        self.add_stage_finalizer(system);
+        self
+    }
+
+    /// Runs the stage finalizers, in registration order, applying the commands of each one.
+    fn run_finalizers(&mut self, world: &mut World) {
+        for index in self.uninitialized_finalizers.drain(..) {
+            self.finalizers[index].initialize(world);
+        }
+        for system in &mut self.finalizers {
             #[cfg(feature = "trace")]
-            let _span = bevy_utils::tracing::info_span!("system_commands", name = &*system.name())
-                .entered();
+            let _system_span =
+                bevy_utils::tracing::info_span!("stage_finalizer", name = &*system.name())
+                    .entered();
+            system.run((), world);
             system.apply_buffers(world);
+            SystemExecutionOrder::record(world, [system.name()]);
         }
     }
 
+    pub fn apply_buffers(&mut self, world: &mut World) {
+        let parallel = &mut self.parallel;
+        world.coalesce_inserts(|world| {
+            // Systems that were never initialized have no buffers yet.
+            for container in parallel.iter_mut().filter(|c| c.is_initialized()) {
+                let system = container.system_mut();
+                #[cfg(feature = "trace")]
+                let _span =
+                    bevy_utils::tracing::info_span!("system_commands", name = &*system.name())
+                        .entered();
+                system.apply_buffers(world);
+            }
+        });
+    }
+
     pub fn set_apply_buffers(&mut self, apply_buffers: bool) {
         self.apply_buffers = apply_buffers;
     }
@@ -359,7 +436,17 @@ impl SystemStage {
         index
     }
 
+    /// Initializes the run criteria and every system of the stage.
     fn initialize_systems(&mut self, world: &mut World) {
+        self.initialize_run_criteria(world);
+        self.initialize_all_systems(world);
+    }
+
+    /// Initializes newly added run criteria and resolves the run criteria of newly added systems.
+    ///
+    /// Systems themselves are initialized lazily, right before they first run, so that systems
+    /// whose run criteria never pass don't pay for it.
+    fn initialize_run_criteria(&mut self, world: &mut World) {
         let mut criteria_labels = HashMap::default();
         let uninitialized_criteria: HashMap<_, _> =
             self.uninitialized_run_criteria.drain(..).collect();
@@ -407,32 +494,52 @@ impl SystemStage {
             if let Some(index) = container.run_criteria() {
                 container.set_run_criteria(new_indices[index]);
             }
-            container.system_mut().initialize(world);
         }
         for index in self.uninitialized_before_commands.drain(..) {
             let container = &mut self.exclusive_before_commands[index];
             if let Some(index) = container.run_criteria() {
                 container.set_run_criteria(new_indices[index]);
             }
-            container.system_mut().initialize(world);
         }
         for index in self.uninitialized_at_end.drain(..) {
             let container = &mut self.exclusive_at_end[index];
             if let Some(index) = container.run_criteria() {
                 container.set_run_criteria(new_indices[index]);
             }
-            container.system_mut().initialize(world);
         }
         for index in self.uninitialized_parallel.drain(..) {
             let container = &mut self.parallel[index];
             if let Some(index) = container.run_criteria() {
                 container.set_run_criteria(new_indices[index]);
             }
-            container.system_mut().initialize(world);
         }
     }
 
-    /// Rearranges all systems in topological orders. Systems must be initialized.
+    /// Initializes every system that hasn't been initialized yet, regardless of its run criteria.
+    fn initialize_all_systems(&mut self, world: &mut World) {
+        for container in self
+            .exclusive_at_start
+            .iter_mut()
+            .chain(&mut self.exclusive_before_commands)
+            .chain(&mut self.exclusive_at_end)
+        {
+            container.initialize(world);
+        }
+        for container in &mut self.parallel {
+            if container.initialize(world) {
+                self.executor_modified = true;
+            }
+        }
+    }
+
+    /// Returns `true` if the access of every system is needed to report diagnostics.
+    fn needs_system_access(&self, world: &World) -> bool {
+        self.must_read_resource.is_some()
+            || world.contains_resource::<ReportExecutionOrderAmbiguities>()
+            || world.contains_resource::<DenyExecutionOrderAmbiguities>()
+    }
+
+    /// Rearranges all systems in topological orders. Run criteria must be initialized.
     fn rebuild_orders_and_dependencies(&mut self) {
         // This assertion exists to document that the number of systems in a stage is limited
         // to guarantee that change detection never yields false positives. However, it's possible
@@ -503,8 +610,91 @@ impl SystemStage {
         );
     }
 
-    /// Logs execution order ambiguities between systems. System orders must be fresh.
+    /// Returns a report of the pairs of systems in this stage that have conflicting access and no
+    /// execution ordering constraint between them, or `None` if there are no such pairs.
+    ///
+    /// Systems that share an ambiguity set are not reported. See
+    /// [`ReportExecutionOrderAmbiguities`] for more details.
+    pub fn ambiguity_report(&mut self, world: &mut World) -> Option<String> {
+        self.update_systems(world);
+        self.initialize_all_systems(world);
+        self.build_ambiguity_report(world)
+    }
+
+    /// Returns the names of the systems of this stage that have never run, for example because
+    /// their run criteria never returned [`ShouldRun::Yes`].
+    ///
+    /// This only observes past runs of the stage, so it is meant to be checked after the stage
+    /// had a chance to run every system, such as after a few frames of an app.
+    pub fn unreachable_systems(&self) -> Vec<Cow<'static, str>> {
+        let parallel = self
+            .parallel
+            .iter()
+            .filter(|container| container.run_count == 0)
+            .map(|container| container.name());
+        let exclusive = self
+            .exclusive_at_start
+            .iter()
+            .chain(&self.exclusive_before_commands)
+            .chain(&self.exclusive_at_end)
+            .filter(|container| container.run_count == 0)
+            .map(|container| container.name());
+        parallel.chain(exclusive).collect()
+    }
+
+    /// Logs a warning for each system of this stage that has never run.
+    /// See [`SystemStage::unreachable_systems`] for more details.
+    pub fn warn_unreachable_systems(&self) {
+        for name in self.unreachable_systems() {
+            warn!("System {} has never run", name);
+        }
+    }
+
+    /// Initializes newly added systems and rebuilds the execution order if the stage was modified.
+    fn update_systems(&mut self, world: &mut World) {
+        if let Some(world_id) = self.world_id {
+            assert!(
+                world.id() == world_id,
+                "Cannot run SystemStage on two different Worlds"
+            );
+        } else {
+            self.world_id = Some(world.id());
+        }
+
+        if self.systems_modified {
+            if self.needs_system_access(world) {
+                self.initialize_systems(world);
+            } else {
+                self.initialize_run_criteria(world);
+            }
+            self.rebuild_orders_and_dependencies();
+            self.systems_modified = false;
+            self.executor.rebuild_cached_data(&self.parallel);
+            self.executor_modified = false;
+            if world.contains_resource::<ReportExecutionOrderAmbiguities>()
+                || world.contains_resource::<DenyExecutionOrderAmbiguities>()
+            {
+                self.report_ambiguities(world);
+            }
+            if let Some(resource_id) = self.must_read_resource {
+                self.check_uses_resource(resource_id, world);
+            }
+        } else if self.executor_modified {
+            self.executor.rebuild_cached_data(&self.parallel);
+            self.executor_modified = false;
+        }
+    }
+
     fn report_ambiguities(&self, world: &World) {
+        if let Some(report) = self.build_ambiguity_report(world) {
+            if world.contains_resource::<DenyExecutionOrderAmbiguities>() {
+                panic!("{}", report);
+            }
+            info!("{}", report);
+        }
+    }
+
+    fn build_ambiguity_report(&self, world: &World) -> Option<String> {
         debug_assert!(!self.systems_modified);
         use std::fmt::Write;
         fn write_display_names_of_pairs(
@@ -568,7 +758,9 @@ impl SystemStage {
                 writeln!(string, " * Exclusive systems at end of stage:").unwrap();
                 write_display_names_of_pairs(&mut string, &self.exclusive_at_end, at_end, world);
             }
-            info!("{}", string);
+            Some(string)
+        } else {
+            None
         }
     }
 
@@ -610,6 +802,9 @@ impl SystemStage {
             for parallel_system in &mut self.parallel {
                 parallel_system.system_mut().check_change_tick(change_tick);
             }
+            for finalizer in &mut self.finalizers {
+                finalizer.check_change_tick(change_tick);
+            }
 
             // Check all component change ticks.
             world.check_change_ticks();
@@ -787,43 +982,21 @@ fn find_ambiguities(systems: &[impl SystemContainer]) -> Vec<(usize, usize, Vec<
 
 impl Stage for SystemStage {
     fn run(&mut self, world: &mut World) {
-        if let Some(world_id) = self.world_id {
-            assert!(
-                world.id() == world_id,
-                "Cannot run SystemStage on two different Worlds"
-            );
-        } else {
-            self.world_id = Some(world.id());
-        }
-
-        if self.systems_modified {
-            self.initialize_systems(world);
-            self.rebuild_orders_and_dependencies();
-            self.systems_modified = false;
-            self.executor.rebuild_cached_data(&self.parallel);
-            self.executor_modified = false;
-            if world.contains_resource::<ReportExecutionOrderAmbiguities>() {
-                self.report_ambiguities(world);
-            }
-            if let Some(resource_id) = self.must_read_resource {
-                self.check_uses_resource(resource_id, world);
-            }
-        } else if self.executor_modified {
-            self.executor.rebuild_cached_data(&self.parallel);
-            self.executor_modified = false;
-        }
+        self.update_systems(world);
 
         let mut run_stage_loop = true;
+        let mut stage_ran = false;
         while run_stage_loop {
             let should_run = self.stage_run_criteria.should_run(world);
             match should_run {
-                ShouldRun::No => return,
+                ShouldRun::No => break,
                 ShouldRun::NoAndCheckAgain => continue,
                 ShouldRun::YesAndCheckAgain => (),
                 ShouldRun::Yes => {
                     run_stage_loop = false;
                 }
             };
+            stage_ran = true;
 
             // Evaluate system run criteria.
             for index in 0..self.run_criteria.len() {
@@ -873,17 +1046,35 @@ impl Stage for SystemStage {
                             name = &*container.name()
                         )
                         .entered();
+                        container.initialize(world);
                         container.system_mut().run(world);
+                        container.run_count += 1;
+                        SystemExecutionOrder::record(world, [container.name()]);
                     }
                 }
 
                 // Run parallel systems using the executor.
                 // TODO: hard dependencies, nested sets, whatever... should be evaluated here.
+                let mut initialized_systems = false;
                 for container in &mut self.parallel {
                     container.should_run =
                         should_run(container, &self.run_criteria, default_should_run);
+                    if container.should_run {
+                        // Systems are initialized here, before the executor shares the world
+                        // between them.
+                        initialized_systems |= container.initialize(world);
+                    }
+                }
+                if initialized_systems {
+                    // Initialization determines whether systems are `Send`.
+                    self.executor.rebuild_cached_data(&self.parallel);
                 }
                 self.executor.run_systems(&mut self.parallel, world);
+                for container in &mut self.parallel {
+                    if container.should_run {
+                        container.run_count += 1;
+                    }
+                }
 
                 // Run systems that want to be between parallel systems and their command buffers.
                 for container in &mut self.exclusive_before_commands {
@@ -894,23 +1085,29 @@ impl Stage for SystemStage {
                             name = &*container.name()
                         )
                         .entered();
+                        container.initialize(world);
                         container.system_mut().run(world);
+                        container.run_count += 1;
+                        SystemExecutionOrder::record(world, [container.name()]);
                     }
                 }
 
                 // Apply parallel systems' buffers.
                 if self.apply_buffers {
-                    for container in &mut self.parallel {
-                        if container.should_run {
-                            #[cfg(feature = "trace")]
-                            let _span = bevy_utils::tracing::info_span!(
-                                "system_commands",
-                                name = &*container.name()
-                            )
-                            .entered();
-                            container.system_mut().apply_buffers(world);
+                    let parallel = &mut self.parallel;
+                    world.coalesce_inserts(|world| {
+                        for container in parallel {
+                            if container.should_run {
+                                #[cfg(feature = "trace")]
+                                let _span = bevy_utils::tracing::info_span!(
+                                    "system_commands",
+                                    name = &*container.name()
+                                )
+                                .entered();
+                                container.system_mut().apply_buffers(world);
+                            }
                         }
-                    }
+                    });
                 }
 
                 // Run systems that want to be at the end of stage.
@@ -922,7 +1119,10 @@ impl Stage for SystemStage {
                             name = &*container.name()
                         )
                         .entered();
+                        container.initialize(world);
                         container.system_mut().run(world);
+                        container.run_count += 1;
+                        SystemExecutionOrder::record(world, [container.name()]);
                     }
                 }
 
@@ -967,6 +1167,10 @@ impl Stage for SystemStage {
                 default_should_run = ShouldRun::No;
             }
         }
+
+        if stage_ran {
+            self.run_finalizers(world);
+        }
     }
 }
 
@@ -974,12 +1178,12 @@ impl Stage for SystemStage {
 mod tests {
     use crate::{
         schedule::{
-            BoxedSystemLabel, ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion,
-            RunCriteria, RunCriteriaDescriptorCoercion, ShouldRun, SingleThreadedExecutor, Stage,
-            SystemSet, SystemStage,
+            BoxedSystemLabel, DenyExecutionOrderAmbiguities, ExclusiveSystemDescriptorCoercion,
+            ParallelSystemDescriptorCoercion, RunCriteria, RunCriteriaDescriptorCoercion, Schedule,
+            ShouldRun, SingleThreadedExecutor, Stage, SystemContainer, SystemSet, SystemStage,
         },
-        system::{In, IntoExclusiveSystem, Local, Query, ResMut},
-        world::World,
+        system::{Commands, In, IntoExclusiveSystem, Local, Query, Res, ResMut},
+        world::{FromWorld, World},
     };
 
     use crate as bevy_ecs;
@@ -1053,6 +1257,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn stage_finalizers() {
+        #[derive(Component)]
+        struct Spawned;
+
+        fn spawn(mut commands: Commands) {
+            commands.spawn().insert(Spawned);
+        }
+        fn count_spawned(query: Query<&Spawned>, mut resource: ResMut<Vec<usize>>) {
+            resource.push(100 + query.iter().count());
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        let mut stage = SystemStage::parallel()
+            .with_stage_finalizer(make_parallel(4))
+            .with_system(make_parallel(1).label("1"))
+            .with_system(make_exclusive(3).exclusive_system().at_end())
+            .with_system(spawn.after("1"))
+            .with_system(make_parallel(2).after("1"));
+        stage.add_stage_finalizer(count_spawned);
+        for _ in 0..3 {
+            stage.run(&mut world);
+        }
+        // The finalizers run last, in registration order, once the commands are applied.
+        let resource = world.resource::<Vec<usize>>();
+        assert_eq!(resource.len(), 15);
+        for (frame, tags) in resource.chunks(5).enumerate() {
+            assert_eq!(tags[0], 1);
+            assert!(tags[1..3].contains(&2));
+            assert_eq!(tags[3..], [4, 101 + frame]);
+        }
+
+        // Finalizers run once, even when run criteria make the systems loop.
+        fn twice(mut runs: Local<usize>) -> ShouldRun {
+            *runs += 1;
+            if runs.is_multiple_of(3) {
+                ShouldRun::No
+            } else {
+                ShouldRun::YesAndCheckAgain
+            }
+        }
+        world.resource_mut::<Vec<usize>>().clear();
+        let mut stage = SystemStage::parallel()
+            .with_system(make_parallel(1).with_run_criteria(twice))
+            .with_stage_finalizer(make_parallel(4));
+        stage.run(&mut world);
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<Vec<usize>>(), vec![1, 1, 4, 1, 1, 4]);
+    }
+
     #[test]
     fn exclusive_after() {
         let mut world = World::new();
@@ -1990,6 +2245,286 @@ mod tests {
         assert_eq!(ambiguities.len(), 0);
     }
 
+    fn writer_a(_: Query<&mut W<f32>>) {}
+    fn writer_b(_: Query<&mut W<f32>>) {}
+
+    #[test]
+    fn unreachable_systems() {
+        fn never() -> ShouldRun {
+            ShouldRun::No
+        }
+        fn forgotten() {}
+        fn gameplay() {}
+
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        let mut schedule = Schedule::default();
+        schedule.add_stage(
+            "update",
+            SystemStage::parallel()
+                .with_system(gameplay)
+                .with_system(forgotten.with_run_criteria(never))
+                .with_system(
+                    make_exclusive(0)
+                        .exclusive_system()
+                        .at_end()
+                        .with_run_criteria(every_other_time),
+                ),
+        );
+
+        // Before running, every system is reported.
+        assert_eq!(schedule.unreachable_systems().len(), 3);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        let unreachable = schedule.unreachable_systems();
+        assert_eq!(unreachable.len(), 1);
+        assert_eq!(format!("{:?}", unreachable[0].0), "\"update\"");
+        assert!(unreachable[0].1.ends_with("forgotten"));
+        schedule.warn_unreachable_systems();
+
+        let stage = schedule.get_stage::<SystemStage>(&"update").unwrap();
+        let mut run_counts = stage
+            .parallel_systems()
+            .iter()
+            .map(|system| system.run_count())
+            .collect::<Vec<_>>();
+        run_counts.sort_unstable();
+        assert_eq!(run_counts, vec![0, 2]);
+    }
+
+    #[test]
+    fn deterministic_system_order() {
+        use crate::schedule::GraphNode;
+
+        fn a() {}
+        fn b() {}
+        fn c() {}
+        fn d() {}
+        fn e() {}
+        fn f() {}
+
+        fn system_order() -> Vec<String> {
+            let mut world = World::new();
+            let mut stage = SystemStage::parallel()
+                .with_system(a)
+                .with_system(b.after(e))
+                .with_system(c)
+                .with_system(d.before(a))
+                .with_system(e)
+                .with_system(f);
+            stage.run(&mut world);
+            stage
+                .parallel_systems()
+                .iter()
+                .map(|system| system.name().rsplit("::").next().unwrap().to_string())
+                .collect()
+        }
+
+        // Each map gets its own random state, so this would vary if the order depended on it.
+        let order = system_order();
+        for _ in 0..10 {
+            assert_eq!(system_order(), order);
+        }
+        // Unordered systems keep their insertion order.
+        assert_eq!(order, vec!["d", "a", "e", "b", "c", "f"]);
+    }
+
+    #[test]
+    fn ambiguity_report() {
+        let mut world = World::new();
+
+        let mut stage = SystemStage::parallel()
+            .with_system(writer_a)
+            .with_system(writer_b);
+        let report = stage.ambiguity_report(&mut world).unwrap();
+        assert!(report.contains("writer_a"));
+        assert!(report.contains("writer_b"));
+        assert!(report.contains("W<f32>"));
+
+        let mut stage = SystemStage::parallel()
+            .with_system(writer_a.label("a"))
+            .with_system(writer_b.after("a"));
+        assert_eq!(stage.ambiguity_report(&mut world), None);
+
+        let mut stage = SystemStage::parallel()
+            .with_system(writer_a.in_ambiguity_set("writers"))
+            .with_system(writer_b.in_ambiguity_set("writers"));
+        assert_eq!(stage.ambiguity_report(&mut world), None);
+
+        let mut schedule = Schedule::default()
+            .with_stage("ordered", SystemStage::parallel().with_system(writer_a))
+            .with_stage(
+                "unordered",
+                SystemStage::parallel()
+                    .with_system(writer_a)
+                    .with_system(writer_b),
+            );
+        let report = schedule.ambiguity_report(&mut world).unwrap();
+        assert!(report.contains("\"unordered\""));
+        assert!(!report.contains("\"ordered\""));
+    }
+
+    #[test]
+    fn add_system_to_stages() {
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        fn count_runs(mut runs: Local<usize>, mut log: ResMut<Vec<usize>>) {
+            *runs += 1;
+            log.push(*runs);
+        }
+
+        let mut schedule = Schedule::default()
+            .with_stage("playing", SystemStage::parallel())
+            .with_stage("paused", SystemStage::parallel());
+        schedule.add_system_to_stages(["playing", "paused"], || {
+            count_runs.with_run_criteria(ShouldRun::once)
+        });
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        // Each stage has its own copy of the system, with its own `Local` and run criteria.
+        assert_eq!(*world.resource::<Vec<usize>>(), vec![1, 1]);
+    }
+
+    #[test]
+    fn run_sub_schedule() {
+        #[derive(Component)]
+        struct Particle;
+        #[derive(Default)]
+        struct Steps(Vec<usize>);
+
+        fn spawn_particle(mut commands: Commands) {
+            commands.spawn().insert(Particle);
+        }
+        fn count_particles(query: Query<&Particle>, mut steps: ResMut<Steps>) {
+            steps.0.push(query.iter().count());
+        }
+        fn substep(world: &mut World) {
+            for _ in 0..3 {
+                world.run_schedule("substep");
+            }
+        }
+        fn substep_later(mut commands: Commands) {
+            commands.run_schedule("substep");
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Steps>();
+        // The sub-schedule has its own stages, and flushes the commands of each.
+        let sub_schedule = Schedule::default()
+            .with_stage("spawn", SystemStage::parallel().with_system(spawn_particle))
+            .with_stage(
+                "count",
+                SystemStage::parallel().with_system(count_particles),
+            );
+        let mut schedule = Schedule::default()
+            .with_sub_schedule("substep", sub_schedule)
+            .with_stage("update", SystemStage::single(substep.exclusive_system()))
+            .with_stage("late", SystemStage::single(substep_later));
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Steps>().0, vec![1, 2, 3, 4]);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Steps>().0, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sub-schedule \"substep\" does not exist or is already running.")]
+    fn run_missing_sub_schedule() {
+        World::new().run_schedule("substep");
+    }
+
+    #[test]
+    #[should_panic(expected = "Execution order ambiguities detected")]
+    fn deny_ambiguities() {
+        let mut world = World::new();
+        world.init_resource::<DenyExecutionOrderAmbiguities>();
+        let mut stage = SystemStage::parallel()
+            .with_system(writer_a)
+            .with_system(writer_b);
+        stage.run(&mut world);
+    }
+
+    struct CountInitializations;
+
+    impl FromWorld for CountInitializations {
+        fn from_world(world: &mut World) -> Self {
+            *world.resource_mut::<usize>() += 1;
+            CountInitializations
+        }
+    }
+
+    #[test]
+    fn lazy_system_initialization() {
+        fn counted(_: Local<CountInitializations>) {}
+        fn enabled(enabled: Res<bool>) -> ShouldRun {
+            if *enabled {
+                ShouldRun::Yes
+            } else {
+                ShouldRun::No
+            }
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0usize);
+        world.insert_resource(false);
+        let mut stage = SystemStage::parallel()
+            .with_system(counted.with_run_criteria(enabled))
+            .with_system(
+                counted
+                    .exclusive_system()
+                    .at_start()
+                    .with_run_criteria(enabled),
+            )
+            .with_system(
+                counted
+                    .exclusive_system()
+                    .at_end()
+                    .with_run_criteria(enabled),
+            );
+        stage.run(&mut world);
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<usize>(), 0);
+
+        *world.resource_mut::<bool>() = true;
+        stage.run(&mut world);
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<usize>(), 3);
+
+        // Systems are initialized eagerly when their access is needed for a report.
+        world.insert_resource(0usize);
+        world.insert_resource(false);
+        let mut stage = SystemStage::parallel().with_system(counted.with_run_criteria(enabled));
+        assert_eq!(stage.ambiguity_report(&mut world), None);
+        assert_eq!(*world.resource::<usize>(), 1);
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<usize>(), 1);
+    }
+
+    #[test]
+    fn apply_buffers_before_initialization() {
+        fn spawn(mut commands: Commands) {
+            commands.spawn();
+        }
+        fn disabled() -> ShouldRun {
+            ShouldRun::No
+        }
+
+        let mut world = World::new();
+        let mut stage = SystemStage::parallel().with_system(spawn);
+        stage.apply_buffers(&mut world);
+        assert_eq!(world.entities().len(), 0);
+
+        // A system skipped by its run criteria stays uninitialized.
+        let mut stage = SystemStage::parallel()
+            .with_system(spawn)
+            .with_system(spawn.with_run_criteria(disabled));
+        stage.set_apply_buffers(false);
+        stage.run(&mut world);
+        stage.apply_buffers(&mut world);
+        assert_eq!(world.entities().len(), 1);
+    }
+
     #[test]
     #[should_panic]
     fn multiple_worlds_same_stage() {
@@ -6,6 +6,7 @@ use crate::{
         GraphNode, ParallelSystemDescriptor,
     },
     system::{ExclusiveSystem, System},
+    world::World,
 };
 use std::borrow::Cow;
 
@@ -22,6 +23,8 @@ pub trait SystemContainer: GraphNode<Label = BoxedSystemLabel> {
     fn run_criteria_label(&self) -> Option<&BoxedRunCriteriaLabel>;
     fn ambiguity_sets(&self) -> &[BoxedAmbiguitySetLabel];
     fn component_access(&self) -> Option<&Access<ComponentId>>;
+    /// Returns the number of times the system has run.
+    fn run_count(&self) -> usize;
 }
 
 pub(super) struct ExclusiveSystemContainer {
@@ -33,6 +36,8 @@ pub(super) struct ExclusiveSystemContainer {
     before: Vec<BoxedSystemLabel>,
     after: Vec<BoxedSystemLabel>,
     ambiguity_sets: Vec<BoxedAmbiguitySetLabel>,
+    initialized: bool,
+    pub(super) run_count: usize,
 }
 
 impl ExclusiveSystemContainer {
@@ -46,12 +51,22 @@ impl ExclusiveSystemContainer {
             before: descriptor.before,
             after: descriptor.after,
             ambiguity_sets: descriptor.ambiguity_sets,
+            initialized: false,
+            run_count: 0,
         }
     }
 
     pub(super) fn system_mut(&mut self) -> &mut Box<dyn ExclusiveSystem> {
         &mut self.system
     }
+
+    /// Initializes the system if it hasn't been already.
+    pub(super) fn initialize(&mut self, world: &mut World) {
+        if !self.initialized {
+            self.system.initialize(world);
+            self.initialized = true;
+        }
+    }
 }
 
 impl GraphNode for ExclusiveSystemContainer {
@@ -103,6 +118,10 @@ impl SystemContainer for ExclusiveSystemContainer {
     fn component_access(&self) -> Option<&Access<ComponentId>> {
         None
     }
+
+    fn run_count(&self) -> usize {
+        self.run_count
+    }
 }
 
 pub struct ParallelSystemContainer {
@@ -115,6 +134,9 @@ pub struct ParallelSystemContainer {
     before: Vec<BoxedSystemLabel>,
     after: Vec<BoxedSystemLabel>,
     ambiguity_sets: Vec<BoxedAmbiguitySetLabel>,
+    read_only: bool,
+    initialized: bool,
+    pub(crate) run_count: usize,
 }
 
 impl ParallelSystemContainer {
@@ -129,6 +151,9 @@ impl ParallelSystemContainer {
             before: descriptor.before,
             after: descriptor.after,
             ambiguity_sets: descriptor.ambiguity_sets,
+            read_only: descriptor.read_only,
+            initialized: false,
+            run_count: 0,
         }
     }
 
@@ -148,6 +173,49 @@ impl ParallelSystemContainer {
         self.should_run
     }
 
+    /// Returns `true` if the system has been initialized. Systems are only initialized once they
+    /// first need to run, or once their access is needed to report diagnostics.
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// Returns `true` if the system has been initialized and declares no write, so it never
+    /// blocks another system. The parallel executor starts these systems last, in the slots that
+    /// the other systems leave free.
+    pub fn is_read_only(&self) -> bool {
+        self.initialized && self.system.component_access().writes().next().is_none()
+    }
+
+    /// Initializes the system if it hasn't been already. Returns `true` if it was initialized by
+    /// this call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the system was marked [`read_only`](super::ParallelSystemDescriptorCoercion::read_only)
+    /// but declares a write.
+    pub(crate) fn initialize(&mut self, world: &mut World) -> bool {
+        if self.initialized {
+            return false;
+        }
+        self.system.initialize(world);
+        self.initialized = true;
+        if self.read_only && !self.is_read_only() {
+            let writes = self
+                .system
+                .component_access()
+                .writes()
+                .filter_map(|id| world.components().get_info(id))
+                .map(|info| info.name())
+                .collect::<Vec<_>>();
+            panic!(
+                "The system {} is marked as read-only, but it writes {}.",
+                self.system.name(),
+                writes.join(", ")
+            );
+        }
+        true
+    }
+
     pub fn dependencies(&self) -> &[usize] {
         &self.dependencies
     }
@@ -202,4 +270,8 @@ impl SystemContainer for ParallelSystemContainer {
     fn component_access(&self) -> Option<&Access<ComponentId>> {
         Some(self.system().component_access())
     }
+
+    fn run_count(&self) -> usize {
+        self.run_count
+    }
 }
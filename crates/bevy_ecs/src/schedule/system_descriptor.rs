@@ -42,6 +42,11 @@ pub enum SystemDescriptor {
     Exclusive(ExclusiveSystemDescriptor),
 }
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a valid system",
+    label = "invalid system",
+    note = "every parameter of a system function must implement `SystemParam`"
+)]
 pub trait IntoSystemDescriptor<Params> {
     fn into_descriptor(self) -> SystemDescriptor;
 }
@@ -102,6 +107,7 @@ pub struct ParallelSystemDescriptor {
     pub(crate) before: Vec<BoxedSystemLabel>,
     pub(crate) after: Vec<BoxedSystemLabel>,
     pub(crate) ambiguity_sets: Vec<BoxedAmbiguitySetLabel>,
+    pub(crate) read_only: bool,
 }
 
 fn new_parallel_descriptor(system: BoxedSystem<(), ()>) -> ParallelSystemDescriptor {
@@ -112,6 +118,7 @@ fn new_parallel_descriptor(system: BoxedSystem<(), ()>) -> ParallelSystemDescrip
         before: Vec::new(),
         after: Vec::new(),
         ambiguity_sets: Vec::new(),
+        read_only: false,
     }
 }
 
@@ -135,6 +142,12 @@ pub trait ParallelSystemDescriptorCoercion<Params> {
     /// Specifies that the system is exempt from execution order ambiguity detection
     /// with other systems in this set.
     fn in_ambiguity_set(self, set: impl AmbiguitySetLabel) -> ParallelSystemDescriptor;
+
+    /// Asserts that the system only reads data, so that it never blocks another system.
+    ///
+    /// Systems without writes are read-only whether they are marked or not: this documents the
+    /// intent, and panics when the system is initialized if it declares a write.
+    fn read_only(self) -> ParallelSystemDescriptor;
 }
 
 impl ParallelSystemDescriptorCoercion<()> for ParallelSystemDescriptor {
@@ -165,6 +178,11 @@ impl ParallelSystemDescriptorCoercion<()> for ParallelSystemDescriptor {
         self.ambiguity_sets.push(Box::new(set));
         self
     }
+
+    fn read_only(mut self) -> ParallelSystemDescriptor {
+        self.read_only = true;
+        self
+    }
 }
 
 impl<S, Params> ParallelSystemDescriptorCoercion<Params> for S
@@ -194,6 +212,10 @@ where
     fn in_ambiguity_set(self, set: impl AmbiguitySetLabel) -> ParallelSystemDescriptor {
         new_parallel_descriptor(Box::new(IntoSystem::into_system(self))).in_ambiguity_set(set)
     }
+
+    fn read_only(self) -> ParallelSystemDescriptor {
+        new_parallel_descriptor(Box::new(IntoSystem::into_system(self))).read_only()
+    }
 }
 
 impl ParallelSystemDescriptorCoercion<()> for BoxedSystem<(), ()> {
@@ -219,6 +241,10 @@ impl ParallelSystemDescriptorCoercion<()> for BoxedSystem<(), ()> {
     fn in_ambiguity_set(self, set: impl AmbiguitySetLabel) -> ParallelSystemDescriptor {
         new_parallel_descriptor(self).in_ambiguity_set(set)
     }
+
+    fn read_only(self) -> ParallelSystemDescriptor {
+        new_parallel_descriptor(self).read_only()
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
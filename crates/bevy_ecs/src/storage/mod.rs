@@ -7,9 +7,83 @@ mod table;
 pub use sparse_set::*;
 pub use table::*;
 
+use crate::component::ComponentId;
+
 /// The raw data stores of a [World](crate::world::World)
 #[derive(Default)]
 pub struct Storages {
     pub sparse_sets: SparseSets,
     pub tables: Tables,
 }
+
+impl Storages {
+    /// Returns the number of bytes allocated for the values of the component `component_id`,
+    /// across all tables and its sparse set.
+    ///
+    /// Only the values are counted, and zero-sized components never allocate any bytes for them.
+    /// Change ticks aren't counted: they are stored per entity for every component, zero-sized
+    /// ones included.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// let mut world = World::new();
+    /// world.spawn_batch((0..100).map(|_| (Player,)));
+    /// let id = world.init_component::<Player>();
+    /// assert_eq!(world.storages().component_data_bytes(id), 0);
+    /// ```
+    pub fn component_data_bytes(&self, component_id: ComponentId) -> usize {
+        let table_bytes: usize = self
+            .tables
+            .iter()
+            .filter_map(|table| table.get_column(component_id))
+            .map(Column::data_bytes)
+            .sum();
+        let sparse_set_bytes = self
+            .sparse_sets
+            .get(component_id)
+            .map_or(0, ComponentSparseSet::data_bytes);
+        table_bytes + sparse_set_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as bevy_ecs, component::Component, query::With, world::World};
+
+    #[derive(Component)]
+    struct Player;
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct Selected;
+
+    #[derive(Component)]
+    struct Position(u64);
+
+    #[test]
+    fn zero_sized_components_take_no_data_bytes() {
+        let mut world = World::new();
+        world.spawn_batch((0..1_000_000).map(|i| (Player, Selected, Position(i))));
+
+        let player = world.init_component::<Player>();
+        let selected = world.init_component::<Selected>();
+        let position = world.init_component::<Position>();
+        let storages = world.storages();
+        assert_eq!(storages.component_data_bytes(player), 0);
+        assert_eq!(storages.component_data_bytes(selected), 0);
+        assert!(storages.component_data_bytes(position) >= 1_000_000 * 8);
+
+        // Queries and filters on zero-sized components still work.
+        let mut query = world.query_filtered::<&Position, (With<Player>, With<Selected>)>();
+        assert_eq!(
+            query
+                .iter(&world)
+                .filter(|position| position.0 % 2 == 0)
+                .count(),
+            500_000
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    component::{ComponentId, ComponentInfo, ComponentTicks},
+    component::{ColumnChangeTick, ComponentId, ComponentInfo, ComponentTicks},
     entity::Entity,
     storage::Column,
 };
@@ -113,6 +113,14 @@ impl ComponentSparseSet {
         self.dense.len() == 0
     }
 
+    /// Returns the number of bytes allocated for the component values of this sparse set.
+    ///
+    /// See [`Column::data_bytes`].
+    #[inline]
+    pub fn data_bytes(&self) -> usize {
+        self.dense.data_bytes()
+    }
+
     /// Inserts the `entity` key and component `value` pair into this sparse
     /// set.
     ///
@@ -127,6 +135,7 @@ impl ComponentSparseSet {
         } else {
             let dense_index = self.dense.len();
             self.dense.push(value, ComponentTicks::new(change_tick));
+            self.dense.record_change(change_tick);
             self.sparse.insert(entity.id(), dense_index as u32);
             #[cfg(debug_assertions)]
             assert_eq!(self.entities.len(), dense_index);
@@ -178,6 +187,12 @@ impl ComponentSparseSet {
         }
     }
 
+    /// Returns the most recent change tick of all the values in this sparse set.
+    #[inline]
+    pub fn change_tick(&self) -> &ColumnChangeTick {
+        self.dense.change_tick()
+    }
+
     #[inline]
     pub fn get_ticks(&self, entity: Entity) -> Option<&UnsafeCell<ComponentTicks>> {
         let dense_index = *self.sparse.get(entity.id())? as usize;
@@ -1,11 +1,11 @@
 use crate::{
-    component::{ComponentId, ComponentInfo, ComponentTicks, Components},
+    component::{ColumnChangeTick, ComponentId, ComponentInfo, ComponentTicks, Components},
     entity::Entity,
     query::debug_checked_unreachable,
     storage::{blob_vec::BlobVec, SparseSet},
 };
 use bevy_ptr::{OwningPtr, Ptr, PtrMut};
-use bevy_utils::HashMap;
+use bevy_utils::hashbrown::HashMap;
 use std::alloc::Layout;
 use std::{
     cell::UnsafeCell,
@@ -36,6 +36,7 @@ impl TableId {
 pub struct Column {
     data: BlobVec,
     ticks: Vec<UnsafeCell<ComponentTicks>>,
+    change_tick: ColumnChangeTick,
 }
 
 impl Column {
@@ -45,6 +46,7 @@ impl Column {
             // SAFETY: component_info.drop() is valid for the types that will be inserted.
             data: unsafe { BlobVec::new(component_info.layout(), component_info.drop(), capacity) },
             ticks: Vec::with_capacity(capacity),
+            change_tick: ColumnChangeTick::default(),
         }
     }
 
@@ -69,6 +71,7 @@ impl Column {
         debug_assert!(row < self.len());
         self.data.initialize_unchecked(row, data);
         *self.ticks.get_unchecked_mut(row).get_mut() = ticks;
+        self.record_change(ticks.changed);
     }
 
     /// Writes component data to the column at given row.
@@ -84,6 +87,18 @@ impl Column {
             .get_unchecked_mut(row)
             .get_mut()
             .set_changed(change_tick);
+        self.record_change(change_tick);
+    }
+
+    /// Updates the [`ColumnChangeTick`] after a value was written at `change_tick`.
+    #[inline]
+    pub(crate) fn record_change(&mut self, change_tick: u32) {
+        if self.len() == 1 {
+            // the column's previous tick may be arbitrarily old
+            self.change_tick.set(change_tick);
+        } else {
+            self.change_tick.record(change_tick);
+        }
     }
 
     #[inline]
@@ -145,6 +160,7 @@ impl Column {
         let ptr = self.data.get_unchecked_mut(dst_row);
         other.data.swap_remove_unchecked(src_row, ptr);
         *self.ticks.get_unchecked_mut(dst_row) = other.ticks.swap_remove(src_row);
+        self.record_change(other.change_tick.get());
     }
 
     // # Safety
@@ -160,6 +176,15 @@ impl Column {
         self.ticks.reserve_exact(additional);
     }
 
+    /// Returns the number of bytes allocated for the component values of this column.
+    ///
+    /// This is always `0` for zero-sized components, such as marker components. The change ticks
+    /// of the column aren't counted.
+    #[inline]
+    pub fn data_bytes(&self) -> usize {
+        self.data.capacity() * self.item_layout().size()
+    }
+
     #[inline]
     pub fn get_data_ptr(&self) -> Ptr<'_> {
         self.data.get_ptr()
@@ -171,6 +196,11 @@ impl Column {
         self.data.get_slice()
     }
 
+    #[inline]
+    pub fn change_tick(&self) -> &ColumnChangeTick {
+        &self.change_tick
+    }
+
     #[inline]
     pub fn get_ticks_slice(&self) -> &[UnsafeCell<ComponentTicks>] {
         &self.ticks
@@ -212,6 +242,7 @@ impl Column {
         for component_ticks in &mut self.ticks {
             component_ticks.get_mut().check_ticks(change_tick);
         }
+        self.change_tick.check_tick(change_tick);
     }
 }
 
@@ -433,7 +464,7 @@ impl Table {
 /// Can be accessed via [`Storages`](crate::storage::Storages)
 pub struct Tables {
     tables: Vec<Table>,
-    table_ids: HashMap<Vec<ComponentId>, TableId>,
+    table_ids: HashMap<Vec<ComponentId>, TableId, fxhash::FxBuildHasher>,
 }
 
 impl Default for Tables {
@@ -0,0 +1,259 @@
+use super::CommandQueue;
+use crate::{
+    change_detection::DetectChanges,
+    world::{Mut, World},
+};
+use bevy_utils::{Duration, Instant};
+
+/// A resource bounding how many queued commands are applied per frame, to smooth out the frames
+/// in which systems queue enormous batches, such as when streaming a big chunk of a level.
+///
+/// While this resource exists, the commands of the [`Commands`](super::Commands) system parameters
+/// are applied in order until the budget of the frame is spent, and the remaining ones wait in a
+/// backlog. Commands queued later are added to the end of the backlog, so the order in which
+/// commands are applied never changes. [`CommandBudget::apply_backlog_system`] resets the budget
+/// and applies the backlog at the start of each frame, which `App` does in its first stage.
+///
+/// The budget is a number of commands, a duration since the start of the frame, or both. Each
+/// command is applied entirely, so a single command spawning a batch, such as
+/// [`Commands::spawn_batch`](super::Commands::spawn_batch), is never split. At least one command
+/// is applied per frame, so the backlog always drains.
+///
+/// The duration is checked every 64 commands, so a frame can apply up to 63 commands past it. A
+/// command superseded by a later command with the same [`Command::target`](super::Command::target)
+/// is only dropped if that later command is applied in the same frame.
+///
+/// The entities reserved by [`Commands::spawn`](super::Commands::spawn) are spawned right away,
+/// without components: only their components wait in the backlog.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::CommandBudget};
+/// # use bevy_utils::Duration;
+/// let mut world = World::new();
+/// world.insert_resource(
+///     CommandBudget::default()
+///         .with_max_commands(1000)
+///         .with_max_duration(Duration::from_millis(4)),
+/// );
+/// ```
+#[derive(Default)]
+pub struct CommandBudget {
+    max_commands: Option<usize>,
+    max_duration: Option<Duration>,
+    backlog: CommandQueue,
+    applied: usize,
+    frame_start: Option<Instant>,
+}
+
+/// The number of commands applied between two checks of [`CommandBudget::max_duration`].
+const DURATION_BATCH: usize = 64;
+
+impl CommandBudget {
+    /// Limits the number of commands applied per frame to `max_commands`.
+    pub fn with_max_commands(mut self, max_commands: usize) -> Self {
+        self.max_commands = Some(max_commands);
+        self
+    }
+
+    /// Stops applying commands once `max_duration` passed since the start of the frame.
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Returns the maximum number of commands applied per frame, if any.
+    pub fn max_commands(&self) -> Option<usize> {
+        self.max_commands
+    }
+
+    /// Returns the duration after which no more commands are applied in a frame, if any.
+    pub fn max_duration(&self) -> Option<Duration> {
+        self.max_duration
+    }
+
+    /// Returns the number of commands applied since the start of the frame.
+    pub fn applied(&self) -> usize {
+        self.applied
+    }
+
+    /// Returns the number of commands waiting for a later frame.
+    pub fn backlog_len(&self) -> usize {
+        self.backlog.len()
+    }
+
+    /// A system that starts a new frame for the [`CommandBudget`] of the world, if it has one,
+    /// and applies the backlog of commands within the new budget.
+    ///
+    /// This should run once per frame, at the start of the frame.
+    pub fn apply_backlog_system(world: &mut World) {
+        if !world.contains_resource::<CommandBudget>() {
+            return;
+        }
+        world.resource_scope(|world, mut budget: Mut<CommandBudget>| {
+            let budget = budget.bypass_change_detection();
+            budget.applied = 0;
+            budget.frame_start = Some(Instant::now());
+            if !budget.backlog.is_empty() {
+                budget.apply_backlog(world);
+            }
+        });
+    }
+
+    /// Adds the commands of `queue` to the backlog of the [`CommandBudget`] of `world`, and
+    /// applies the backlog within the budget left in the frame.
+    pub(crate) fn apply_queue(world: &mut World, queue: &mut CommandQueue) {
+        world.resource_scope(|world, mut budget: Mut<CommandBudget>| {
+            let budget = budget.bypass_change_detection();
+            budget.backlog.append(queue);
+            budget.apply_backlog(world);
+        });
+    }
+
+    fn apply_backlog(&mut self, world: &mut World) {
+        let frame_start = *self.frame_start.get_or_insert_with(Instant::now);
+        let (applied, max_commands, max_duration) =
+            (self.applied, self.max_commands, self.max_duration);
+        self.applied += self.backlog.apply_while(world, |index| {
+            let applied = applied + index;
+            let spent = max_commands.is_some_and(|max| applied >= max)
+                || max_duration.is_some_and(|max| frame_start.elapsed() >= max);
+            if applied > 0 && spent {
+                return 0;
+            }
+            let left = max_commands.map_or(usize::MAX, |max| max.saturating_sub(applied).max(1));
+            if max_duration.is_some() {
+                left.min(DURATION_BATCH)
+            } else {
+                left
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandBudget;
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        schedule::{
+            ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion, Schedule, Stage,
+            SystemStage,
+        },
+        system::{Commands, IntoExclusiveSystem, Local, ParallelCommands},
+        world::World,
+    };
+
+    #[derive(Component)]
+    struct Chunk(usize);
+
+    fn stream(mut commands: Commands, mut streamed: Local<bool>) {
+        if !std::mem::replace(&mut *streamed, true) {
+            for i in 0..10_000 {
+                commands.spawn().insert(Chunk(i));
+            }
+        }
+    }
+
+    fn despawn_first(mut commands: Commands, mut despawned: Local<bool>, world: &World) {
+        if !std::mem::replace(&mut *despawned, true) {
+            let first = world.entities().resolve_from_id(0).unwrap();
+            commands.entity(first).despawn();
+        }
+    }
+
+    #[test]
+    fn spread_commands_over_frames() {
+        let mut world = World::new();
+        world.insert_resource(CommandBudget::default().with_max_commands(1000));
+        let mut schedule = Schedule::default();
+        schedule.add_stage(
+            "first",
+            SystemStage::single_threaded()
+                .with_system(CommandBudget::apply_backlog_system.exclusive_system()),
+        );
+        schedule.add_stage("update", SystemStage::single_threaded().with_system(stream));
+        schedule.add_stage(
+            "last",
+            SystemStage::single_threaded().with_system(despawn_first),
+        );
+
+        let mut query = world.query::<&Chunk>();
+        let mut counts = Vec::new();
+        for _ in 0..11 {
+            schedule.run(&mut world);
+            counts.push(query.iter(&world).count());
+        }
+
+        // The despawn queued in the first frame waits for the spawns queued before it.
+        let mut expected = (1..=10).map(|frame| frame * 1000).collect::<Vec<_>>();
+        expected.push(9999);
+        assert_eq!(counts, expected);
+        assert_eq!(world.resource::<CommandBudget>().backlog_len(), 0);
+        assert!(query.iter(&world).all(|chunk| chunk.0 != 0));
+    }
+
+    #[test]
+    fn parallel_commands_wait_for_backlog() {
+        fn push(tag: usize) -> impl FnOnce(&mut World) {
+            move |world| world.resource_mut::<Vec<usize>>().push(tag)
+        }
+        fn queue(mut commands: Commands, mut queued: Local<bool>) {
+            if !std::mem::replace(&mut *queued, true) {
+                for tag in 0..3 {
+                    commands.add(push(tag));
+                }
+            }
+        }
+        fn queue_parallel(commands: ParallelCommands, mut queued: Local<bool>) {
+            if !std::mem::replace(&mut *queued, true) {
+                commands.command_scope(|mut commands| commands.add(push(3)));
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Vec<usize>>();
+        world.insert_resource(CommandBudget::default().with_max_commands(1));
+        let mut stage = SystemStage::single_threaded()
+            .with_system(
+                CommandBudget::apply_backlog_system
+                    .exclusive_system()
+                    .at_start(),
+            )
+            .with_system(queue)
+            .with_system(queue_parallel.after(queue));
+        stage.run(&mut world);
+        assert_eq!(*world.resource::<Vec<usize>>(), vec![0]);
+        for _ in 0..3 {
+            stage.run(&mut world);
+        }
+        assert_eq!(*world.resource::<Vec<usize>>(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn supersede_within_applied_commands() {
+        fn insert_twice(mut commands: Commands, mut queued: Local<bool>, world: &World) {
+            if !std::mem::replace(&mut *queued, true) {
+                let entity = world.entities().resolve_from_id(0).unwrap();
+                commands.entity(entity).insert(Chunk(1)).insert(Chunk(2));
+            }
+        }
+
+        let mut world = World::new();
+        let entity = world.spawn().id();
+        world.insert_resource(CommandBudget::default().with_max_commands(1));
+        let mut stage = SystemStage::single_threaded()
+            .with_system(
+                CommandBudget::apply_backlog_system
+                    .exclusive_system()
+                    .at_start(),
+            )
+            .with_system(insert_twice);
+
+        // The first insert isn't dropped while the second one waits for the next frame.
+        stage.run(&mut world);
+        assert_eq!(world.get::<Chunk>(entity).map(|chunk| chunk.0), Some(1));
+        stage.run(&mut world);
+        assert_eq!(world.get::<Chunk>(entity).map(|chunk| chunk.0), Some(2));
+    }
+}
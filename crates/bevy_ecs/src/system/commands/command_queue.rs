@@ -1,11 +1,19 @@
-use std::mem::{ManuallyDrop, MaybeUninit};
+use std::{
+    any::TypeId,
+    mem::{ManuallyDrop, MaybeUninit},
+};
 
 use super::Command;
-use crate::world::World;
+use crate::{entity::Entity, world::World};
+use bevy_utils::HashSet;
 
 struct CommandMeta {
     offset: usize,
     func: unsafe fn(value: *mut MaybeUninit<u8>, world: &mut World),
+    discard: unsafe fn(value: *mut MaybeUninit<u8>),
+    target: Option<(Entity, TypeId)>,
+    /// Whether the command can be held back by [`World::coalesce_inserts`].
+    coalesced: bool,
 }
 
 /// A queue of [`Command`]s
@@ -42,12 +50,20 @@ impl CommandQueue {
             command.write(world);
         }
 
+        /// SAFETY: Same as `write_command`, but the command is dropped instead of written.
+        unsafe fn discard_command<T: Command>(command: *mut MaybeUninit<u8>) {
+            drop(command.cast::<T>().read_unaligned());
+        }
+
         let size = std::mem::size_of::<C>();
         let old_len = self.bytes.len();
 
         self.metas.push(CommandMeta {
             offset: old_len,
             func: write_command::<C>,
+            discard: discard_command::<C>,
+            target: command.target(),
+            coalesced: command.is_coalesced(),
         });
 
         // Use `ManuallyDrop` to forget `command` right away, avoiding
@@ -76,27 +92,161 @@ impl CommandQueue {
         }
     }
 
+    /// Returns the number of [`Command`]s in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.metas.len()
+    }
+
+    /// Returns `true` if the queue contains no [`Command`]s.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.metas.is_empty()
+    }
+
     /// Execute the queued [`Command`]s in the world.
     /// This clears the queue.
     #[inline]
     pub fn apply(&mut self, world: &mut World) {
-        // flush the previously queued entities
-        world.flush();
+        #[cfg(feature = "trace")]
+        let commands = self.metas.len();
+        #[cfg(feature = "trace")]
+        let _span = bevy_utils::tracing::info_span!("apply_commands", commands).entered();
+
+        let _spawned = flush_reserved(world);
+        self.apply_flushed(world, |_| usize::MAX);
+
+        #[cfg(feature = "trace")]
+        bevy_utils::tracing::debug!(
+            commands,
+            spawned = _spawned,
+            entities = world.entities().len(),
+            "applied commands"
+        );
+    }
+
+    /// Moves all the [`Command`]s of `other` to the end of this queue, leaving `other` empty.
+    pub fn append(&mut self, other: &mut CommandQueue) {
+        let offset = self.bytes.len();
+        self.bytes.extend_from_slice(&other.bytes);
+        self.metas
+            .extend(other.metas.drain(..).map(|meta| CommandMeta {
+                offset: meta.offset + offset,
+                ..meta
+            }));
+        // The commands were moved to `self`, so `other` only forgets them.
+        other.bytes.clear();
+    }
+
+    /// Executes the queued [`Command`]s in order, in batches of the number of commands `batch`
+    /// returns given the number of commands this call went through so far, until it returns `0`,
+    /// and keeps the remaining commands queued.
+    ///
+    /// A command is only superseded by a later command of the same batch, so that it is never
+    /// dropped while the command replacing it waits in the queue.
+    ///
+    /// Returns the number of commands that were gone through.
+    pub(crate) fn apply_while(
+        &mut self,
+        world: &mut World,
+        batch: impl FnMut(usize) -> usize,
+    ) -> usize {
+        flush_reserved(world);
+        self.apply_flushed(world, batch)
+    }
 
-        // SAFETY: In the iteration below, `meta.func` will safely consume and drop each pushed command.
-        // This operation is so that we can reuse the bytes `Vec<u8>`'s internal storage and prevent
-        // unnecessary allocations.
+    /// Like [`CommandQueue::apply_while`], once the reserved entities were flushed.
+    fn apply_flushed(&mut self, world: &mut World, mut batch: impl FnMut(usize) -> usize) -> usize {
+        let bytes_len = self.bytes.len();
+        // SAFETY: In the iteration below, `meta.func` or `meta.discard` will safely consume and drop
+        // each command that is gone through, and the remaining ones are moved back to the start
+        // of the storage afterwards. This operation is so that we can reuse the bytes `Vec<u8>`'s
+        // internal storage and prevent unnecessary allocations.
         unsafe { self.bytes.set_len(0) };
+        let mut metas = std::mem::take(&mut self.metas);
+
+        let mut index = 0;
+        while index < metas.len() {
+            let end = index.saturating_add(batch(index)).min(metas.len());
+            if end == index {
+                break;
+            }
+            let superseded = superseded(&metas[index..end]);
+            for (meta, superseded) in metas[index..end]
+                .iter()
+                .zip(superseded.into_iter().chain(std::iter::repeat(false)))
+            {
+                // SAFETY: The implementations of `write_command` and `discard_command` are safe for
+                // the according Command type. It's ok to read from `bytes.as_mut_ptr()` because we
+                // just wrote to it in `push`. The bytes are safely cast to their original type,
+                // safely read, and then dropped.
+                unsafe {
+                    let command = self.bytes.as_mut_ptr().add(meta.offset);
+                    if superseded {
+                        (meta.discard)(command);
+                    } else {
+                        if !meta.coalesced {
+                            world.flush_coalesced_inserts();
+                        }
+                        (meta.func)(command, world);
+                    }
+                }
+                index += 1;
+            }
+        }
 
-        for meta in self.metas.drain(..) {
-            // SAFETY: The implementation of `write_command` is safe for the according Command type.
-            // It's ok to read from `bytes.as_mut_ptr()` because we just wrote to it in `push`.
-            // The bytes are safely cast to their original type, safely read, and then dropped.
+        if let Some(first) = metas.get(index) {
+            let start = first.offset;
+            // SAFETY: the bytes of the remaining commands are still stored in `start..bytes_len`,
+            // within the capacity of `bytes`, and are moved to the start of the storage.
             unsafe {
-                (meta.func)(self.bytes.as_mut_ptr().add(meta.offset), world);
+                let ptr = self.bytes.as_mut_ptr();
+                std::ptr::copy(ptr.add(start), ptr, bytes_len - start);
+                self.bytes.set_len(bytes_len - start);
             }
+            self.metas = metas
+                .drain(index..)
+                .map(|meta| CommandMeta {
+                    offset: meta.offset - start,
+                    ..meta
+                })
+                .collect();
+        } else {
+            metas.clear();
+            self.metas = metas;
+        }
+        index
+    }
+}
+
+/// Flushes the entities reserved by [`Commands`](super::Commands), and returns how many were
+/// spawned.
+fn flush_reserved(world: &mut World) -> u32 {
+    let len = world.entities().len();
+    world.flush();
+    let spawned = world.entities().len() - len;
+    #[cfg(feature = "command_stats")]
+    super::stats::record(world, |stats| stats.spawned += spawned as usize);
+    spawned
+}
+
+/// Returns whether each command is superseded by a later command with the same
+/// [`Command::target`], with no command without a target between them.
+///
+/// Returns an empty `Vec` if no command is superseded.
+fn superseded(metas: &[CommandMeta]) -> Vec<bool> {
+    if metas.iter().all(|meta| meta.target.is_none()) {
+        return Vec::new();
+    }
+    let mut superseded = vec![false; metas.len()];
+    let mut targets = HashSet::default();
+    for (index, meta) in metas.iter().enumerate().rev() {
+        match meta.target {
+            Some(target) => superseded[index] = !targets.insert(target),
+            None => targets.clear(),
         }
     }
+    superseded
 }
 
 #[cfg(test)]
@@ -1,16 +1,31 @@
+/// Emits a `tracing` event about an applied [`Command`] when the `trace` feature is enabled, and
+/// compiles to nothing otherwise.
+macro_rules! trace_command {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "trace")]
+        bevy_utils::tracing::trace!($($arg)*);
+    };
+}
+
+mod budget;
 mod command_queue;
 mod parallel_scope;
+mod stats;
 
 use crate::{
     bundle::Bundle,
     component::Component,
     entity::{Entities, Entity},
-    world::{FromWorld, World},
+    query::{ROQueryItem, ReadOnlyWorldQuery, With},
+    schedule::{BoxedStageLabel, StageLabel},
+    world::{EntityIndices, FromWorld, Mut, PrefabRegistry, World},
 };
 use bevy_utils::tracing::{error, info, warn};
+pub use budget::CommandBudget;
 pub use command_queue::CommandQueue;
 pub use parallel_scope::*;
-use std::marker::PhantomData;
+pub use stats::CommandStats;
+use std::{any::TypeId, borrow::Cow, marker::PhantomData};
 
 use super::Resource;
 
@@ -43,6 +58,27 @@ use super::Resource;
 /// ```
 pub trait Command: Send + Sync + 'static {
     fn write(self, world: &mut World);
+
+    /// Returns the entity and the type of the component this command inserts on it or removes
+    /// from it, if that is all this command does.
+    ///
+    /// When a [`CommandQueue`] is applied, a command with a target is skipped if a later command
+    /// has the same target, unless a command without a target was queued between them. This
+    /// collapses the inserts and removals of a component queued in a row into the last one, and
+    /// saves the archetype moves in between: a component that is removed then inserted again is
+    /// only replaced, so it doesn't show up in [`RemovedComponents`](crate::system::RemovedComponents).
+    /// Skipped commands are dropped without being written.
+    fn target(&self) -> Option<(Entity, TypeId)> {
+        None
+    }
+
+    /// Returns `true` if this command may be applied while the components inserted by the
+    /// commands before it are held back by [`World::coalesce_inserts`], as it only inserts a
+    /// component itself.
+    #[doc(hidden)]
+    fn is_coalesced(&self) -> bool {
+        false
+    }
 }
 
 /// A queue of [commands](Command) that get executed at the end of the stage of the system that called them.
@@ -277,6 +313,39 @@ impl<'w, 's> Commands<'w, 's> {
         self.queue.push(SpawnBatch { bundles_iter });
     }
 
+    /// Spawns an entity with the bundle returned by `bundle`, unless an entity with a `C` component
+    /// already exists.
+    ///
+    /// The check happens when the command is applied, so running the system again before the
+    /// spawn is applied doesn't spawn a second entity. The bundle should contain `C`, or a new
+    /// entity will be spawned each time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Player;
+    /// # #[derive(Component)]
+    /// # struct Health(u32);
+    /// #
+    /// fn ensure_player(mut commands: Commands) {
+    ///     commands.spawn_singleton::<Player, _>(|| (Player, Health(100)));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(ensure_player);
+    /// ```
+    pub fn spawn_singleton<C, B>(&mut self, bundle: impl FnOnce() -> B + Send + Sync + 'static)
+    where
+        C: Component,
+        B: Bundle,
+    {
+        self.queue.push(SpawnSingleton {
+            bundle,
+            phantom: PhantomData::<C>,
+        });
+    }
+
     /// For a given batch of ([Entity], [Bundle]) pairs, either spawns each [Entity] with the given
     /// bundle (if the entity does not exist), or inserts the [Bundle] (if the entity already exists).
     ///
@@ -296,6 +365,119 @@ impl<'w, 's> Commands<'w, 's> {
         self.queue.push(InsertOrSpawnBatch { bundles_iter });
     }
 
+    /// Inserts the bundle returned by `bundle` onto every entity matching the query `Q`, unless it
+    /// returns [`None`] for that entity.
+    ///
+    /// The query is iterated when the command is applied, so it sees the effects of the commands
+    /// applied before it, and the bundles are then inserted in one batch. This is faster than
+    /// inserting onto each entity with its own command.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Health(u32);
+    /// # #[derive(Component)]
+    /// # struct Dead;
+    /// #
+    /// fn mark_dead(mut commands: Commands) {
+    ///     commands.insert_for_each::<&Health, _>(|health| (health.0 == 0).then_some((Dead,)));
+    /// }
+    /// # bevy_ecs::system::assert_is_system(mark_dead);
+    /// ```
+    pub fn insert_for_each<Q, B>(
+        &mut self,
+        bundle: impl for<'a> FnMut(ROQueryItem<'a, Q>) -> Option<B> + Send + Sync + 'static,
+    ) where
+        Q: ReadOnlyWorldQuery + 'static,
+        B: Bundle,
+    {
+        self.queue.push(InsertForEach {
+            bundle,
+            phantom: PhantomData::<fn() -> Q>,
+        });
+    }
+
+    /// Spawns a new entity with the bundle of the prefab `name`, registered in the
+    /// [`PrefabRegistry`] resource, and returns the corresponding [`EntityCommands`].
+    ///
+    /// The prefab is looked up when the command is applied, which panics if the
+    /// [`PrefabRegistry`] doesn't exist or the prefab isn't registered in it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Position(f32);
+    /// #
+    /// fn spawn_wave(mut commands: Commands) {
+    ///     for x in 0..3 {
+    ///         commands.spawn_prefab("goblin").insert(Position(x as f32));
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(spawn_wave);
+    /// ```
+    pub fn spawn_prefab<'a>(
+        &'a mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let entity = self.entities.reserve_entity();
+        self.add(SpawnPrefab {
+            entity,
+            name: name.into(),
+        });
+        EntityCommands {
+            entity,
+            commands: self,
+        }
+    }
+
+    /// Exchanges the `T` components of the entities `a` and `b`.
+    ///
+    /// The values are swapped in place when the command is applied, without moving the entities
+    /// to other archetypes, and both components are marked as changed. If either entity doesn't
+    /// exist or doesn't have a `T`, a warning is logged and nothing is swapped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Slot(u32);
+    /// #
+    /// # struct Dragged(Entity, Entity);
+    /// #
+    /// fn drop_item(mut commands: Commands, dragged: Res<Dragged>) {
+    ///     commands.swap_components::<Slot>(dragged.0, dragged.1);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(drop_item);
+    /// ```
+    pub fn swap_components<T: Component>(&mut self, a: Entity, b: Entity) {
+        self.queue.push(SwapComponents {
+            a,
+            b,
+            phantom: PhantomData::<T>,
+        });
+    }
+
+    /// Exchanges the components of the bundle `B` between the entities `a` and `b`.
+    ///
+    /// Unlike [`swap_components`](Self::swap_components), the bundles are removed from both
+    /// entities and inserted again, so they are marked as added. If either entity doesn't exist
+    /// or doesn't have every component of `B`, a warning is logged and nothing is swapped.
+    pub fn swap_bundle<B: Bundle>(&mut self, a: Entity, b: Entity) {
+        self.queue.push(SwapBundle {
+            a,
+            b,
+            phantom: PhantomData::<B>,
+        });
+    }
+
     /// Inserts a resource with standard starting values to the [`World`].
     ///
     /// If the resource already exists, nothing happens.
@@ -383,6 +565,26 @@ impl<'w, 's> Commands<'w, 's> {
         });
     }
 
+    /// Runs a sub-schedule of a running [`Schedule`](crate::schedule::Schedule).
+    ///
+    /// See [`World::run_schedule`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # fn system(mut commands: Commands) {
+    /// commands.run_schedule("extract");
+    /// # }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn run_schedule(&mut self, label: impl StageLabel) {
+        self.queue.push(RunSchedule {
+            label: Box::new(label),
+        });
+    }
+
     /// Adds a command directly to the command queue.
     ///
     /// `command` can be a built-in command, custom struct that implements [`Command`] or a closure
@@ -420,6 +622,75 @@ impl<'w, 's> Commands<'w, 's> {
     pub fn add<C: Command>(&mut self, command: C) {
         self.queue.push(command);
     }
+
+    /// Queues `func` to run with exclusive access to the [`World`] when the commands are applied.
+    ///
+    /// This lets a system that is otherwise fine to run in parallel do some work that needs the
+    /// whole [`World`], without making it an exclusive system.
+    ///
+    /// Commands are applied in the order they were queued, so `func` sees the effects of every
+    /// command queued before it, including structural changes such as spawned entities and
+    /// inserted components, and the commands of systems applied earlier in the stage. Commands
+    /// queued after it are applied once it returns.
+    ///
+    /// This is the same as passing a closure to [`Commands::add`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// struct EnemyCount(usize);
+    ///
+    /// fn spawn_enemy(mut commands: Commands) {
+    ///     commands.spawn().insert(Enemy);
+    ///     commands.run_exclusive(|world| {
+    ///         let count = world.query::<&Enemy>().iter(world).count();
+    ///         world.insert_resource(EnemyCount(count));
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(spawn_enemy);
+    /// ```
+    pub fn run_exclusive(&mut self, func: impl FnOnce(&mut World) + Send + Sync + 'static) {
+        self.queue.push(func);
+    }
+
+    /// Returns the number of commands queued by this system that have not been applied yet.
+    ///
+    /// A system's commands are applied at the end of its stage, so this is usually the number of
+    /// commands queued during the current run. It can be used to throttle systems that queue a
+    /// lot of work, to avoid spikes when the commands are applied.
+    ///
+    /// Entities reserved with [`Commands::spawn`] don't count until a command, such as an
+    /// insertion, is queued for them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Enemy;
+    /// const MAX_QUEUED: usize = 100;
+    ///
+    /// fn spawn_enemies(mut commands: Commands) {
+    ///     while commands.len() < MAX_QUEUED {
+    ///         commands.spawn().insert(Enemy);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(spawn_enemies);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if this system has no queued commands that have not been applied yet.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
 }
 
 /// A list of commands that will be run to modify an [entity](crate::entity).
@@ -488,6 +759,10 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
 
     /// Adds a single [`Component`] to the entity.
     ///
+    /// When a stage applies the commands of its systems, the components that consecutive commands
+    /// insert on an entity, even from different systems, are written to it together, with a single
+    /// archetype move. Any other command sees the components inserted before it.
+    ///
     /// # Example
     ///
     /// `Self::insert` can be chained with [`Commands::spawn`].
@@ -520,6 +795,123 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         self
     }
 
+    /// Adds a [`Component`] to the entity, unless it already has one.
+    ///
+    /// Whether the entity has the component is checked when the command is applied, so an
+    /// existing value is left untouched, even if it was inserted by a command applied earlier. If
+    /// several systems call this for the same component, the first command applied wins.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// fn init_health(mut commands: Commands, query: Query<Entity, With<Player>>) {
+    ///     for entity in query.iter() {
+    ///         commands.entity(entity).insert_if_new(Health(100));
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(init_health);
+    /// ```
+    pub fn insert_if_new(&mut self, component: impl Component) -> &mut Self {
+        self.commands.add(InsertIfNew {
+            entity: self.entity,
+            component,
+        });
+        self
+    }
+
+    /// Adds a [`Bundle`] of components to the entity, unless it already has any of them.
+    ///
+    /// The bundle is inserted as a whole or not at all: if the entity has some of its components,
+    /// none are inserted, so that the existing values are left untouched. See
+    /// [`insert_if_new`](Self::insert_if_new).
+    pub fn insert_bundle_if_new(&mut self, bundle: impl Bundle) -> &mut Self {
+        self.commands.add(InsertBundleIfNew {
+            entity: self.entity,
+            bundle,
+        });
+        self
+    }
+
+    /// Adds a [`Component`] to the entity, created with its [`FromWorld`] implementation when the
+    /// command is applied.
+    ///
+    /// This is useful for components that are derived from resources, such as a handle loaded
+    /// from an asset server resource.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, world::FromWorld};
+    /// #
+    /// struct DefaultColor(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct Color(u32);
+    ///
+    /// impl FromWorld for Color {
+    ///     fn from_world(world: &mut World) -> Self {
+    ///         Color(world.resource::<DefaultColor>().0)
+    ///     }
+    /// }
+    ///
+    /// fn spawn_colored(mut commands: Commands) {
+    ///     commands.spawn().insert_from_world::<Color>();
+    /// }
+    /// # bevy_ecs::system::assert_is_system(spawn_colored);
+    /// ```
+    pub fn insert_from_world<T: Component + FromWorld>(&mut self) -> &mut Self {
+        self.commands.add(InsertFromWorld::<T> {
+            entity: self.entity,
+            _phantom: PhantomData,
+        });
+        self
+    }
+
+    /// Adds the [`Component`] returned by `func` to the entity. `func` is called with the
+    /// [`World`] when the command is applied.
+    ///
+    /// If `func` returns `None`, for example because a resource it needs is missing, a warning is
+    /// logged and nothing is inserted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// struct DefaultColor(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct Color(u32);
+    ///
+    /// fn spawn_colored(mut commands: Commands) {
+    ///     commands.spawn().insert_with(|world: &World| {
+    ///         world
+    ///             .get_resource::<DefaultColor>()
+    ///             .map(|color| Color(color.0))
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(spawn_colored);
+    /// ```
+    pub fn insert_with<T, F>(&mut self, func: F) -> &mut Self
+    where
+        T: Component,
+        F: FnOnce(&World) -> Option<T> + Send + Sync + 'static,
+    {
+        self.commands.add(InsertWith {
+            entity: self.entity,
+            func,
+        });
+        self
+    }
+
     /// Removes a [`Bundle`] of components from the entity.
     ///
     /// See [`EntityMut::remove_bundle`](crate::world::EntityMut::remove_bundle) for more
@@ -582,9 +974,23 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         self
     }
 
+    /// Assigns the entity an [`EntityIndex`](crate::world::EntityIndex) from the
+    /// [`EntityIndices`] resource, which is inserted if it doesn't exist, and inserts it.
+    ///
+    /// The index is kept if the entity already has one.
+    pub fn insert_entity_index(&mut self) -> &mut Self {
+        self.commands.add(InsertEntityIndex {
+            entity: self.entity,
+        });
+        self
+    }
+
     /// Despawns the entity.
     ///
-    /// See [`World::despawn`] for more details.
+    /// See [`World::despawn`] for more details. If the entity no longer exists when the command
+    /// is applied, for example because another system despawned it during the same frame, a
+    /// warning is logged and nothing else happens. Use
+    /// [`despawn_if_exists`](Self::despawn_if_exists) when that is expected.
     ///
     /// # Example
     ///
@@ -608,6 +1014,34 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         });
     }
 
+    /// Despawns the entity if it still exists when the command is applied.
+    ///
+    /// Unlike [`despawn`](Self::despawn), this doesn't log a warning when the entity is already
+    /// gone, so several systems can despawn the same entity in a frame without any noise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Health(u32);
+    /// #
+    /// fn despawn_dead_system(mut commands: Commands, query: Query<(Entity, &Health)>) {
+    ///     for (entity, health) in query.iter() {
+    ///         if health.0 == 0 {
+    ///             commands.entity(entity).despawn_if_exists();
+    ///         }
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(despawn_dead_system);
+    /// ```
+    pub fn despawn_if_exists(&mut self) {
+        self.commands.add(DespawnIfExists {
+            entity: self.entity,
+        });
+    }
+
     /// Logs the components of the entity at the info level.
     pub fn log_components(&mut self) {
         self.commands.add(LogComponents {
@@ -640,7 +1074,9 @@ where
     T: Bundle,
 {
     fn write(self, world: &mut World) {
-        world.spawn().insert_bundle(self.bundle);
+        let _entity = world.spawn().insert_bundle(self.bundle).id();
+        stats::record(world, |stats| stats.spawned += 1);
+        trace_command!(entity = ?_entity, bundle = std::any::type_name::<T>(), "spawned entity");
     }
 }
 
@@ -650,42 +1086,196 @@ pub struct GetOrSpawn {
 
 impl Command for GetOrSpawn {
     fn write(self, world: &mut World) {
+        let spawned = !world.is_alive(self.entity);
         world.get_or_spawn(self.entity);
+        if spawned {
+            stats::record(world, |stats| stats.spawned += 1);
+        }
     }
 }
 
-pub struct SpawnBatch<I>
-where
-    I: IntoIterator,
-    I::Item: Bundle,
-{
-    pub bundles_iter: I,
+pub struct SpawnSingleton<C, F> {
+    pub bundle: F,
+    pub phantom: PhantomData<C>,
 }
 
-impl<I> Command for SpawnBatch<I>
+impl<C, B, F> Command for SpawnSingleton<C, F>
 where
-    I: IntoIterator + Send + Sync + 'static,
-    I::Item: Bundle,
+    C: Component,
+    B: Bundle,
+    F: FnOnce() -> B + Send + Sync + 'static,
 {
     fn write(self, world: &mut World) {
-        world.spawn_batch(self.bundles_iter);
+        let mut query = world.query_filtered::<(), With<C>>();
+        // A disabled singleton still exists.
+        query.include_disabled(world);
+        if query.iter(world).next().is_none() {
+            world.spawn().insert_bundle((self.bundle)());
+            stats::record(world, |stats| stats.spawned += 1);
+        }
     }
 }
 
-pub struct InsertOrSpawnBatch<I, B>
-where
-    I: IntoIterator + Send + Sync + 'static,
-    B: Bundle,
-    I::IntoIter: Iterator<Item = (Entity, B)>,
-{
-    pub bundles_iter: I,
+pub struct InsertForEach<Q, F> {
+    pub bundle: F,
+    pub phantom: PhantomData<fn() -> Q>,
 }
 
-impl<I, B> Command for InsertOrSpawnBatch<I, B>
+impl<Q, B, F> Command for InsertForEach<Q, F>
 where
-    I: IntoIterator + Send + Sync + 'static,
+    Q: ReadOnlyWorldQuery + 'static,
     B: Bundle,
-    I::IntoIter: Iterator<Item = (Entity, B)>,
+    F: for<'a> FnMut(ROQueryItem<'a, Q>) -> Option<B> + Send + Sync + 'static,
+{
+    fn write(mut self, world: &mut World) {
+        let bundles = world
+            .query::<(Entity, Q)>()
+            .iter(world)
+            .filter_map(|(entity, item)| Some((entity, (self.bundle)(item)?)))
+            .collect::<Vec<_>>();
+        let inserted = bundles.len();
+        // The entities were just matched, so they all exist.
+        let _ = world.insert_or_spawn_batch(bundles);
+        stats::record(world, |stats| stats.inserted += inserted);
+    }
+}
+
+pub struct SpawnPrefab {
+    pub entity: Entity,
+    pub name: Cow<'static, str>,
+}
+
+impl Command for SpawnPrefab {
+    fn write(self, world: &mut World) {
+        world.resource_scope(|world, prefabs: Mut<PrefabRegistry>| {
+            if let Some(mut entity) = world.get_or_spawn(self.entity) {
+                if !prefabs.insert_into(&self.name, &mut entity) {
+                    panic!(
+                        "Could not spawn the prefab {:?} because it isn't registered in the `PrefabRegistry`.",
+                        self.name
+                    );
+                }
+                trace_command!(entity = ?self.entity, prefab = %self.name, "spawned prefab");
+            }
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct SwapComponents<T> {
+    pub a: Entity,
+    pub b: Entity,
+    pub phantom: PhantomData<T>,
+}
+
+impl<T> Command for SwapComponents<T>
+where
+    T: Component,
+{
+    fn write(self, world: &mut World) {
+        if self.a == self.b {
+            return;
+        }
+        match world.query::<&mut T>().get_many_mut(world, [self.a, self.b]) {
+            Ok([mut a, mut b]) => {
+                std::mem::swap(&mut *a, &mut *b);
+                trace_command!(a = ?self.a, b = ?self.b, component = std::any::type_name::<T>(), "swapped components");
+            }
+            Err(_) => warn!(
+                "Could not swap the components (of type `{}`) of entities {:?} and {:?} because they don't both have one.",
+                std::any::type_name::<T>(),
+                self.a,
+                self.b
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SwapBundle<T> {
+    pub a: Entity,
+    pub b: Entity,
+    pub phantom: PhantomData<T>,
+}
+
+impl<T> Command for SwapBundle<T>
+where
+    T: Bundle,
+{
+    fn write(self, world: &mut World) {
+        if self.a == self.b {
+            return;
+        }
+        let component_ids = T::component_ids(&mut world.components, &mut world.storages);
+        let has_bundle = |entity| {
+            world.get_entity(entity).is_some_and(|entity| {
+                component_ids
+                    .iter()
+                    .all(|component_id| entity.contains_id(*component_id))
+            })
+        };
+        if !has_bundle(self.a) || !has_bundle(self.b) {
+            warn!(
+                "Could not swap the bundles (of type `{}`) of entities {:?} and {:?} because they don't both have one.",
+                std::any::type_name::<T>(),
+                self.a,
+                self.b
+            );
+            return;
+        }
+        let a = world.entity_mut(self.a).remove_bundle::<T>();
+        let b = world.entity_mut(self.b).remove_bundle::<T>();
+        // Both entities have every component of the bundle, so both removals succeed.
+        if let (Some(a), Some(b)) = (a, b) {
+            world.entity_mut(self.a).insert_bundle(b);
+            world.entity_mut(self.b).insert_bundle(a);
+            stats::record(world, |stats| {
+                stats.removed += 2;
+                stats.inserted += 2;
+            });
+            trace_command!(a = ?self.a, b = ?self.b, bundle = std::any::type_name::<T>(), "swapped bundles");
+        }
+    }
+}
+
+pub struct SpawnBatch<I>
+where
+    I: IntoIterator,
+    I::Item: Bundle,
+{
+    pub bundles_iter: I,
+}
+
+impl<I> Command for SpawnBatch<I>
+where
+    I: IntoIterator + Send + Sync + 'static,
+    I::Item: Bundle,
+{
+    fn write(self, world: &mut World) {
+        let spawned = world.spawn_batch(self.bundles_iter).count();
+        stats::record(world, |stats| stats.spawned += spawned);
+        trace_command!(
+            spawned,
+            bundle = std::any::type_name::<I::Item>(),
+            "spawned entity batch"
+        );
+    }
+}
+
+pub struct InsertOrSpawnBatch<I, B>
+where
+    I: IntoIterator + Send + Sync + 'static,
+    B: Bundle,
+    I::IntoIter: Iterator<Item = (Entity, B)>,
+{
+    pub bundles_iter: I,
+}
+
+impl<I, B> Command for InsertOrSpawnBatch<I, B>
+where
+    I: IntoIterator + Send + Sync + 'static,
+    B: Bundle,
+    I::IntoIter: Iterator<Item = (Entity, B)>,
 {
     fn write(self, world: &mut World) {
         if let Err(invalid_entities) = world.insert_or_spawn_batch(self.bundles_iter) {
@@ -705,12 +1295,29 @@ pub struct Despawn {
 
 impl Command for Despawn {
     fn write(self, world: &mut World) {
-        if !world.despawn(self.entity) {
+        if world.despawn(self.entity) {
+            stats::record(world, |stats| stats.despawned += 1);
+            trace_command!(entity = ?self.entity, "despawned entity");
+        } else {
             warn!("error[B0003]: Could not despawn entity {:?} because it doesn't exist in this World.", self.entity);
         }
     }
 }
 
+#[derive(Debug)]
+pub struct DespawnIfExists {
+    pub entity: Entity,
+}
+
+impl Command for DespawnIfExists {
+    fn write(self, world: &mut World) {
+        if world.despawn(self.entity) {
+            stats::record(world, |stats| stats.despawned += 1);
+            trace_command!(entity = ?self.entity, "despawned entity");
+        }
+    }
+}
+
 pub struct InsertBundle<T> {
     pub entity: Entity,
     pub bundle: T,
@@ -723,6 +1330,7 @@ where
     fn write(self, world: &mut World) {
         if let Some(mut entity) = world.get_entity_mut(self.entity) {
             entity.insert_bundle(self.bundle);
+            stats::record(world, |stats| stats.inserted += 1);
         } else {
             panic!("error[B0003]: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World.", std::any::type_name::<T>(), self.entity);
         }
@@ -730,21 +1338,145 @@ where
 }
 
 #[derive(Debug)]
-pub struct Insert<T> {
+pub struct InsertIfNew<T> {
     pub entity: Entity,
     pub component: T,
 }
 
-impl<T> Command for Insert<T>
+impl<T> Command for InsertIfNew<T>
 where
     T: Component,
 {
     fn write(self, world: &mut World) {
         if let Some(mut entity) = world.get_entity_mut(self.entity) {
-            entity.insert(self.component);
+            if !entity.contains::<T>() {
+                entity.insert(self.component);
+                stats::record(world, |stats| stats.inserted += 1);
+            }
+        } else {
+            panic!("error[B0003]: Could not add a component (of type `{}`) to entity {:?} because it doesn't exist in this World.", std::any::type_name::<T>(), self.entity);
+        }
+    }
+}
+
+pub struct InsertBundleIfNew<T> {
+    pub entity: Entity,
+    pub bundle: T,
+}
+
+impl<T> Command for InsertBundleIfNew<T>
+where
+    T: Bundle + 'static,
+{
+    fn write(self, world: &mut World) {
+        let component_ids = T::component_ids(&mut world.components, &mut world.storages);
+        if let Some(mut entity) = world.get_entity_mut(self.entity) {
+            if !component_ids.iter().any(|id| entity.contains_id(*id)) {
+                entity.insert_bundle(self.bundle);
+                stats::record(world, |stats| stats.inserted += 1);
+            }
         } else {
+            panic!("error[B0003]: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World.", std::any::type_name::<T>(), self.entity);
+        }
+    }
+}
+
+pub struct InsertFromWorld<T> {
+    pub entity: Entity,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> Command for InsertFromWorld<T>
+where
+    T: Component + FromWorld,
+{
+    fn write(self, world: &mut World) {
+        if world.get_entity(self.entity).is_none() {
             panic!("error[B0003]: Could not add a component (of type `{}`) to entity {:?} because it doesn't exist in this World.", std::any::type_name::<T>(), self.entity);
         }
+        let component = T::from_world(world);
+        Insert {
+            entity: self.entity,
+            component,
+        }
+        .write(world);
+    }
+}
+
+pub struct InsertWith<F> {
+    pub entity: Entity,
+    pub func: F,
+}
+
+impl<T, F> Command for InsertWith<F>
+where
+    T: Component,
+    F: FnOnce(&World) -> Option<T> + Send + Sync + 'static,
+{
+    fn write(self, world: &mut World) {
+        match (self.func)(world) {
+            Some(component) => Insert {
+                entity: self.entity,
+                component,
+            }
+            .write(world),
+            None => warn!(
+                "Could not create a component (of type `{}`) for entity {:?}, so it was not inserted.",
+                std::any::type_name::<T>(),
+                self.entity
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Insert<T> {
+    pub entity: Entity,
+    pub component: T,
+}
+
+impl<T> Command for Insert<T>
+where
+    T: Component,
+{
+    fn write(self, world: &mut World) {
+        if let Err(component) = world.insert_coalesced(self.entity, self.component) {
+            if let Some(mut entity) = world.get_entity_mut(self.entity) {
+                entity.insert(component);
+            } else {
+                panic!("error[B0003]: Could not add a component (of type `{}`) to entity {:?} because it doesn't exist in this World.", std::any::type_name::<T>(), self.entity);
+            }
+        }
+        stats::record(world, |stats| stats.inserted += 1);
+    }
+
+    fn target(&self) -> Option<(Entity, TypeId)> {
+        Some((self.entity, TypeId::of::<T>()))
+    }
+
+    fn is_coalesced(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug)]
+pub struct InsertEntityIndex {
+    pub entity: Entity,
+}
+
+impl Command for InsertEntityIndex {
+    fn write(self, world: &mut World) {
+        if world.get_entity(self.entity).is_none() {
+            panic!("error[B0003]: Could not add an entity index to entity {:?} because it doesn't exist in this World.", self.entity);
+        }
+        let index = world
+            .get_resource_or_insert_with(EntityIndices::default)
+            .assign(self.entity);
+        Insert {
+            entity: self.entity,
+            component: index,
+        }
+        .write(world);
     }
 }
 
@@ -760,9 +1492,15 @@ where
 {
     fn write(self, world: &mut World) {
         if let Some(mut entity_mut) = world.get_entity_mut(self.entity) {
-            entity_mut.remove::<T>();
+            if entity_mut.remove::<T>().is_some() {
+                stats::record(world, |stats| stats.removed += 1);
+            }
         }
     }
+
+    fn target(&self) -> Option<(Entity, TypeId)> {
+        Some((self.entity, TypeId::of::<T>()))
+    }
 }
 
 #[derive(Debug)]
@@ -777,9 +1515,14 @@ where
 {
     fn write(self, world: &mut World) {
         if let Some(mut entity_mut) = world.get_entity_mut(self.entity) {
+            let archetype_id = entity_mut.location().archetype_id;
             // remove intersection to gracefully handle components that were removed before running
             // this command
             entity_mut.remove_bundle_intersection::<T>();
+            // The entity only moved to another archetype if it had components of the bundle.
+            if entity_mut.location().archetype_id != archetype_id {
+                stats::record(world, |stats| stats.removed += 1);
+            }
         }
     }
 }
@@ -801,6 +1544,7 @@ pub struct InsertResource<R: Resource> {
 impl<R: Resource> Command for InsertResource<R> {
     fn write(self, world: &mut World) {
         world.insert_resource(self.resource);
+        trace_command!(resource = std::any::type_name::<R>(), "inserted resource");
     }
 }
 
@@ -814,6 +1558,16 @@ impl<R: Resource> Command for RemoveResource<R> {
     }
 }
 
+pub struct RunSchedule {
+    label: BoxedStageLabel,
+}
+
+impl Command for RunSchedule {
+    fn write(self, world: &mut World) {
+        world.run_schedule_dyn(&*self.label);
+    }
+}
+
 /// [`Command`] to log the components of a given entity. See [`EntityCommands::log_components`].
 pub struct LogComponents {
     entity: Entity,
@@ -836,8 +1590,9 @@ mod tests {
     use crate::{
         self as bevy_ecs,
         component::Component,
-        system::{CommandQueue, Commands},
-        world::World,
+        schedule::{Stage, SystemStage},
+        system::{Command, CommandQueue, Commands},
+        world::{FromWorld, World},
     };
     use std::sync::{
         atomic::{AtomicUsize, Ordering},
@@ -921,6 +1676,436 @@ mod tests {
         assert_eq!(results3, vec![(42u32, 0u64), (0u32, 42u64)]);
     }
 
+    struct SpawnPrefab {
+        value: u32,
+        count: usize,
+    }
+
+    impl Command for SpawnPrefab {
+        fn write(self, world: &mut World) {
+            for _ in 0..self.count {
+                world.spawn().insert_bundle((W(self.value), W(0u64)));
+            }
+        }
+    }
+
+    #[test]
+    fn spawn_prefabs() {
+        use crate::world::PrefabRegistry;
+
+        #[derive(Component, Debug, PartialEq)]
+        struct Health(u32);
+        #[derive(Component, Default)]
+        struct Coin;
+        #[derive(Component, Debug, PartialEq)]
+        struct Position(u32);
+
+        let mut world = World::default();
+        let mut prefabs = PrefabRegistry::default();
+        prefabs
+            .register("goblin", || (Health(10),))
+            .register_default::<(Coin,)>("coin");
+        world.insert_resource(prefabs);
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let goblin = commands.spawn_prefab("goblin").insert(Position(1)).id();
+        let coin = commands.spawn_prefab("coin").id();
+        queue.apply(&mut world);
+
+        assert_eq!(world.get::<Health>(goblin), Some(&Health(10)));
+        assert_eq!(world.get::<Position>(goblin), Some(&Position(1)));
+        assert!(world.get::<Coin>(goblin).is_none());
+        assert!(world.get::<Coin>(coin).is_some());
+        assert!(world.get::<Health>(coin).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't registered in the `PrefabRegistry`")]
+    fn spawn_unregistered_prefab() {
+        let mut world = World::default();
+        world.init_resource::<crate::world::PrefabRegistry>();
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, &world).spawn_prefab("dragon");
+        queue.apply(&mut world);
+    }
+
+    #[test]
+    fn swap_components() {
+        #[derive(Component, Debug, PartialEq)]
+        struct Slot(u32);
+
+        let mut world = World::default();
+        let a = world.spawn().insert(Slot(1)).insert(W(10u32)).id();
+        let b = world.spawn().insert(Slot(2)).insert(W(20u32)).id();
+        let empty = world.spawn().id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.swap_components::<Slot>(a, b);
+        // Entities without the component are skipped.
+        commands.swap_components::<Slot>(a, empty);
+        commands.swap_bundle::<(W<u32>,)>(b, empty);
+        queue.apply(&mut world);
+        assert_eq!(world.get::<Slot>(a), Some(&Slot(2)));
+        assert_eq!(world.get::<Slot>(b), Some(&Slot(1)));
+        assert!(world.get::<Slot>(empty).is_none());
+        assert_eq!(world.get::<W<u32>>(b).unwrap().0, 20);
+
+        Commands::new(&mut queue, &world).swap_bundle::<(Slot, W<u32>)>(a, b);
+        queue.apply(&mut world);
+        assert_eq!(world.get::<Slot>(a), Some(&Slot(1)));
+        assert_eq!(world.get::<W<u32>>(a).unwrap().0, 20);
+        assert_eq!(world.get::<Slot>(b), Some(&Slot(2)));
+        assert_eq!(world.get::<W<u32>>(b).unwrap().0, 10);
+    }
+
+    #[test]
+    fn custom_command() {
+        let mut world = World::default();
+
+        // A command can be written to a world directly.
+        SpawnPrefab { value: 1, count: 1 }.write(&mut world);
+        assert_eq!(world.entities().len(), 1);
+
+        let mut command_queue = CommandQueue::default();
+        Commands::new(&mut command_queue, &world).add(SpawnPrefab { value: 2, count: 2 });
+        assert_eq!(world.entities().len(), 1);
+        command_queue.apply(&mut world);
+
+        let mut results = world
+            .query::<&W<u32>>()
+            .iter(&world)
+            .map(|v| v.0)
+            .collect::<Vec<_>>();
+        results.sort_unstable();
+        assert_eq!(results, vec![1, 2, 2]);
+    }
+
+    #[test]
+    fn spawn_singleton() {
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+
+        // Queued twice before being applied.
+        for _ in 0..2 {
+            Commands::new(&mut command_queue, &world)
+                .spawn_singleton::<W<u32>, _>(|| (W(1u32), W(2u64)));
+        }
+        command_queue.apply(&mut world);
+        for _ in 0..2 {
+            Commands::new(&mut command_queue, &world)
+                .spawn_singleton::<W<u32>, _>(|| (W(3u32), W(4u64)));
+            command_queue.apply(&mut world);
+        }
+
+        let results = world
+            .query::<(&W<u32>, &W<u64>)>()
+            .iter(&world)
+            .map(|(a, b)| (a.0, b.0))
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec![(1u32, 2u64)]);
+    }
+
+    #[test]
+    #[cfg(feature = "command_stats")]
+    fn command_stats() {
+        use crate::system::CommandStats;
+
+        let mut world = World::default();
+        world.init_resource::<CommandStats>();
+        let mut command_queue = CommandQueue::default();
+
+        let mut commands = Commands::new(&mut command_queue, &world);
+        let a = commands.spawn().insert(W(1u32)).id();
+        let b = commands.spawn_bundle((W(2u32), W(3u64))).id();
+        commands.spawn_batch(vec![(W(4u32),), (W(5u32),), (W(6u32),)]);
+        commands.entity(b).remove::<W<u64>>();
+        // Nothing left to remove.
+        commands.entity(b).remove::<W<u64>>();
+        commands.entity(a).despawn();
+        command_queue.apply(&mut world);
+
+        assert_eq!(
+            *world.resource::<CommandStats>(),
+            CommandStats {
+                spawned: 5,
+                despawned: 1,
+                inserted: 2,
+                removed: 1,
+            }
+        );
+
+        world.resource_mut::<CommandStats>().reset();
+        Commands::new(&mut command_queue, &world)
+            .entity(b)
+            .remove_bundle::<(W<u32>, W<u64>)>()
+            // Nothing left to remove.
+            .remove_bundle::<(W<u32>, W<u64>)>()
+            .despawn();
+        command_queue.apply(&mut world);
+        assert_eq!(
+            *world.resource::<CommandStats>(),
+            CommandStats {
+                despawned: 1,
+                removed: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "command_stats")]
+    fn command_stats_spawn_prefab() {
+        use crate::system::CommandStats;
+        use crate::world::PrefabRegistry;
+
+        let mut world = World::default();
+        world.init_resource::<CommandStats>();
+        let mut prefabs = PrefabRegistry::default();
+        prefabs.register("goblin", || (W(10u32),));
+        world.insert_resource(prefabs);
+
+        let mut command_queue = CommandQueue::default();
+        Commands::new(&mut command_queue, &world).spawn_prefab("goblin");
+        command_queue.apply(&mut world);
+
+        // The entity of a prefab is reserved, so it is only counted once flushed.
+        assert_eq!(
+            *world.resource::<CommandStats>(),
+            CommandStats {
+                spawned: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn despawn_twice_in_one_frame() {
+        use crate::{
+            entity::Entity,
+            query::With,
+            schedule::{Stage, SystemStage},
+            system::Query,
+        };
+
+        fn despawn(mut commands: Commands, query: Query<Entity, With<W<u32>>>) {
+            for entity in query.iter() {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        fn despawn_if_exists(mut commands: Commands, query: Query<Entity, With<W<u32>>>) {
+            for entity in query.iter() {
+                commands.entity(entity).despawn_if_exists();
+            }
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn().insert(W(1u32)).id();
+        let survivor = world.spawn().insert(W(2u64)).id();
+        let mut stage = SystemStage::parallel()
+            .with_system(despawn)
+            .with_system(despawn_if_exists)
+            .with_system(despawn_if_exists);
+        stage.run(&mut world);
+
+        assert!(!world.is_alive(entity));
+        assert!(world.is_alive(survivor));
+        assert_eq!(world.entities().len(), 1);
+    }
+
+    #[test]
+    fn stop_queuing_at_limit() {
+        #[derive(Component)]
+        struct Enemy;
+
+        const LIMIT: usize = 10;
+
+        fn spawner(mut commands: Commands) {
+            assert!(commands.is_empty());
+            for _ in 0..100 {
+                if commands.len() >= LIMIT {
+                    break;
+                }
+                commands.spawn().insert(Enemy);
+            }
+            assert_eq!(commands.len(), LIMIT);
+        }
+
+        let mut world = World::default();
+        let mut stage = SystemStage::parallel().with_system(spawner);
+        stage.run(&mut world);
+        assert_eq!(world.query::<&Enemy>().iter(&world).count(), LIMIT);
+        // The queue is emptied once applied, so the next run queues as many again.
+        stage.run(&mut world);
+        assert_eq!(world.query::<&Enemy>().iter(&world).count(), 2 * LIMIT);
+    }
+
+    #[test]
+    fn insert_from_world_and_insert_with() {
+        struct DefaultColor(u32);
+
+        #[derive(Component, Debug, PartialEq)]
+        struct Color(u32);
+
+        impl FromWorld for Color {
+            fn from_world(world: &mut World) -> Self {
+                Color(world.resource::<DefaultColor>().0)
+            }
+        }
+
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+
+        // Without the resource, `insert_with` skips the insertion.
+        let missing = Commands::new(&mut command_queue, &world)
+            .spawn()
+            .insert_with(|world: &World| {
+                world
+                    .get_resource::<DefaultColor>()
+                    .map(|color| Color(color.0))
+            })
+            .id();
+        command_queue.apply(&mut world);
+        assert!(world.get_entity(missing).is_some());
+        assert!(world.get::<Color>(missing).is_none());
+
+        let mut commands = Commands::new(&mut command_queue, &world);
+        commands.insert_resource(DefaultColor(7));
+        // The component is created when the commands are applied, after the resource above.
+        let from_world = commands.spawn().insert_from_world::<Color>().id();
+        let with = commands
+            .spawn()
+            .insert_with(|world: &World| {
+                world
+                    .get_resource::<DefaultColor>()
+                    .map(|color| Color(color.0 + 1))
+            })
+            .id();
+        command_queue.apply(&mut world);
+        assert_eq!(world.get::<Color>(from_world), Some(&Color(7)));
+        assert_eq!(world.get::<Color>(with), Some(&Color(8)));
+    }
+
+    #[test]
+    fn run_exclusive_after_earlier_commands() {
+        struct Count(usize);
+
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut command_queue, &world);
+        let entity = commands.spawn().insert(W(1u32)).id();
+        commands.run_exclusive(move |world| {
+            // The entity and its component were inserted by the commands queued before.
+            let value = world.get::<W<u32>>(entity).unwrap().0;
+            let count = world.query::<&W<u32>>().iter(world).count();
+            world.insert_resource(Count(count + value as usize));
+        });
+        commands.entity(entity).insert(W(10u32));
+        command_queue.apply(&mut world);
+
+        assert_eq!(world.resource::<Count>().0, 2);
+        // Commands queued after the closure were applied after it.
+        assert_eq!(world.get::<W<u32>>(entity).unwrap().0, 10);
+    }
+
+    #[test]
+    fn insert_if_new() {
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let existing = world.spawn().insert(W(1u32)).id();
+        let empty = world.spawn().id();
+
+        let mut commands = Commands::new(&mut command_queue, &world);
+        commands.entity(existing).insert_if_new(W(10u32));
+        // The first command applied wins.
+        commands
+            .entity(empty)
+            .insert_if_new(W(20u32))
+            .insert_if_new(W(30u32));
+        // Bundles are only inserted if none of their components are there.
+        commands
+            .entity(existing)
+            .insert_bundle_if_new((W(40u32), W(40u64)));
+        commands
+            .entity(empty)
+            .insert_bundle_if_new((W(50u64), W(50u8)));
+        command_queue.apply(&mut world);
+
+        assert_eq!(world.get::<W<u32>>(existing).unwrap().0, 1);
+        assert!(world.get::<W<u64>>(existing).is_none());
+        assert_eq!(world.get::<W<u32>>(empty).unwrap().0, 20);
+        assert_eq!(world.get::<W<u64>>(empty).unwrap().0, 50);
+        assert_eq!(world.get::<W<u8>>(empty).unwrap().0, 50);
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn trace_spawn_command() {
+        use crate::system::Spawn;
+        use bevy_utils::tracing::{
+            field::{Field, Visit},
+            span, subscriber, Event, Metadata, Subscriber,
+        };
+        use std::sync::Mutex;
+
+        /// Records the fields of every event as `name=value` strings.
+        #[derive(Default)]
+        struct CaptureEvents(Arc<Mutex<Vec<Vec<String>>>>);
+
+        struct FieldVisitor<'a>(&'a mut Vec<String>);
+
+        impl Visit for FieldVisitor<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0.push(format!("{}={:?}", field.name(), value));
+            }
+        }
+
+        impl Subscriber for CaptureEvents {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = Vec::new();
+                event.record(&mut FieldVisitor(&mut fields));
+                self.0.lock().unwrap().push(fields);
+            }
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let capture = CaptureEvents::default();
+        let events = capture.0.clone();
+        subscriber::with_default(capture, || {
+            let mut world = World::default();
+            world.spawn();
+            let mut command_queue = CommandQueue::default();
+            let entity = Commands::new(&mut command_queue, &world)
+                .spawn_bundle((W(1u32), W(2u64)))
+                .id();
+            Commands::new(&mut command_queue, &world).add(Spawn { bundle: (W(3u8),) });
+            command_queue.apply(&mut world);
+            assert!(world.get::<W<u32>>(entity).is_some());
+        });
+
+        let events = events.lock().unwrap();
+        assert!(events
+            .iter()
+            .any(|fields| fields.first().unwrap() == "message=spawned entity"));
+        assert!(events.contains(&vec![
+            "message=applied commands".to_string(),
+            "commands=2".to_string(),
+            "spawned=1".to_string(),
+            "entities=3".to_string(),
+        ]));
+    }
+
     #[test]
     fn remove_components() {
         let mut world = World::default();
@@ -968,6 +2153,134 @@ mod tests {
         assert_eq!(results_after_u64, vec![]);
     }
 
+    #[test]
+    fn collapse_insert_remove() {
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let entity = world.spawn().insert(W(0u64)).id();
+        let (dropck, is_dropped) = DropCk::new_pair();
+        let archetypes = world.archetypes().len();
+
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .insert(dropck)
+            .insert(W(1u32))
+            .remove::<W<u32>>()
+            .insert(W(2u32))
+            .remove::<DropCk>();
+        command_queue.apply(&mut world);
+
+        // Only the last command for each component was written, so the entity moved once, straight
+        // to its final archetype, and nothing was removed from it.
+        assert_eq!(world.archetypes().len(), archetypes + 1);
+        let dropck_id = world.init_component::<DropCk>();
+        assert!(world
+            .archetypes()
+            .iter()
+            .all(|archetype| !archetype.contains(dropck_id)));
+        assert_eq!(world.get::<W<u32>>(entity).unwrap().0, 2);
+        assert!(world.get::<DropCk>(entity).is_none());
+        assert_eq!(is_dropped.load(Ordering::Relaxed), 1);
+        assert_eq!(world.removed::<W<u32>>().count(), 0);
+        assert_eq!(world.removed::<DropCk>().count(), 0);
+
+        // Other commands may observe the intermediate state, so commands around them aren't
+        // collapsed.
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .remove::<W<u32>>()
+            .commands()
+            .add(move |world: &mut World| assert!(world.get::<W<u32>>(entity).is_none()));
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .insert(W(3u32));
+        command_queue.apply(&mut world);
+        assert_eq!(world.get::<W<u32>>(entity).unwrap().0, 3);
+        assert_eq!(world.removed::<W<u32>>().collect::<Vec<_>>(), vec![entity]);
+    }
+
+    #[test]
+    fn coalesce_inserts_across_systems() {
+        use crate::schedule::ParallelSystemDescriptorCoercion;
+
+        let mut world = World::default();
+        let entity = world.spawn().id();
+        let other = world.spawn().id();
+        let (first_dropck, first_dropped) = DropCk::new_pair();
+        let (second_dropck, second_dropped) = DropCk::new_pair();
+        let first_dropck = std::sync::Mutex::new(Some(first_dropck));
+        let second_dropck = std::sync::Mutex::new(Some(second_dropck));
+
+        let mut stage = SystemStage::parallel()
+            .with_system(
+                (move |mut commands: Commands| {
+                    let dropck = first_dropck.lock().unwrap().take().unwrap();
+                    commands.entity(entity).insert(W(1u32)).insert(dropck);
+                })
+                .label("first"),
+            )
+            .with_system(
+                (move |mut commands: Commands| {
+                    let dropck = second_dropck.lock().unwrap().take().unwrap();
+                    commands.entity(entity).insert(W(2u32)).insert(dropck);
+                })
+                .after("first"),
+            )
+            .with_system(move |mut commands: Commands| {
+                commands.entity(entity).insert(W(3u64));
+            })
+            .with_system(move |mut commands: Commands| {
+                commands.entity(other).insert(W(4u16));
+                commands.add(move |world: &mut World| {
+                    assert_eq!(world.get::<W<u16>>(other).unwrap().0, 4);
+                });
+            });
+        stage.run(&mut world);
+
+        // The inserts of all the systems moved the entity once, straight to its final archetype.
+        let component = world.init_component::<W<u32>>();
+        let archetypes = world
+            .archetypes()
+            .iter()
+            .filter(|archetype| archetype.contains(component))
+            .count();
+        assert_eq!(archetypes, 1);
+        assert_eq!(world.get::<W<u32>>(entity).unwrap().0, 2);
+        assert_eq!(world.get::<W<u64>>(entity).unwrap().0, 3);
+        assert!(world.get::<DropCk>(entity).is_some());
+        assert_eq!(first_dropped.load(Ordering::Relaxed), 1);
+        assert_eq!(second_dropped.load(Ordering::Relaxed), 0);
+        assert!(world.coalesced_inserts.is_none());
+    }
+
+    #[test]
+    fn insert_for_each_match() {
+        use crate::query::With;
+
+        #[derive(Component)]
+        struct Enemy;
+
+        #[derive(Component, Debug, PartialEq)]
+        struct Marked;
+
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let enemy = world.spawn().insert(Enemy).id();
+        let friend = world.spawn().insert(W(1u32)).id();
+
+        let mut commands = Commands::new(&mut command_queue, &world);
+        // Applied before the bulk insert, so it is matched too.
+        let spawned_enemy = commands.spawn().insert(Enemy).id();
+        commands.insert_for_each::<With<Enemy>, _>(|()| Some((Marked,)));
+        // Returning `None` skips the entity.
+        commands.insert_for_each::<&W<u32>, _>(|w| (w.0 != 1).then_some((Marked,)));
+        command_queue.apply(&mut world);
+
+        assert_eq!(world.get::<Marked>(enemy), Some(&Marked));
+        assert_eq!(world.get::<Marked>(spawned_enemy), Some(&Marked));
+        assert_eq!(world.get::<Marked>(friend), None);
+    }
+
     #[test]
     fn remove_resources() {
         let mut world = World::default();
@@ -8,7 +8,7 @@ use crate::{
     system::{SystemParam, SystemParamFetch, SystemParamState},
 };
 
-use super::{CommandQueue, Commands};
+use super::{CommandBudget, CommandQueue, Commands};
 
 #[doc(hidden)]
 #[derive(Default)]
@@ -75,8 +75,13 @@ unsafe impl SystemParamState for ParallelCommandsState {
     }
 
     fn apply(&mut self, world: &mut World) {
+        let budgeted = world.contains_resource::<CommandBudget>();
         for cq in self.thread_local_storage.iter_mut() {
-            cq.get_mut().apply(world);
+            if budgeted {
+                CommandBudget::apply_queue(world, cq.get_mut());
+            } else {
+                cq.get_mut().apply(world);
+            }
         }
     }
 }
@@ -0,0 +1,57 @@
+use crate::world::World;
+
+/// Counts the structural changes applied by the built-in [`Command`](super::Command)s.
+///
+/// When the `command_stats` feature is enabled and this resource exists in the [`World`], applying
+/// a [`CommandQueue`](super::CommandQueue) records here the entities spawned with
+/// [`Commands::spawn`](super::Commands::spawn), and what each built-in command that spawns or
+/// despawns an entity, or inserts or removes components, did. Counters accumulate until
+/// [`reset`](Self::reset) is called, so a debug overlay can read and reset them once per frame to
+/// show how much structural churn the app has. Without the feature nothing is recorded, so
+/// release builds don't pay for the counters.
+///
+/// Custom commands and [`Commands::insert_or_spawn_batch`](super::Commands::insert_or_spawn_batch)
+/// are not counted.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::CommandStats};
+/// fn report_churn(mut stats: ResMut<CommandStats>) {
+///     println!(
+///         "spawned {}, despawned {}",
+///         stats.spawned, stats.despawned
+///     );
+///     stats.reset();
+/// }
+/// # bevy_ecs::system::assert_is_system(report_churn);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CommandStats {
+    /// The number of entities spawned.
+    pub spawned: usize,
+    /// The number of entities despawned.
+    pub despawned: usize,
+    /// The number of component or bundle insertions.
+    pub inserted: usize,
+    /// The number of component or bundle removals.
+    pub removed: usize,
+}
+
+impl CommandStats {
+    /// Sets every counter back to zero.
+    pub fn reset(&mut self) {
+        *self = CommandStats::default();
+    }
+}
+
+/// Records a structural change in the [`CommandStats`] of `world`, if it has them.
+#[cfg(feature = "command_stats")]
+#[inline]
+pub(super) fn record(world: &mut World, f: impl FnOnce(&mut CommandStats)) {
+    if let Some(mut stats) = world.get_resource_mut::<CommandStats>() {
+        f(&mut stats);
+    }
+}
+
+#[cfg(not(feature = "command_stats"))]
+#[inline(always)]
+pub(super) fn record(_world: &mut World, _f: impl FnOnce(&mut CommandStats)) {}
@@ -149,6 +149,36 @@ mod tests {
         assert_eq!(*world.resource::<usize>(), 1);
     }
 
+    #[test]
+    fn query_in_exclusive_system() {
+        fn grow(world: &mut World) {
+            let mut state = world.query::<(Entity, &mut Foo)>();
+            let mut query = state.query_mut(world);
+            for (_, mut foo) in query.iter_mut() {
+                foo.0 += 1.0;
+            }
+            let largest = query
+                .iter()
+                .max_by(|(_, a), (_, b)| a.0.total_cmp(&b.0))
+                .map(|(entity, _)| entity);
+            if let Some(entity) = largest {
+                query.get_mut(entity).unwrap().1 .0 *= 10.0;
+            }
+            // The world is available again once the query is dropped.
+            *world.resource_mut::<usize>() += 1;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0usize);
+        let first = world.spawn().insert(Foo(0.0)).id();
+        let second = world.spawn().insert(Foo(1.0)).id();
+        let mut stage = SystemStage::single_threaded().with_system(grow.exclusive_system());
+        stage.run(&mut world);
+        assert_eq!(world.get::<Foo>(first).unwrap().0, 1.0);
+        assert_eq!(world.get::<Foo>(second).unwrap().0, 20.0);
+        assert_eq!(*world.resource::<usize>(), 1);
+    }
+
     #[test]
     fn update_archetype_for_exclusive_system_coerced() {
         fn spawn_entity(mut commands: crate::prelude::Commands) {
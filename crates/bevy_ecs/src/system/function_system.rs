@@ -9,7 +9,7 @@ use crate::{
         check_system_change_tick, ReadOnlySystemParamFetch, System, SystemParam, SystemParamFetch,
         SystemParamItem, SystemParamState,
     },
-    world::{World, WorldId},
+    world::{StructuralChangeGuard, World, WorldId},
 };
 use bevy_ecs_macros::all_tuples;
 use std::{borrow::Cow, fmt::Debug, hash::Hash, marker::PhantomData};
@@ -256,6 +256,11 @@ impl<Param: SystemParam> FromWorld for SystemState<Param> {
 // This trait has to be generic because we have potentially overlapping impls, in particular
 // because Rust thinks a type could impl multiple different `FnMut` combinations
 // even though none can currently
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a valid system",
+    label = "invalid system",
+    note = "every parameter of a system function must implement `SystemParam`"
+)]
 pub trait IntoSystem<In, Out, Params>: Sized {
     type System: System<In = In, Out = Out>;
     /// Turns this value into its corresponding [`System`].
@@ -353,6 +358,22 @@ where
     // When lines get too long, rustfmt can sometimes refuse to format them.
     // Work around this by storing the message separately.
     const PARAM_MESSAGE: &'static str = "System's param_state was not found. Did you forget to initialize this system before running it?";
+
+    /// Overrides the name of this system, which defaults to the type name of its function.
+    ///
+    /// All closures defined in the same function share a type name, so naming them lets logs,
+    /// ambiguity reports and profiling spans tell them apart.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// let system = IntoSystem::into_system(|| {}).named("my_system");
+    /// assert_eq!(system.name(), "my_system");
+    /// ```
+    #[must_use]
+    pub fn named(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.system_meta.name = name.into();
+        self
+    }
 }
 
 impl<In, Out, Param, Marker, F> System for FunctionSystem<In, Out, Param, Marker, F>
@@ -400,7 +421,10 @@ where
             world,
             change_tick,
         );
-        let out = self.func.run(input, params);
+        let out = {
+            let _guard = StructuralChangeGuard::new(world, self.system_meta.name.clone());
+            self.func.run(input, params)
+        };
         self.system_meta.last_change_tick = change_tick;
         out
     }
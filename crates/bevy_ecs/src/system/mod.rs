@@ -52,12 +52,14 @@
 //! - [`ResMut`] and `Option<ResMut>`
 //! - [`Commands`]
 //! - [`Local`]
+//! - [`SharedLocal`]
 //! - [`EventReader`](crate::event::EventReader)
 //! - [`EventWriter`](crate::event::EventWriter)
 //! - [`NonSend`] and `Option<NonSend>`
 //! - [`NonSendMut`] and `Option<NonSendMut>`
 //! - [`&World`](crate::world::World)
 //! - [`RemovedComponents`]
+//! - [`RemovedResource`]
 //! - [`SystemChangeTick`]
 //! - [`Archetypes`](crate::archetype::Archetypes) (Provides Archetype metadata)
 //! - [`Bundles`](crate::bundle::Bundles) (Provides Bundles metadata)
@@ -70,18 +72,22 @@ mod commands;
 mod exclusive_system;
 mod function_system;
 mod query;
+mod query_diff;
 #[allow(clippy::module_inception)]
 mod system;
 mod system_chaining;
 mod system_param;
+mod toggle_marker;
 
 pub use commands::*;
 pub use exclusive_system::*;
 pub use function_system::*;
 pub use query::*;
+pub use query_diff::*;
 pub use system::*;
 pub use system_chaining::*;
 pub use system_param::*;
+pub use toggle_marker::*;
 
 /// Ensure that a given function is a system
 ///
@@ -108,10 +114,10 @@ mod tests {
         entity::{Entities, Entity},
         prelude::AnyOf,
         query::{Added, Changed, Or, With, Without},
-        schedule::{Schedule, Stage, SystemStage},
+        schedule::{ParallelSystemDescriptorCoercion, Schedule, Stage, SystemStage},
         system::{
             Commands, IntoExclusiveSystem, IntoSystem, Local, NonSend, NonSendMut, ParamSet, Query,
-            RemovedComponents, Res, ResMut, System, SystemState,
+            RemovedComponents, RemovedResource, Res, ResMut, System, SystemState,
         },
         world::{FromWorld, World},
     };
@@ -242,6 +248,81 @@ mod tests {
         assert!(*world.resource::<bool>(), "system ran");
     }
 
+    #[test]
+    fn query_change_ticks() {
+        struct Spawned(Entity);
+        struct Ticks(u32, u32);
+        fn spawn(mut commands: Commands, mut done: Local<bool>) {
+            if !*done {
+                let entity = commands.spawn().insert(W(0)).id();
+                commands.insert_resource(Spawned(entity));
+                *done = true;
+            }
+        }
+        fn read_ticks(
+            query: Query<&W<i32>>,
+            spawned: Option<Res<Spawned>>,
+            mut ticks: ResMut<Ticks>,
+        ) {
+            if let Some(spawned) = spawned {
+                let component_ticks = query.get_change_ticks::<W<i32>>(spawned.0).unwrap();
+                ticks.0 = component_ticks.added_tick();
+                ticks.1 = component_ticks.changed_tick();
+            }
+        }
+
+        let mut world = World::default();
+        world.insert_resource(Ticks(0, 0));
+        let mut schedule = Schedule::default();
+        let mut update = SystemStage::parallel();
+        update.add_system(spawn);
+        update.add_system(read_ticks);
+        schedule.add_stage("update", update);
+
+        schedule.run(&mut world);
+        // commands are applied at the end of the stage
+        let inserted_at = world.read_change_tick();
+        schedule.run(&mut world);
+        let ticks = world.resource::<Ticks>();
+        assert_eq!((ticks.0, ticks.1), (inserted_at, inserted_at));
+
+        let entity = world.resource::<Spawned>().0;
+        world.get_mut::<W<i32>>(entity).unwrap().0 += 1;
+        schedule.run(&mut world);
+        let ticks = world.resource::<Ticks>();
+        assert_eq!(ticks.0, inserted_at);
+        assert!(ticks.1 > ticks.0);
+    }
+
+    #[test]
+    fn resources_are_borrowed_in_place() {
+        // Deliberately not `Clone`: systems must only ever see a borrow of the stored value.
+        struct Store(Vec<u8>);
+        struct Address(usize);
+
+        fn grow(mut store: ResMut<Store>) {
+            store.0.push(0);
+        }
+
+        fn record(store: Res<Store>, mut address: ResMut<Address>) {
+            address.0 = &*store as *const Store as usize;
+        }
+
+        let mut world = World::default();
+        world.insert_resource(Store(Vec::with_capacity(1024)));
+        world.insert_resource(Address(0));
+        let stored = world.resource::<Store>() as *const Store as usize;
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(grow);
+        stage.add_system(record.after(grow));
+        stage.run(&mut world);
+        stage.run(&mut world);
+
+        assert_eq!(world.resource::<Address>().0, stored);
+        assert_eq!(world.resource::<Store>().0.len(), 2);
+    }
+
     #[test]
     fn changed_resource_system() {
         struct Added(usize);
@@ -381,6 +462,25 @@ mod tests {
         run_system(&mut world, sys);
     }
 
+    #[test]
+    #[should_panic = "&bevy_ecs::system::tests::A conflicts with a previous access in this query. Shared access cannot coincide with exclusive access."]
+    fn self_conflicting_query_system() {
+        fn sys(_q: Query<(&mut A, &A)>) {}
+
+        let mut system = IntoSystem::into_system(sys);
+        // The conflict is caught when the system is initialized, before it ever runs.
+        system.initialize(&mut World::default());
+    }
+
+    #[test]
+    #[should_panic = "&mut bevy_ecs::system::tests::A conflicts with a previous access in this query. Mutable component access must be unique."]
+    fn self_conflicting_query_mut_system() {
+        fn sys(_q: Query<(&mut A, Option<&mut A>)>) {}
+
+        let mut system = IntoSystem::into_system(sys);
+        system.initialize(&mut World::default());
+    }
+
     #[test]
     fn query_set_system() {
         fn sys(mut _set: ParamSet<(Query<&mut A>, Query<&A>)>) {}
@@ -474,6 +574,37 @@ mod tests {
         assert!(*world.resource::<bool>());
     }
 
+    #[test]
+    fn shared_local_system() {
+        use crate::{schedule::SystemStage, system::SharedLocal};
+
+        struct Spawned;
+        struct Despawned;
+
+        fn count(
+            mut spawned: SharedLocal<Spawned, u32>,
+            mut despawned: SharedLocal<Despawned, u32>,
+        ) {
+            *spawned += 1;
+            *despawned += 2;
+        }
+
+        fn check(spawned: SharedLocal<Spawned, u32>, mut counts: ResMut<Vec<u32>>) {
+            counts.push(*spawned);
+        }
+
+        let mut world = World::default();
+        world.insert_resource(Vec::<u32>::new());
+        let mut stage = SystemStage::parallel()
+            .with_system(count.label("count"))
+            .with_system(check.after("count"));
+        stage.run(&mut world);
+        stage.run(&mut world);
+
+        // Both systems share the `Spawned` counter, which is separate from the `Despawned` one.
+        assert_eq!(*world.resource::<Vec<u32>>(), vec![1, 2]);
+    }
+
     #[test]
     fn non_send_option_system() {
         let mut world = World::default();
@@ -584,6 +715,149 @@ mod tests {
         assert_eq!(world.resource::<NSystems>().0, 2);
     }
 
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "changed the structure of the world while it ran")]
+    fn immediate_spawn_during_iteration() {
+        use crate::world::StructuralChangeGuard;
+
+        // Systems hold the guard while they run, so no system can be used to trigger it without
+        // aliasing the world: hold it directly instead.
+        let mut world = World::default();
+        let mut other_world = World::default();
+        let _guard = StructuralChangeGuard::new(&world, "iterating_system".into());
+        // Only the world the system runs on is guarded.
+        other_world.spawn().insert(A);
+        world.spawn().insert(A);
+    }
+
+    #[test]
+    fn removal_visible_in_later_stage() {
+        #[derive(Default)]
+        struct Observed(Vec<Entity>);
+
+        fn remove(mut commands: Commands, query: Query<Entity, With<A>>) {
+            for entity in query.iter() {
+                commands.entity(entity).remove::<A>();
+            }
+        }
+
+        fn observe(removed: RemovedComponents<A>, mut observed: ResMut<Observed>) {
+            observed.0.extend(removed.iter());
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Observed>();
+        let entity = world.spawn().insert(A).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", SystemStage::parallel().with_system(remove));
+        schedule.add_stage("post_update", SystemStage::parallel().with_system(observe));
+        schedule.add_stage("last", SystemStage::parallel().with_system(observe));
+
+        schedule.run(&mut world);
+        // The consumer in "last" has its own cursor, and sees the removal too.
+        assert_eq!(world.resource::<Observed>().0, vec![entity, entity]);
+
+        // A consumer that runs again before the trackers are cleared doesn't see removals twice.
+        let mut observe = IntoSystem::into_system(observe);
+        observe.initialize(&mut world);
+        observe.run((), &mut world);
+        observe.run((), &mut world);
+        assert_eq!(world.resource::<Observed>().0.len(), 3);
+
+        world.clear_trackers();
+        schedule.run(&mut world);
+        observe.run((), &mut world);
+        assert_eq!(world.resource::<Observed>().0.len(), 3);
+
+        // The cursors also reset when the trackers are cleared by an exclusive system, as in
+        // `App`s.
+        schedule.add_stage(
+            "clear_trackers",
+            SystemStage::single_threaded().with_system(World::clear_trackers.exclusive_system()),
+        );
+        schedule.run(&mut world);
+        let second = world.spawn().insert(A).id();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Observed>().0[3..], [second, second]);
+    }
+
+    #[test]
+    fn resource_removal_observed() {
+        struct Level(u32);
+
+        #[derive(Clone, Copy)]
+        enum Transition {
+            None,
+            Unload,
+            Load,
+            Reload,
+        }
+
+        #[derive(Default)]
+        struct Observed(Vec<(bool, bool, bool)>);
+
+        fn transition(mut commands: Commands, transition: Res<Transition>) {
+            match *transition {
+                Transition::None => {}
+                Transition::Unload => commands.remove_resource::<Level>(),
+                Transition::Load => commands.insert_resource(Level(1)),
+                Transition::Reload => {
+                    commands.remove_resource::<Level>();
+                    commands.insert_resource(Level(2));
+                }
+            }
+        }
+
+        fn observe(
+            removed: RemovedResource<Level>,
+            level: Option<Res<Level>>,
+            mut observed: ResMut<Observed>,
+        ) {
+            let (added, changed) = level.map_or((false, false), |level| {
+                (level.is_added(), level.is_changed())
+            });
+            observed.0.push((removed.is_removed(), added, changed));
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Observed>();
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", SystemStage::parallel().with_system(transition));
+        schedule.add_stage("last", SystemStage::parallel().with_system(observe));
+
+        for frame in [
+            Transition::Load,
+            Transition::None,
+            Transition::Unload,
+            Transition::None,
+            Transition::Load,
+            Transition::Reload,
+            Transition::None,
+        ] {
+            world.insert_resource(frame);
+            schedule.run(&mut world);
+            world.clear_trackers();
+        }
+
+        assert_eq!(
+            world.resource::<Observed>().0,
+            vec![
+                (false, true, true),
+                (false, false, false),
+                (true, false, false),
+                (false, false, false),
+                (false, true, true),
+                // Removing and inserting again within a frame is neither a removal nor an
+                // addition, only a change.
+                (false, false, true),
+                (false, false, false),
+            ]
+        );
+        assert_eq!(world.resource::<Level>().0, 2);
+    }
+
     #[test]
     fn world_collections_system() {
         let mut world = World::default();
@@ -628,6 +902,40 @@ mod tests {
         assert!(*world.resource::<bool>());
     }
 
+    #[test]
+    fn world_param_follows_other_entity() {
+        #[derive(Component)]
+        struct Target(Entity);
+
+        fn follow(world: &World, query: Query<&Target>) -> Vec<Option<usize>> {
+            query
+                .iter()
+                .map(|target| world.get::<W<usize>>(target.0).map(|w| w.0))
+                .collect()
+        }
+
+        fn write(mut query: Query<&mut W<usize>>) {
+            for mut w in query.iter_mut() {
+                w.0 += 1;
+            }
+        }
+
+        let mut world = World::default();
+        let target = world.spawn().insert(W(10usize)).id();
+        world.spawn().insert(Target(target));
+
+        let mut follow = IntoSystem::into_system(follow);
+        follow.initialize(&mut world);
+        assert_eq!(follow.run((), &mut world), vec![Some(10)]);
+
+        // `&World` reads every component, so it conflicts with any writer.
+        let mut write = IntoSystem::into_system(write);
+        write.initialize(&mut world);
+        assert!(!follow
+            .component_access()
+            .is_compatible(write.component_access()));
+    }
+
     #[test]
     fn get_system_conflicts() {
         fn sys_x(_: Res<A>, _: Res<B>, _: Query<(&C, &D)>) {}
@@ -673,6 +981,360 @@ mod tests {
         with_filter.run((), &mut world);
     }
 
+    #[test]
+    fn generic_system_per_component() {
+        trait Decay: Component {
+            fn value(&mut self) -> &mut u32;
+        }
+
+        #[derive(Component)]
+        struct Poison(u32);
+        impl Decay for Poison {
+            fn value(&mut self) -> &mut u32 {
+                &mut self.0
+            }
+        }
+
+        #[derive(Component)]
+        struct Burn(u32);
+        impl Decay for Burn {
+            fn value(&mut self) -> &mut u32 {
+                &mut self.0
+            }
+        }
+
+        fn decay<T: Decay>(mut query: Query<&mut T>) {
+            for mut effect in query.iter_mut() {
+                *effect.value() -= 1;
+            }
+        }
+
+        // Each monomorphization is a distinct system, named after its type parameter.
+        let poison = IntoSystem::into_system(decay::<Poison>);
+        let burn = IntoSystem::into_system(decay::<Burn>);
+        assert_ne!(poison.name(), burn.name());
+        assert!(poison
+            .name()
+            .ends_with("decay<bevy_ecs::system::tests::generic_system_per_component::Poison>"));
+
+        let mut world = World::default();
+        let entity = world.spawn().insert(Poison(5)).insert(Burn(3)).id();
+        let mut stage = SystemStage::parallel()
+            .with_system(decay::<Poison>)
+            .with_system(decay::<Burn>)
+            .with_system(decay::<Burn>);
+        stage.run(&mut world);
+        assert_eq!(world.get::<Poison>(entity).unwrap().0, 4);
+        assert_eq!(world.get::<Burn>(entity).unwrap().0, 1);
+    }
+
+    #[test]
+    fn query_for_each_with() {
+        struct Factor(usize);
+        struct Sum(usize);
+
+        fn scaled_sum(mut query: Query<&mut W<usize>>, factor: Res<Factor>, mut sum: ResMut<Sum>) {
+            query.for_each_with(&*factor, |factor, mut w| {
+                w.0 *= factor.0;
+                sum.0 += w.0;
+            });
+        }
+
+        let mut world = World::default();
+        world.insert_resource(Factor(3));
+        world.insert_resource(Sum(0));
+        world.spawn_batch((1..=4usize).map(|i| (W(i),)));
+
+        run_system(&mut world, scaled_sum);
+        assert_eq!(world.resource::<Sum>().0, 30);
+    }
+
+    #[test]
+    fn single_component_query_items() {
+        fn sum(
+            bare: Query<&W<usize>>,
+            mut bare_mut: Query<&mut W<u32>>,
+            tuple: Query<(&W<usize>,)>,
+        ) {
+            // A bare component yields the component itself, and a one-tuple yields a one-tuple.
+            let bare_sum: usize = bare.iter().map(|w: &W<usize>| w.0).sum();
+            let tuple_sum: usize = tuple.iter().map(|(w,): (&W<usize>,)| w.0).sum();
+            assert_eq!(bare_sum, tuple_sum);
+            for mut w in bare_mut.iter_mut() {
+                w.0 = bare_sum as u32;
+            }
+        }
+
+        let mut world = World::default();
+        world.spawn().insert(W(1usize)).insert(W(0u32));
+        world.spawn().insert(W(2usize));
+        run_system(&mut world, sum);
+        let mut query = world.query::<&W<u32>>();
+        assert_eq!(query.single(&world).0, 3);
+    }
+
+    #[test]
+    fn query_contains_filters() {
+        let mut world = World::default();
+        let entity = world.spawn().insert(A).insert(C).id();
+        let missing_data = world.spawn().insert(C).id();
+
+        type Filter = (With<C>, Without<D>);
+        let mut system_state: SystemState<Query<&A, Filter>> = SystemState::new(&mut world);
+        {
+            let query = system_state.get(&world);
+            assert!(query.contains(entity));
+            assert!(!query.contains(missing_data));
+        }
+
+        world.entity_mut(entity).insert(D);
+        let query = system_state.get(&world);
+        assert!(!query.contains(entity));
+
+        let mut changed: SystemState<Query<(), Changed<A>>> = SystemState::new(&mut world);
+        assert!(changed.get(&world).contains(entity));
+        assert!(!changed.get(&world).contains(entity));
+    }
+
+    #[test]
+    fn query_batches_sum_in_scope() {
+        use bevy_tasks::{ComputeTaskPool, TaskPool};
+
+        let mut world = World::default();
+        for i in 0..1000usize {
+            let mut entity = world.spawn();
+            entity.insert(W(i));
+            if i % 3 == 0 {
+                entity.insert(A);
+            }
+        }
+        world.spawn().insert(B);
+
+        fn sum(query: Query<&W<usize>>, mut total: ResMut<usize>) {
+            let batches = query.batches(64);
+            // Each archetype is split on its own: 334 and 666 entities.
+            assert_eq!(batches.len(), 6 + 11);
+            assert!(batches.iter().all(|batch| batch.entities().len() <= 64));
+            let sums = ComputeTaskPool::get().scope(|scope| {
+                for batch in batches {
+                    scope.spawn(async move { batch.iter().map(|w| w.0).sum::<usize>() });
+                }
+            });
+            *total = sums.into_iter().sum();
+        }
+
+        ComputeTaskPool::init(TaskPool::default);
+        world.insert_resource(0usize);
+        run_system(&mut world, sum);
+        assert_eq!(*world.resource::<usize>(), (0..1000).sum());
+
+        // Non-archetypal filters are applied within each batch.
+        let mut changed = SystemState::<Query<&W<usize>, Changed<W<usize>>>>::new(&mut world);
+        changed.get(&world);
+        for (i, mut w) in world
+            .query::<&mut W<usize>>()
+            .iter_mut(&mut world)
+            .enumerate()
+        {
+            if i % 100 == 0 {
+                w.0 += 1;
+            }
+        }
+        let query = changed.get(&world);
+        let changed_count = query
+            .batches(7)
+            .iter()
+            .map(|batch| batch.iter().count())
+            .sum::<usize>();
+        assert_eq!(changed_count, 10);
+    }
+
+    #[test]
+    fn query_nth() {
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct Sparse;
+
+        fn assert_nth(nth: impl Fn(usize) -> Option<usize>, expected: Vec<usize>) {
+            let results = (0..=expected.len()).map(nth).collect::<Vec<_>>();
+            let expected = expected.into_iter().map(Some).chain([None]);
+            assert_eq!(results, expected.collect::<Vec<_>>());
+        }
+
+        let mut world = World::default();
+        // Spread the results over several tables and archetypes.
+        for i in 0..12usize {
+            let mut entity = world.spawn();
+            entity.insert(W(i));
+            match i % 3 {
+                1 => entity.insert(A),
+                2 => entity.insert(B),
+                _ => &mut entity,
+            };
+            if i % 2 == 0 {
+                entity.insert(Sparse);
+            }
+        }
+        world.spawn().insert(A);
+
+        let mut dense = SystemState::<Query<&mut W<usize>>>::new(&mut world);
+        let mut with = SystemState::<Query<&W<usize>, With<A>>>::new(&mut world);
+        let mut sparse = SystemState::<Query<(&W<usize>, Option<&Sparse>)>>::new(&mut world);
+        let mut changed = SystemState::<Query<&W<usize>, Changed<W<usize>>>>::new(&mut world);
+        changed.get(&world);
+
+        let query = dense.get_mut(&mut world);
+        assert_nth(
+            |n| query.nth(n).map(|w| w.0),
+            query.iter().map(|w| w.0).collect(),
+        );
+        assert_eq!(query.iter().count(), 12);
+        let query = with.get(&world);
+        assert_nth(|n| query.nth(n).map(|w| w.0), vec![1, 4, 7, 10]);
+        let query = sparse.get(&world);
+        let expected = query.iter().map(|(w, _)| w.0).collect();
+        assert_nth(|n| query.nth(n).map(|(w, _)| w.0), expected);
+
+        let mut query = dense.get_mut(&mut world);
+        let fifth = query.iter().nth(4).unwrap().0;
+        query.nth_mut(4).unwrap().0 += 100;
+        query.nth_mut(7).unwrap().0 += 100;
+        let query = changed.get(&world);
+        // Per-entity filters can't skip archetypes, and still give the same results.
+        let expected = query.iter().map(|w| w.0).collect::<Vec<_>>();
+        assert!(expected.contains(&(fifth + 100)));
+        assert_eq!(expected.len(), 2);
+        assert_nth(|n| query.nth(n).map(|w| w.0), expected);
+    }
+
+    #[test]
+    fn query_iter_many_in_order() {
+        let mut world = World::default();
+        let entities = world
+            .spawn_batch((0..4usize).map(|i| (W(i),)))
+            .collect::<Vec<_>>();
+        let missing = world.spawn().id();
+        world.despawn(missing);
+        let unmatched = world.spawn().insert(A).id();
+
+        let order = vec![
+            entities[2],
+            missing,
+            entities[0],
+            unmatched,
+            entities[3],
+            entities[0],
+        ];
+        let mut system_state: SystemState<Query<&mut W<usize>>> = SystemState::new(&mut world);
+        let mut query = system_state.get_mut(&mut world);
+        assert_eq!(
+            query.iter_many(&order).map(|w| w.0).collect::<Vec<_>>(),
+            vec![2, 0, 3, 0]
+        );
+        // Duplicates are skipped when iterating mutably.
+        for mut w in query.iter_many_mut(&order) {
+            w.0 += 10;
+        }
+        assert_eq!(
+            query.iter().map(|w| w.0).collect::<Vec<_>>(),
+            vec![10, 1, 12, 13]
+        );
+    }
+
+    #[test]
+    fn query_with_iter_scope() {
+        let mut world = World::default();
+        let entities = world
+            .spawn_batch((0..4usize).map(|i| (W(i),)))
+            .collect::<Vec<_>>();
+
+        let mut system_state: SystemState<Query<(Entity, &mut W<usize>)>> =
+            SystemState::new(&mut world);
+        let mut query = system_state.get_mut(&mut world);
+        let odd = query.with_iter_mut(|iter| {
+            iter.filter(|(_, w)| w.0 % 2 == 1)
+                .map(|(entity, mut w)| {
+                    w.0 *= 10;
+                    entity
+                })
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(odd, vec![entities[1], entities[3]]);
+
+        // The iterator's borrow has ended, so the query can be used for lookups again.
+        assert_eq!(query.get(odd[1]).unwrap().1 .0, 30);
+        let sum = query.with_iter(|iter| iter.map(|(_, w)| w.0).sum::<usize>());
+        assert_eq!(sum, 42);
+        assert_eq!(query.get_mut(entities[2]).unwrap().1 .0, 2);
+    }
+
+    #[test]
+    fn query_extract() {
+        #[derive(Component, Clone, Debug, PartialEq)]
+        struct Label(String);
+
+        let mut world = World::default();
+        let a = world.spawn().insert(Label("a".to_string())).insert(A).id();
+        let b = world.spawn().insert(Label("b".to_string())).id();
+        world.spawn().insert(A);
+
+        let mut system_state: SystemState<Query<(Entity, &Label)>> = SystemState::new(&mut world);
+        let mut extracted = system_state.get(&world).extract();
+        drop(world);
+
+        // The extracted data is owned, so it can outlive the world and move to another thread.
+        let handle = std::thread::spawn(move || {
+            extracted.sort_by_key(|(entity, _)| *entity);
+            extracted
+        });
+        assert_eq!(
+            handle.join().unwrap(),
+            vec![(a, Label("a".to_string())), (b, Label("b".to_string()))]
+        );
+    }
+
+    #[test]
+    fn query_collect_into() {
+        #[derive(Component, Clone, Debug, PartialEq)]
+        struct Depth(usize);
+
+        struct Capacities(Vec<usize>);
+
+        fn collect(
+            query: Query<(Entity, &Depth)>,
+            mut buf: Local<Vec<(Entity, Depth)>>,
+            mut capacities: ResMut<Capacities>,
+        ) {
+            query.collect_into(&mut buf);
+            assert_eq!(buf.len(), 3);
+            assert_eq!(
+                buf.iter().map(|(_, depth)| depth.0).sum::<usize>(),
+                query.iter().map(|(_, depth)| depth.0).sum::<usize>()
+            );
+            capacities.0.push(buf.capacity());
+        }
+
+        let mut world = World::default();
+        world.insert_resource(Capacities(Vec::new()));
+        for depth in 0..3 {
+            world.spawn().insert(Depth(depth));
+        }
+
+        let mut stage = SystemStage::single_threaded().with_system(collect);
+        for depth in 0..4 {
+            stage.run(&mut world);
+            // Results change from frame to frame, but their count doesn't.
+            for mut d in world.query::<&mut Depth>().iter_mut(&mut world) {
+                d.0 += depth;
+            }
+        }
+
+        // The buffer only allocates on the first frame.
+        let capacities = &world.resource::<Capacities>().0;
+        assert_eq!(capacities.len(), 4);
+        assert!(capacities.iter().all(|capacity| *capacity == capacities[0]));
+    }
+
     #[test]
     #[allow(clippy::too_many_arguments)]
     fn can_have_16_parameters() {
@@ -1006,4 +1668,24 @@ mod tests {
             }
         });
     }
+
+    #[test]
+    fn named_closures() {
+        let first = IntoSystem::into_system(|| {});
+        let second = IntoSystem::into_system(|| {});
+        assert_eq!(first.name(), second.name());
+
+        let first = first.named("first");
+        let second = second.named("second");
+        assert_eq!(first.name(), "first");
+        assert_eq!(second.name(), "second");
+
+        let mut world = World::new();
+        let mut stage = SystemStage::parallel()
+            .with_system(IntoSystem::into_system(|_: ResMut<usize>| {}).named("first"))
+            .with_system(IntoSystem::into_system(|_: ResMut<usize>| {}).named("second"));
+        let report = stage.ambiguity_report(&mut world).unwrap();
+        assert!(report.contains("\"first\""));
+        assert!(report.contains("\"second\""));
+    }
 }
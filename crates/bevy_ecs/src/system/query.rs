@@ -1,13 +1,14 @@
 use crate::{
-    component::Component,
-    entity::Entity,
+    component::{Component, ComponentTicks},
+    entity::{Entity, SpawnOrder},
     query::{
-        NopFetch, QueryCombinationIter, QueryEntityError, QueryFetch, QueryItem, QueryIter,
-        QueryManyIter, QuerySingleError, QueryState, ROQueryFetch, ROQueryItem, ReadOnlyWorldQuery,
-        WorldQuery,
+        ArchetypeChunk, NopFetch, QueryBatch, QueryCombinationIter, QueryEntityError, QueryFetch,
+        QueryItem, QueryIter, QueryManyIter, QuerySingleError, QueryState, ROQueryFetch,
+        ROQueryItem, ReadOnlyWorldQuery, WorldQuery,
     },
     world::{Mut, World},
 };
+use bevy_utils::HashSet;
 use std::{any::TypeId, borrow::Borrow, fmt::Debug};
 
 /// Provides scoped access to components in a [`World`].
@@ -146,6 +147,11 @@ use std::{any::TypeId, borrow::Borrow, fmt::Debug};
 /// - [`Without`](crate::query::Without)
 /// - [`Or`](crate::query::Or)
 ///
+/// Filters can also be mixed with the fetched data in the first type parameter. They then yield
+/// `()`, and only restrict the matched entities: `Query<(&ComponentA, Without<ComponentC>)>`
+/// matches the same entities as `Query<&ComponentA, Without<ComponentC>>`, but yields
+/// `(&ComponentA, ())` items.
+///
 /// ## Optional component access
 ///
 /// A component can be made optional in a query by wrapping it into an [`Option`]. In the
@@ -239,6 +245,16 @@ use std::{any::TypeId, borrow::Borrow, fmt::Debug};
 /// methods instead. Keep in mind though that they will return a [`QuerySingleError`] if the
 /// number of query results differ from being exactly one. If that's the case, use `iter.next()`
 /// (or `iter_mut.next()`) to only get the first query result.
+///
+/// # Structural changes during iteration
+///
+/// Systems with a `Query` can't change the structure of the [`World`] while they run: entities
+/// are spawned and despawned, and components inserted and removed, through
+/// [`Commands`](crate::system::Commands), which are only applied once the stage's systems
+/// finished. Query iteration is therefore stable, and never sees entities that were spawned or
+/// changed archetype while it ran. In debug builds, this rule is checked, and a structural change
+/// made immediately by a system without exclusive world access, which only unsound code can
+/// do, panics with the name of the system.
 pub struct Query<'world, 'state, Q: WorldQuery, F: WorldQuery = ()> {
     pub(crate) world: &'world World,
     pub(crate) state: &'state QueryState<Q, F>,
@@ -331,6 +347,75 @@ impl<'w, 's, Q: WorldQuery, F: WorldQuery> Query<'w, 's, Q, F> {
         }
     }
 
+    /// Calls `f` with an [`Iterator`] over the query results, and returns its result.
+    ///
+    /// The iterator and everything it yields only live until `f` returns, so the query can be
+    /// used for lookups such as [`Query::get`] right after the scope, without having to think
+    /// about when the iterator's borrow ends.
+    ///
+    /// See [`Self::with_iter_mut`] for queries that contain at least one mutable component.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Height(f32);
+    /// fn tallest_system(query: Query<(Entity, &Height)>) {
+    ///     let tallest = query.with_iter(|iter| {
+    ///         iter.max_by(|(_, a), (_, b)| a.0.total_cmp(&b.0))
+    ///             .map(|(entity, _)| entity)
+    ///     });
+    ///     if let Some(entity) = tallest {
+    ///         println!("{} is the tallest", query.get(entity).unwrap().1 .0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(tallest_system);
+    /// ```
+    #[inline]
+    pub fn with_iter<R>(
+        &self,
+        f: impl for<'a> FnOnce(QueryIter<'a, 's, Q, ROQueryFetch<'a, Q>, F>) -> R,
+    ) -> R {
+        f(self.iter())
+    }
+
+    /// Calls `f` with an [`Iterator`] over the query results, and returns its result.
+    ///
+    /// The mutable borrow of the query is released as soon as `f` returns. See
+    /// [`Self::with_iter`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Health(u32);
+    /// fn heal_system(mut query: Query<(Entity, &mut Health)>) {
+    ///     let healed: Vec<Entity> = query.with_iter_mut(|iter| {
+    ///         iter.filter(|(_, health)| health.0 < 10)
+    ///             .map(|(entity, mut health)| {
+    ///                 health.0 += 1;
+    ///                 entity
+    ///             })
+    ///             .collect()
+    ///     });
+    ///     for entity in healed {
+    ///         println!("healed to {}", query.get(entity).unwrap().1 .0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(heal_system);
+    /// ```
+    #[inline]
+    pub fn with_iter_mut<R>(
+        &mut self,
+        f: impl for<'a> FnOnce(QueryIter<'a, 'a, Q, QueryFetch<'a, Q>, F>) -> R,
+    ) -> R {
+        f(self.iter_mut())
+    }
+
     /// Returns an [`Iterator`] over all possible combinations of `K` query results without repetition.
     /// This can only return immutable data
     ///
@@ -566,6 +651,75 @@ impl<'w, 's, Q: WorldQuery, F: WorldQuery> Query<'w, 's, Q, F> {
         };
     }
 
+    /// Runs `f` on each query result, along with `ctx`, like [`Self::for_each_mut`].
+    ///
+    /// This is meant for data shared by every query result, such as a resource: it is fetched
+    /// once by the system and passed to `f` as-is, which makes it clear that the loop doesn't
+    /// look it up again for each entity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Velocity { x: f32, y: f32, z: f32 }
+    /// struct Gravity(f32);
+    ///
+    /// fn gravity_system(mut query: Query<&mut Velocity>, gravity: Res<Gravity>) {
+    ///     query.for_each_with(&*gravity, |gravity, mut velocity| {
+    ///         velocity.y -= gravity.0 / 60.0;
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(gravity_system);
+    /// ```
+    #[inline]
+    pub fn for_each_with<'a, C: ?Sized>(
+        &'a mut self,
+        ctx: &C,
+        mut f: impl FnMut(&C, QueryItem<'a, Q>),
+    ) {
+        self.for_each_mut(|item| f(ctx, item));
+    }
+
+    /// Runs `f` once for each archetype matched by the query, with an [`ArchetypeChunk`] that
+    /// iterates over the query results of that archetype.
+    ///
+    /// Every matched entity is yielded by exactly one chunk, and empty archetypes are skipped.
+    /// This is the building block for custom iteration strategies, such as handling each
+    /// archetype as a separate batch of work.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Velocity { x: f32, y: f32, z: f32 }
+    /// fn gravity_system(mut query: Query<&mut Velocity>) {
+    ///     query.for_each_archetype(|chunk| {
+    ///         println!("{} entities in archetype {:?}", chunk.entities().len(), chunk.archetype_id());
+    ///         for mut velocity in chunk {
+    ///             velocity.y -= 9.8 / 60.0;
+    ///         }
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(gravity_system);
+    /// ```
+    #[inline]
+    pub fn for_each_archetype<'a>(&'a mut self, f: impl FnMut(ArchetypeChunk<'a, Q, F>)) {
+        // SAFETY: system runs without conflicts with other systems. same-system queries have runtime
+        // borrow checks when they conflict
+        unsafe {
+            self.state.for_each_archetype_unchecked_manual(
+                self.world,
+                f,
+                self.last_change_tick,
+                self.change_tick,
+            );
+        };
+    }
+
     /// Runs `f` on each query result in parallel using the [`World`]'s [`ComputeTaskPool`].
     ///
     /// This can only be called for immutable data, see [`Self::par_for_each_mut`] for
@@ -639,6 +793,69 @@ impl<'w, 's, Q: WorldQuery, F: WorldQuery> Query<'w, 's, Q, F> {
         };
     }
 
+    /// Runs `f` on each unordered pair of query results in parallel using the [`World`]'s
+    /// [`ComputeTaskPool`], with mutable access to both results of the pair.
+    ///
+    /// This is the parallel counterpart of [`Query::iter_combinations_mut`] for pairs, which is
+    /// useful for pairwise interactions such as collisions or gravity.
+    ///
+    /// No entity can be accessed by two threads at once, so the pairs are scheduled as a
+    /// round-robin tournament. With `n` matched entities, there are `n - 1` rounds (or `n`
+    /// rounds if `n` is odd), and each round pairs every entity with at most one other, so the
+    /// pairs of a round are disjoint. Each unordered pair is in exactly one round, in an
+    /// unspecified order. The pairs of a round are split in batches of `batch_size` that run in
+    /// parallel, and the next round starts once they all finished.
+    ///
+    /// Each round waits for its slowest batch, and fetches each result by entity, so this is
+    /// slower than [`Query::iter_combinations_mut`] unless the work done per pair is significant.
+    /// With few entities, a round has few pairs and runs mostly on one thread.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Body {
+    ///     position: f32,
+    ///     force: f32,
+    /// }
+    ///
+    /// fn attract(mut bodies: Query<&mut Body>) {
+    ///     bodies.par_iter_combinations_mut(16, |[mut a, mut b]| {
+    ///         let force = (b.position - a.position).signum();
+    ///         a.force += force;
+    ///         b.force -= force;
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(attract);
+    /// ```
+    ///
+    /// # Panics
+    /// The [`ComputeTaskPool`] is not initialized. If using this from a query that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    ///
+    /// [`ComputeTaskPool`]: bevy_tasks::prelude::ComputeTaskPool
+    #[inline]
+    pub fn par_iter_combinations_mut<
+        FN: for<'x> Fn([QueryItem<'x, Q>; 2]) + Send + Sync + Clone,
+    >(
+        &mut self,
+        batch_size: usize,
+        f: FN,
+    ) {
+        // SAFETY: system runs without conflicts with other systems. same-system queries have runtime
+        // borrow checks when they conflict
+        unsafe {
+            self.state.par_iter_combinations_unchecked_manual::<FN>(
+                self.world,
+                batch_size,
+                f,
+                self.last_change_tick,
+                self.change_tick,
+            );
+        };
+    }
+
     /// Calls a closure on each result of [`Query`] where the entities match.
     /// # Examples
     ///
@@ -688,6 +905,57 @@ impl<'w, 's, Q: WorldQuery, F: WorldQuery> Query<'w, 's, Q, F> {
         };
     }
 
+    /// Returns an [`Iterator`] over the query results for the given list of entities, in the
+    /// order of the list.
+    ///
+    /// Entities that don't exist or don't match the query are skipped. Since mutable query
+    /// results must not alias, an entity that appears several times in the list is only yielded
+    /// the first time. To detect duplicates, the list is first collected into a buffer; use
+    /// [`many_for_each_mut`](Self::many_for_each_mut) to avoid that allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Depth(f32);
+    ///
+    /// struct RenderOrder(Vec<Entity>);
+    ///
+    /// fn system(mut depths: Query<&mut Depth>, order: Res<RenderOrder>) {
+    ///     for (i, mut depth) in depths.iter_many_mut(&order.0).enumerate() {
+    ///         depth.0 = i as f32;
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    #[inline]
+    pub fn iter_many_mut<EntityList: IntoIterator>(
+        &mut self,
+        entities: EntityList,
+    ) -> QueryManyIter<'_, '_, Q, QueryFetch<'_, Q>, F, std::vec::IntoIter<Entity>>
+    where
+        EntityList::Item: Borrow<Entity>,
+    {
+        let mut seen = HashSet::default();
+        let entities = entities
+            .into_iter()
+            .map(|entity| *entity.borrow())
+            .filter(|entity| seen.insert(*entity))
+            .collect::<Vec<_>>();
+        // SAFETY: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict.
+        // `entities` contains no duplicates, so the results don't alias.
+        unsafe {
+            self.state.iter_many_unchecked_manual(
+                entities,
+                self.world,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
     /// Returns the query result for the given [`Entity`].
     ///
     /// In case of a nonexisting entity or mismatched component, a [`QueryEntityError`] is
@@ -835,6 +1103,67 @@ impl<'w, 's, Q: WorldQuery, F: WorldQuery> Query<'w, 's, Q, F> {
         }
     }
 
+    /// Returns the read-only query result at index `n` in iteration order, or [`None`] if the
+    /// query has `n` results or fewer.
+    ///
+    /// This is equivalent to `query.iter().nth(n)`, but faster: unless the filter `F` checks
+    /// entities individually, such as [`Changed`](crate::query::Changed) or
+    /// [`Added`](crate::query::Added), tables or archetypes before the result are skipped by
+    /// their length, without fetching their items.
+    ///
+    /// The index follows the order of archetypes or tables, which isn't stable: spawning and
+    /// despawning entities, or inserting and removing components, can move every entity to
+    /// another index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Name(String);
+    /// #
+    /// fn show_fifth(query: Query<&Name>) {
+    ///     if let Some(name) = query.nth(4) {
+    ///         println!("The fifth entity is {}", name.0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(show_fifth);
+    /// ```
+    ///
+    /// See [`Query::nth_mut`] for queries that contain at least one mutable component.
+    #[inline]
+    pub fn nth(&self, n: usize) -> Option<ROQueryItem<'_, Q>> {
+        // SAFETY: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state.nth_unchecked_manual::<ROQueryFetch<Q>>(
+                self.world,
+                n,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Returns the query result at index `n` in iteration order, or [`None`] if the query has
+    /// `n` results or fewer.
+    ///
+    /// See [`Query::nth`] for how the result is found, and why its index isn't stable.
+    #[inline]
+    pub fn nth_mut(&mut self, n: usize) -> Option<QueryItem<'_, Q>> {
+        // SAFETY: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state.nth_unchecked_manual::<QueryFetch<Q>>(
+                self.world,
+                n,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
     /// Returns the query results for the given array of [`Entity`].
     ///
     /// In case of a nonexisting entity, duplicate entities or mismatched component, a [`QueryEntityError`] is
@@ -982,6 +1311,62 @@ impl<'w, 's, Q: WorldQuery, F: WorldQuery> Query<'w, 's, Q, F> {
         }
     }
 
+    /// Returns the [`ComponentTicks`] of the [`Entity`]'s [`Component`] of the given type,
+    /// recording when it was added and when it was last changed.
+    ///
+    /// In case of a nonexisting entity or mismatched component, a [`QueryComponentError`] is
+    /// returned instead.
+    ///
+    /// # Example
+    ///
+    /// Here, `get_change_ticks` is used to sort the entities by how recently their `Score`
+    /// was modified.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Score(u32);
+    /// #
+    /// fn leaderboard_system(query: Query<(Entity, &Score)>) {
+    ///     let mut entities: Vec<Entity> = query.iter().map(|(entity, _)| entity).collect();
+    ///     entities.sort_by_key(|entity| {
+    ///         let ticks = query.get_change_ticks::<Score>(*entity).unwrap();
+    ///         std::cmp::Reverse(ticks.changed_tick())
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(leaderboard_system);
+    /// ```
+    #[inline]
+    pub fn get_change_ticks<T: Component>(
+        &self,
+        entity: Entity,
+    ) -> Result<&ComponentTicks, QueryComponentError> {
+        let world = self.world;
+        let entity_ref = world
+            .get_entity(entity)
+            .ok_or(QueryComponentError::NoSuchEntity)?;
+        let component_id = world
+            .components()
+            .get_id(TypeId::of::<T>())
+            .ok_or(QueryComponentError::MissingComponent)?;
+        let archetype_component = entity_ref
+            .archetype()
+            .get_archetype_component_id(component_id)
+            .ok_or(QueryComponentError::MissingComponent)?;
+        if self
+            .state
+            .archetype_component_access
+            .has_read(archetype_component)
+        {
+            entity_ref
+                .get_change_ticks::<T>()
+                .ok_or(QueryComponentError::MissingComponent)
+        } else {
+            Err(QueryComponentError::MissingReadAccess)
+        }
+    }
+
     /// Returns a mutable reference to the [`Entity`]'s [`Component`] of the given type.
     ///
     /// In case of a nonexisting entity or mismatched component, a [`QueryEntityError`] is
@@ -1024,6 +1409,38 @@ impl<'w, 's, Q: WorldQuery, F: WorldQuery> Query<'w, 's, Q, F> {
     ///
     /// This function makes it possible to violate Rust's aliasing guarantees. You must make sure
     /// this call does not result in multiple mutable references to the same component
+    ///
+    /// # Example
+    ///
+    /// Here, `get_component_unchecked_mut` is used to mutate both entities of every pair at once,
+    /// which is sound because the two entities of a pair are always distinct.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Position(f32);
+    /// #
+    /// fn separation_system(query: Query<(Entity, &mut Position)>) {
+    ///     let entities: Vec<Entity> = query.iter().map(|(entity, _)| entity).collect();
+    ///     for (i, &a) in entities.iter().enumerate() {
+    ///         for &b in &entities[i + 1..] {
+    ///             // SAFETY: `a != b`, and no other reference to a `Position` of this query is alive
+    ///             let (mut position_a, mut position_b) = unsafe {
+    ///                 (
+    ///                     query.get_component_unchecked_mut::<Position>(a).unwrap(),
+    ///                     query.get_component_unchecked_mut::<Position>(b).unwrap(),
+    ///                 )
+    ///             };
+    ///             if (position_a.0 - position_b.0).abs() < 1.0 {
+    ///                 position_a.0 -= 0.5;
+    ///                 position_b.0 += 0.5;
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(separation_system);
+    /// ```
     #[inline]
     pub unsafe fn get_component_unchecked_mut<T: Component>(
         &self,
@@ -1223,6 +1640,13 @@ impl<'w, 's, Q: WorldQuery, F: WorldQuery> Query<'w, 's, Q, F> {
 
     /// Returns `true` if the given [`Entity`] matches the query.
     ///
+    /// Like [`World::is_alive`], this checks the generation of `entity`, so it returns `false`
+    /// for an [`Entity`] whose id has been reused by a newer entity.
+    ///
+    /// Nothing is fetched, and both the archetype filters, like [`With`](crate::query::With) and
+    /// [`Without`](crate::query::Without), and the change filters, like
+    /// [`Changed`](crate::query::Changed), are taken into account.
+    ///
     /// # Example
     ///
     /// ```
@@ -1390,4 +1814,154 @@ impl<'w, 's, Q: ReadOnlyWorldQuery, F: WorldQuery> Query<'w, 's, Q, F> {
                 .iter_unchecked_manual(self.world, self.last_change_tick, self.change_tick)
         }
     }
+
+    /// Splits the matched entities into [`QueryBatch`]es of at most `batch_size` entities, each
+    /// within a single archetype, with the actual "inner" world lifetime.
+    ///
+    /// Unlike [`par_for_each`](Self::par_for_each), this leaves spawning the tasks to the caller,
+    /// which chooses how to partition the work and how to combine the results. Batches borrow the
+    /// world immutably, so they can be moved into the tasks of a task pool
+    /// [`scope`](bevy_tasks::TaskPool::scope), which joins them before the borrow ends.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_tasks::ComputeTaskPool;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Mass(f32);
+    /// fn total_mass(query: Query<&Mass>) {
+    ///     let sums = ComputeTaskPool::get().scope(|scope| {
+    ///         for batch in query.batches(1024) {
+    ///             scope.spawn(async move { batch.iter().map(|mass| mass.0).sum::<f32>() });
+    ///         }
+    ///     });
+    ///     println!("total mass: {}", sums.iter().sum::<f32>());
+    /// }
+    /// # bevy_ecs::system::assert_is_system(total_mass);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is `0`.
+    pub fn batches(&self, batch_size: usize) -> Vec<QueryBatch<'w, 's, Q, F>>
+    where
+        F: ReadOnlyWorldQuery,
+    {
+        assert!(batch_size > 0, "The batch size of a query must not be 0.");
+        let archetypes = self.world.archetypes();
+        let mut batches = Vec::new();
+        for archetype_id in &self.state.matched_archetype_ids {
+            let len = archetypes[*archetype_id].len();
+            for start in (0..len).step_by(batch_size) {
+                let range = start..len.min(start + batch_size);
+                // SAFETY: the archetype is matched by the query and the range is in bounds of its
+                // entities.
+                batches.push(unsafe {
+                    QueryBatch::new(
+                        self.world,
+                        self.state,
+                        *archetype_id,
+                        range,
+                        self.last_change_tick,
+                        self.change_tick,
+                    )
+                });
+            }
+        }
+        batches
+    }
+}
+
+impl<'w, 's, 'a, T: Component + Clone, F: WorldQuery> Query<'w, 's, (Entity, &'a T), F> {
+    /// Clones the `T` component of every matched entity into an owned [`Vec`], alongside the
+    /// entity it belongs to.
+    ///
+    /// The returned data does not borrow from the [`World`], so it can be stored past the end
+    /// of the system or sent to another thread, for example to hand a snapshot to a render thread.
+    ///
+    /// Every component is cloned, which can be expensive for large components or many entities.
+    /// Prefer [`iter`](Self::iter) when the data does not need to outlive the borrow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component, Clone)]
+    /// struct Sprite {
+    ///     index: usize,
+    /// }
+    ///
+    /// struct ExtractedSprites(Vec<(Entity, Sprite)>);
+    ///
+    /// fn extract_sprites(sprites: Query<(Entity, &Sprite)>, mut extracted: ResMut<ExtractedSprites>) {
+    ///     extracted.0 = sprites.extract();
+    /// }
+    /// # bevy_ecs::system::assert_is_system(extract_sprites);
+    /// ```
+    pub fn extract(&self) -> Vec<(Entity, T)> {
+        self.iter()
+            .map(|(entity, component)| (entity, component.clone()))
+            .collect()
+    }
+
+    /// Clears `buf`, then fills it with a clone of the `T` component of every matched entity,
+    /// alongside the entity it belongs to.
+    ///
+    /// This is the same as [`extract`](Self::extract), but reuses the allocation of `buf`. Keeping
+    /// the buffer around, for example in a [`Local`](crate::system::Local), avoids allocating
+    /// every frame once it is large enough.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component, Clone)]
+    /// struct Depth(f32);
+    ///
+    /// fn sort_by_depth(query: Query<(Entity, &Depth)>, mut sorted: Local<Vec<(Entity, Depth)>>) {
+    ///     query.collect_into(&mut sorted);
+    ///     sorted.sort_by(|(_, a), (_, b)| a.0.total_cmp(&b.0));
+    ///     // ...
+    /// }
+    /// # bevy_ecs::system::assert_is_system(sort_by_depth);
+    /// ```
+    pub fn collect_into(&self, buf: &mut Vec<(Entity, T)>) {
+        buf.clear();
+        buf.extend(
+            self.iter()
+                .map(|(entity, component)| (entity, component.clone())),
+        );
+    }
+}
+
+impl<'w, 's, 'a, Q: WorldQuery, F: WorldQuery> Query<'w, 's, (&'a SpawnOrder, Q), F> {
+    /// Returns the query results sorted by their [`SpawnOrder`], from the earliest spawned
+    /// entity to the latest.
+    ///
+    /// The results are collected and sorted first, so this allocates and costs `O(n log n)` for
+    /// `n` results, on top of the iteration itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::{entity::SpawnOrder, prelude::*};
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Enemy { name: String }
+    /// fn enemy_turns_system(query: Query<(&SpawnOrder, &Enemy)>) {
+    ///     for (_, enemy) in query.iter_spawn_order() {
+    ///         println!("{} takes its turn", enemy.name);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(enemy_turns_system);
+    /// ```
+    pub fn iter_spawn_order(&self) -> std::vec::IntoIter<(&SpawnOrder, ROQueryItem<'_, Q>)> {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_by_key(|(order, _)| **order);
+        items.into_iter()
+    }
 }
@@ -0,0 +1,133 @@
+use crate::{entity::Entity, query::WorldQuery, system::Query};
+use bevy_utils::HashSet;
+
+/// Tracks which entities started or stopped matching a query between two runs of a system.
+///
+/// This generalizes [`Added`](crate::query::Added) and [`RemovedComponents`](crate::system::RemovedComponents)
+/// to arbitrary filters: an entity is reported as added when it starts matching the filter, for
+/// example because a component it requires was inserted or one it excludes was removed, and as
+/// removed when it stops matching it or is despawned. This is useful for keeping an external
+/// mirror, such as a physics world or a navmesh, in sync with a query.
+///
+/// Store it in a [`Local`](crate::system::Local), so each system tracks its own query, and call
+/// [`update`](Self::update) once per run with a query for [`Entity`].
+///
+/// `QueryDiff` keeps two sets of entities as large as the query, so it costs memory
+/// proportional to the number of matched entities, and each update iterates the whole query.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::QueryDiff};
+/// # #[derive(Component)]
+/// # struct Obstacle;
+/// fn sync_navmesh(
+///     mut diff: Local<QueryDiff>,
+///     obstacles: Query<Entity, With<Obstacle>>,
+/// ) {
+///     let (added, removed) = diff.update(&obstacles);
+///     for entity in added {
+///         println!("add {:?} to the navmesh", entity);
+///     }
+///     for entity in removed {
+///         println!("remove {:?} from the navmesh", entity);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(sync_navmesh);
+/// ```
+#[derive(Debug, Default)]
+pub struct QueryDiff {
+    previous: HashSet<Entity>,
+    current: HashSet<Entity>,
+}
+
+impl QueryDiff {
+    /// Records the entities currently matched by `query`, and returns the entities that started
+    /// matching it and the entities that stopped matching it since the previous call.
+    ///
+    /// On the first call, every matched entity is reported as added.
+    pub fn update<F: WorldQuery>(
+        &mut self,
+        query: &Query<Entity, F>,
+    ) -> (Vec<Entity>, Vec<Entity>) {
+        self.current.clear();
+        self.current.extend(query.iter());
+        let added = self.current.difference(&self.previous).copied().collect();
+        let removed = self.previous.difference(&self.current).copied().collect();
+        std::mem::swap(&mut self.previous, &mut self.current);
+        (added, removed)
+    }
+
+    /// Returns the entities that matched the query the last time [`update`](Self::update) was
+    /// called.
+    pub fn matched(&self) -> &HashSet<Entity> {
+        &self.previous
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryDiff;
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        entity::Entity,
+        query::{With, Without},
+        system::{IntoSystem, Local, Query, ResMut, System},
+        world::World,
+    };
+
+    #[derive(Component)]
+    struct Tagged;
+
+    #[derive(Component)]
+    struct Hidden;
+
+    #[derive(Default)]
+    struct Diffs(Vec<(Vec<Entity>, Vec<Entity>)>);
+
+    fn record_diff(
+        mut diff: Local<QueryDiff>,
+        query: Query<Entity, (With<Tagged>, Without<Hidden>)>,
+        mut diffs: ResMut<Diffs>,
+    ) {
+        let (mut added, mut removed) = diff.update(&query);
+        added.sort();
+        removed.sort();
+        diffs.0.push((added, removed));
+    }
+
+    #[test]
+    fn query_diff() {
+        let mut world = World::new();
+        world.init_resource::<Diffs>();
+        let a = world.spawn().insert(Tagged).id();
+        let b = world.spawn().insert(Tagged).id();
+        let c = world.spawn().id();
+
+        let mut system = IntoSystem::into_system(record_diff);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+
+        world.entity_mut(a).remove::<Tagged>();
+        world.entity_mut(b).insert(Hidden);
+        world.entity_mut(c).insert(Tagged);
+        system.run((), &mut world);
+
+        system.run((), &mut world);
+
+        world.entity_mut(b).remove::<Hidden>();
+        world.despawn(c);
+        system.run((), &mut world);
+
+        assert_eq!(
+            world.resource::<Diffs>().0,
+            vec![
+                (vec![a, b], vec![]),
+                (vec![c], vec![a, b]),
+                (vec![], vec![]),
+                (vec![b], vec![c]),
+            ]
+        );
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
     query::{
         Access, FilteredAccess, FilteredAccessSet, QueryState, ReadOnlyWorldQuery, WorldQuery,
     },
-    system::{CommandQueue, Commands, Query, SystemMeta},
+    system::{CommandBudget, CommandQueue, Commands, Query, SystemMeta},
     world::{FromWorld, World},
 };
 pub use bevy_ecs_macros::SystemParam;
@@ -88,6 +88,11 @@ use std::{
 ///
 /// This will most commonly occur when working with `SystemParam`s generically, as the requirement
 /// has not been proven to the compiler.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be used as a system parameter",
+    label = "invalid system parameter",
+    note = "resources are requested with `Res<T>` or `ResMut<T>`, and components with `Query<&T>` or `Query<&mut T>`"
+)]
 pub trait SystemParam: Sized {
     type Fetch: for<'w, 's> SystemParamFetch<'w, 's>;
 }
@@ -217,6 +222,16 @@ pub struct ParamSetState<T: for<'w, 's> SystemParamFetch<'w, 's>>(T);
 
 impl_param_set!();
 
+/// A type that can be stored in a [`World`] as a resource.
+///
+/// Each resource is stored once, in place, inside the [`World`]. [`Res`] and [`ResMut`] borrow
+/// that value for the duration of a system run; they never clone it, so resources don't need to
+/// implement [`Clone`], and large resources such as asset stores cost nothing extra to access.
+/// As with any borrow, the scheduler makes sure a [`ResMut`] is never alive at the same time as
+/// another borrow of the same resource.
+///
+/// To share data beyond the lifetime of a borrow, store a cheap handle in the resource (for
+/// example an [`Arc`](std::sync::Arc)) and clone the handle explicitly.
 pub trait Resource: Send + Sync + 'static {}
 
 impl<T> Resource for T where T: Send + Sync + 'static {}
@@ -263,6 +278,16 @@ impl<'w, T: Resource> Res<'w, T> {
             .is_changed(self.last_change_tick, self.change_tick)
     }
 
+    /// Returns the change tick recording the last time the resource was added or mutably
+    /// dereferenced.
+    ///
+    /// Unlike [`is_changed`](Res::is_changed), this can be compared against a tick stored by the
+    /// system itself, for example to rebuild a cache derived from several resources only when
+    /// one of them changed since the cache was built.
+    pub fn last_changed(&self) -> u32 {
+        self.ticks.changed
+    }
+
     pub fn into_inner(self) -> &'w T {
         self.value
     }
@@ -467,6 +492,7 @@ impl<'w, 's, T: Resource> SystemParamFetch<'w, 's> for ResMutState<T> {
             value: value.value,
             ticks: Ticks {
                 component_ticks: value.ticks.component_ticks,
+                column_tick: None,
                 last_change_tick: system_meta.last_change_tick,
                 change_tick,
             },
@@ -507,6 +533,7 @@ impl<'w, 's, T: Resource> SystemParamFetch<'w, 's> for OptionResMutState<T> {
                 value: value.value,
                 ticks: Ticks {
                     component_ticks: value.ticks.component_ticks,
+                    column_tick: None,
                     last_change_tick: system_meta.last_change_tick,
                     change_tick,
                 },
@@ -528,7 +555,11 @@ unsafe impl SystemParamState for CommandQueue {
     }
 
     fn apply(&mut self, world: &mut World) {
-        self.apply(world);
+        if world.contains_resource::<CommandBudget>() {
+            CommandBudget::apply_queue(world, self);
+        } else {
+            self.apply(world);
+        }
     }
 }
 
@@ -699,6 +730,120 @@ impl<'w, 's, T: Resource + FromWorld> SystemParamFetch<'w, 's> for LocalState<T>
     }
 }
 
+/// A local [`SystemParam`] shared by every system that uses the same key type `K`.
+///
+/// Unlike [`Local`], which is unique to its system, all the `SharedLocal<K, T>`s with the same
+/// `K` and `T` refer to the same value. This lets a few related systems share state without
+/// exposing it as a resource: only the systems that know the key can reach it, so the key type
+/// is usually kept private to the module of these systems. The value is created with
+/// [`FromWorld`] when the first system using it is initialized.
+///
+/// Sharing a value has the same scheduling implications as sharing a [`ResMut`]: the systems
+/// sharing a local conflict with each other, so they never run in parallel, and the order they
+/// run in is ambiguous unless it is constrained with labels.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::SharedLocal};
+/// # let world = &mut World::default();
+/// struct Spawned;
+///
+/// fn count_enemies(mut count: SharedLocal<Spawned, usize>) {
+///     *count += 1;
+/// }
+/// fn read_count(count: SharedLocal<Spawned, usize>) -> usize {
+///     *count
+/// }
+/// let mut count_system = IntoSystem::into_system(count_enemies);
+/// let mut read_system = IntoSystem::into_system(read_count);
+/// count_system.initialize(world);
+/// read_system.initialize(world);
+///
+/// count_system.run((), world);
+/// // Unlike a `Local`, the count is shared.
+/// assert_eq!(read_system.run((), world), 1);
+/// ```
+pub struct SharedLocal<'a, K: 'static, T: Resource>(ResMut<'a, SharedLocalValue<K, T>>);
+
+impl<'a, K: 'static, T: Resource> Debug for SharedLocal<'a, K, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SharedLocal")
+            .field(&(*self.0).value)
+            .finish()
+    }
+}
+
+impl<'a, K: 'static, T: Resource> Deref for SharedLocal<'a, K, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &(*self.0).value
+    }
+}
+
+impl<'a, K: 'static, T: Resource> DerefMut for SharedLocal<'a, K, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut (*self.0).value
+    }
+}
+
+/// The resource storing the value of [`SharedLocal<K, T>`].
+#[doc(hidden)]
+pub struct SharedLocalValue<K, T> {
+    value: T,
+    marker: PhantomData<fn() -> K>,
+}
+
+/// The [`SystemParamState`] of [`SharedLocal<K, T>`].
+#[doc(hidden)]
+pub struct SharedLocalState<K, T>(ResMutState<SharedLocalValue<K, T>>);
+
+impl<'a, K: 'static, T: Resource + FromWorld> SystemParam for SharedLocal<'a, K, T> {
+    type Fetch = SharedLocalState<K, T>;
+}
+
+// SAFETY: this impl defers to `ResMutState`, which initializes
+// and validates the correct world access
+unsafe impl<K: 'static, T: Resource + FromWorld> SystemParamState for SharedLocalState<K, T> {
+    fn init(world: &mut World, system_meta: &mut SystemMeta) -> Self {
+        if !world.contains_resource::<SharedLocalValue<K, T>>() {
+            let value = T::from_world(world);
+            world.insert_resource(SharedLocalValue::<K, T> {
+                value,
+                marker: PhantomData,
+            });
+        }
+        Self(ResMutState::init(world, system_meta))
+    }
+}
+
+impl<'w, 's, K: 'static, T: Resource + FromWorld> SystemParamFetch<'w, 's>
+    for SharedLocalState<K, T>
+{
+    type Item = SharedLocal<'w, K, T>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'s mut Self,
+        system_meta: &SystemMeta,
+        world: &'w World,
+        change_tick: u32,
+    ) -> Self::Item {
+        SharedLocal(ResMutState::get_param(
+            &mut state.0,
+            system_meta,
+            world,
+            change_tick,
+        ))
+    }
+}
+
 /// A [`SystemParam`] that grants access to the entities that had their `T` [`Component`] removed.
 ///
 /// Note that this does not allow you to see which data existed before removal.
@@ -715,6 +860,11 @@ impl<'w, 's, T: Resource + FromWorld> SystemParamFetch<'w, 's> for LocalState<T>
 /// stage. As such `RemovedComponents` systems should be scheduled after the stage where
 /// removal occurs but before `CoreStage::Last`.
 ///
+/// Removals are kept until the trackers are cleared, so a system in a later stage sees the
+/// removals of the earlier stages of the same frame. Each system keeps its own cursor into the
+/// removals, and only sees the entities removed since the last time it ran: a system that runs
+/// several times in a frame sees each removal once.
+///
 /// # Examples
 ///
 /// Basic usage:
@@ -734,15 +884,15 @@ impl<'w, 's, T: Resource + FromWorld> SystemParamFetch<'w, 's> for LocalState<T>
 /// # bevy_ecs::system::assert_is_system(react_on_removal);
 /// ```
 pub struct RemovedComponents<'a, T: Component> {
-    world: &'a World,
-    component_id: ComponentId,
+    removed: &'a [Entity],
     marker: PhantomData<T>,
 }
 
 impl<'a, T: Component> RemovedComponents<'a, T> {
-    /// Returns an iterator over the entities that had their `T` [`Component`] removed.
+    /// Returns an iterator over the entities that had their `T` [`Component`] removed since the
+    /// last run of this system.
     pub fn iter(&self) -> std::iter::Cloned<std::slice::Iter<'_, Entity>> {
-        self.world.removed_with_id(self.component_id)
+        self.removed.iter().cloned()
     }
 }
 
@@ -753,6 +903,12 @@ unsafe impl<T: Component> ReadOnlySystemParamFetch for RemovedComponentsState<T>
 #[doc(hidden)]
 pub struct RemovedComponentsState<T> {
     component_id: ComponentId,
+    /// The number of removals this system has already seen.
+    cursor: usize,
+    /// The number of times the removals were cleared when `cursor` was last updated, to detect
+    /// that they have been cleared since. The world's change ticks can't tell, as exclusive
+    /// systems such as `World::clear_trackers` restore [`World::last_change_tick`] once they ran.
+    generation: u32,
     marker: PhantomData<T>,
 }
 
@@ -766,6 +922,8 @@ unsafe impl<T: Component> SystemParamState for RemovedComponentsState<T> {
     fn init(world: &mut World, _system_meta: &mut SystemMeta) -> Self {
         Self {
             component_id: world.init_component::<T>(),
+            cursor: 0,
+            generation: world.removed_components_generation,
             marker: PhantomData,
         }
     }
@@ -781,9 +939,101 @@ impl<'w, 's, T: Component> SystemParamFetch<'w, 's> for RemovedComponentsState<T
         world: &'w World,
         _change_tick: u32,
     ) -> Self::Item {
+        let removed = world
+            .removed_components
+            .get(state.component_id)
+            .map_or(&[][..], |removed| removed.as_slice());
+        if state.generation != world.removed_components_generation {
+            state.generation = world.removed_components_generation;
+            state.cursor = 0;
+        }
+        let start = state.cursor.min(removed.len());
+        state.cursor = removed.len();
         RemovedComponents {
-            world,
-            component_id: state.component_id,
+            removed: &removed[start..],
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A [`SystemParam`] that tells whether the `T` [`Resource`] was removed since the last run of
+/// this system.
+///
+/// This lets cleanup systems react to a resource disappearing, such as the active level being
+/// cleared on a transition. A resource that is inserted again is no longer reported as
+/// removed. A resource that is removed and inserted again within the same frame, as delimited by
+/// [`World::clear_trackers`], is not reported as removed nor as added to a system that runs
+/// once per frame: it keeps the added tick of the removed value, and only reads as changed.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::system::RemovedResource;
+/// #
+/// struct Level(u32);
+///
+/// fn unload_level(removed: RemovedResource<Level>) {
+///     if removed.is_removed() {
+///         println!("The level was unloaded");
+///     }
+/// }
+///
+/// # bevy_ecs::system::assert_is_system(unload_level);
+/// ```
+pub struct RemovedResource<T: Resource> {
+    removed: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T: Resource> RemovedResource<T> {
+    /// Returns `true` if the `T` [`Resource`] was removed since the last run of this system, and
+    /// wasn't inserted again since.
+    pub fn is_removed(&self) -> bool {
+        self.removed
+    }
+}
+
+// SAFETY: Only reads World resource removals
+unsafe impl<T: Resource> ReadOnlySystemParamFetch for RemovedResourceState<T> {}
+
+/// The [`SystemParamState`] of [`RemovedResource<T>`].
+#[doc(hidden)]
+pub struct RemovedResourceState<T> {
+    component_id: ComponentId,
+    marker: PhantomData<T>,
+}
+
+impl<T: Resource> SystemParam for RemovedResource<T> {
+    type Fetch = RemovedResourceState<T>;
+}
+
+// SAFETY: no resource access. resource removals can be read in parallel and are never mutably
+// borrowed during system execution
+unsafe impl<T: Resource> SystemParamState for RemovedResourceState<T> {
+    fn init(world: &mut World, _system_meta: &mut SystemMeta) -> Self {
+        Self {
+            component_id: world.initialize_resource::<T>(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'w, 's, T: Resource> SystemParamFetch<'w, 's> for RemovedResourceState<T> {
+    type Item = RemovedResource<T>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'s mut Self,
+        system_meta: &SystemMeta,
+        world: &'w World,
+        change_tick: u32,
+    ) -> Self::Item {
+        let removed = world
+            .removed_resources
+            .get(state.component_id)
+            .is_some_and(|removal| removal.is_removed(system_meta.last_change_tick, change_tick));
+        RemovedResource {
+            removed,
             marker: PhantomData,
         }
     }
@@ -1032,6 +1282,7 @@ impl<'w, 's, T: 'static> SystemParamFetch<'w, 's> for NonSendMutState<T> {
             value: column.get_data_ptr().assert_unique().deref_mut::<T>(),
             ticks: Ticks {
                 component_ticks: column.get_ticks_unchecked(0).deref_mut(),
+                column_tick: None,
                 last_change_tick: system_meta.last_change_tick,
                 change_tick,
             },
@@ -1073,6 +1324,7 @@ impl<'w, 's, T: 'static> SystemParamFetch<'w, 's> for OptionNonSendMutState<T> {
                 value: column.get_data_ptr().assert_unique().deref_mut::<T>(),
                 ticks: Ticks {
                     component_ticks: column.get_ticks_unchecked(0).deref_mut(),
+                    column_tick: None,
                     last_change_tick: system_meta.last_change_tick,
                     change_tick,
                 },
@@ -1466,8 +1718,11 @@ mod tests {
     use super::SystemParam;
     use crate::{
         self as bevy_ecs, // Necessary for the `SystemParam` Derive when used inside `bevy_ecs`.
+        component::Component,
         query::WorldQuery,
-        system::Query,
+        schedule::{Stage, SystemStage},
+        system::{Query, Res, ResMut},
+        world::World,
     };
 
     // Compile test for #2838
@@ -1480,4 +1735,39 @@ mod tests {
     > {
         _query: Query<'w, 's, Q, F>,
     }
+
+    struct Multiplier(usize);
+
+    struct Score(usize);
+
+    #[derive(Component)]
+    struct Points(usize);
+
+    #[derive(SystemParam)]
+    struct ScoreCtx<'w, 's> {
+        multiplier: Res<'w, Multiplier>,
+        score: ResMut<'w, Score>,
+        points: Query<'w, 's, &'static Points>,
+    }
+
+    #[test]
+    fn derived_param_with_three_fields() {
+        fn tally(mut ctx: ScoreCtx) {
+            let total = ctx.points.iter().map(|points| points.0).sum::<usize>();
+            ctx.score.0 += total * ctx.multiplier.0;
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Multiplier(3));
+        world.insert_resource(Score(0));
+        world.spawn().insert(Points(1));
+        world.spawn().insert(Points(2));
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(tally);
+        stage.run(&mut world);
+        assert_eq!(world.resource::<Score>().0, 9);
+        stage.run(&mut world);
+        assert_eq!(world.resource::<Score>().0, 18);
+    }
 }
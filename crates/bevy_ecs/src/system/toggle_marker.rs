@@ -0,0 +1,114 @@
+use crate::{
+    component::Component,
+    entity::Entity,
+    query::{ROQueryItem, WorldQuery},
+    system::{Commands, Query},
+};
+
+type MarkerQuery<'w, 's, M, Q> = Query<'w, 's, (Entity, Q, Option<&'static M>)>;
+
+/// Creates a system that inserts the marker component `M` on entities whose `Q` query item
+/// satisfies `predicate`, and removes it from those whose item doesn't.
+///
+/// Only transitions issue commands: entities that already have the marker and still satisfy
+/// `predicate`, or that don't have it and still don't, are left alone, so the marker keeps its
+/// change ticks and [`Added`](crate::query::Added) only matches entities that just gained it.
+/// Entities that don't match `Q` are not touched.
+///
+/// Since the marker is inserted and removed with [`Commands`], the changes are visible once the
+/// commands are applied, usually at the end of the stage.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, system::toggle_marker};
+/// #[derive(Component)]
+/// struct Distance(f32);
+///
+/// #[derive(Component, Default)]
+/// struct Visible;
+///
+/// let mut stage = SystemStage::parallel();
+/// stage.add_system(toggle_marker::<Visible, &Distance>(|distance| distance.0 < 100.0));
+/// ```
+pub fn toggle_marker<M, Q>(
+    predicate: impl Fn(ROQueryItem<'_, Q>) -> bool + Send + Sync + 'static,
+) -> impl FnMut(Commands, MarkerQuery<M, Q>) + Send + Sync + 'static
+where
+    M: Component + Default,
+    Q: WorldQuery + 'static,
+{
+    move |mut commands: Commands, query: MarkerQuery<M, Q>| {
+        for (entity, item, marker) in query.iter() {
+            match (predicate(item), marker.is_some()) {
+                (true, false) => {
+                    commands.entity(entity).insert(M::default());
+                }
+                (false, true) => {
+                    commands.entity(entity).remove::<M>();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::toggle_marker;
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        entity::Entity,
+        query::With,
+        schedule::{Stage, SystemStage},
+        world::World,
+    };
+
+    #[derive(Component)]
+    struct Distance(f32);
+
+    #[derive(Component, Default)]
+    struct Visible;
+
+    #[test]
+    fn toggle_marker_on_transitions() {
+        let mut world = World::new();
+        let near = world.spawn().insert(Distance(10.0)).id();
+        let far = world.spawn().insert(Distance(500.0)).id();
+        let unrelated = world.spawn().insert(Visible).id();
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(toggle_marker::<Visible, &Distance>(|distance| {
+            distance.0 < 100.0
+        }));
+
+        stage.run(&mut world);
+        assert!(world.get::<Visible>(near).is_some());
+        assert!(world.get::<Visible>(far).is_none());
+        assert!(world.get::<Visible>(unrelated).is_some());
+
+        // Entities that keep their membership keep the marker's change ticks.
+        world.clear_trackers();
+        stage.run(&mut world);
+        assert!(!world
+            .entity(near)
+            .get_change_ticks::<Visible>()
+            .unwrap()
+            .is_added(world.last_change_tick(), world.read_change_tick()));
+
+        world.get_mut::<Distance>(near).unwrap().0 = 200.0;
+        world.get_mut::<Distance>(far).unwrap().0 = 50.0;
+        stage.run(&mut world);
+        assert!(world.get::<Visible>(near).is_none());
+        assert!(world.get::<Visible>(far).is_some());
+        let mut visible = world
+            .query_filtered::<Entity, With<Visible>>()
+            .iter(&world)
+            .collect::<Vec<_>>();
+        visible.sort();
+        let mut expected = vec![far, unrelated];
+        expected.sort();
+        assert_eq!(visible, expected);
+    }
+}
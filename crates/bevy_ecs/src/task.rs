@@ -0,0 +1,87 @@
+//! Integration of [`bevy_tasks`] with the ECS.
+//!
+//! A [`Task`] can be added to an entity as a component, to keep track of work running on a
+//! [`TaskPool`](bevy_tasks::TaskPool) such as the [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool).
+//! The [`poll_tasks`] system replaces each completed task with its output.
+
+use crate::{
+    component::{Component, SparseStorage},
+    entity::Entity,
+    system::{Commands, Query},
+};
+use bevy_tasks::Task;
+
+// Tasks are short-lived components, which makes them a good fit for sparse set storage.
+impl<T: Send + 'static> Component for Task<T> {
+    type Storage = SparseStorage;
+}
+
+/// Polls every [`Task<T>`] component once and, for each completed task, inserts its output as a
+/// component and removes the task from the entity.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, task::poll_tasks};
+/// # use bevy_tasks::{AsyncComputeTaskPool, TaskPool};
+/// #[derive(Component)]
+/// struct Mesh(Vec<[f32; 3]>);
+///
+/// fn generate_meshes(mut commands: Commands) {
+///     let task = AsyncComputeTaskPool::init(TaskPool::default)
+///         .spawn(async { Mesh(vec![[0.0, 0.0, 0.0]]) });
+///     commands.spawn().insert(task);
+/// }
+///
+/// let mut stage = SystemStage::parallel()
+///     .with_system(generate_meshes)
+///     .with_system(poll_tasks::<Mesh>);
+/// stage.run(&mut World::new());
+/// ```
+pub fn poll_tasks<T: Component>(mut commands: Commands, mut tasks: Query<(Entity, &mut Task<T>)>) {
+    for (entity, mut task) in tasks.iter_mut() {
+        if let Some(output) = task.poll_once() {
+            commands.entity(entity).insert(output).remove::<Task<T>>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::poll_tasks;
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        schedule::{Stage, SystemStage},
+        world::World,
+    };
+    use bevy_tasks::{Task, TaskPool};
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Output(usize);
+
+    #[test]
+    fn poll_completed_tasks() {
+        let pool = TaskPool::new();
+        let (sender, receiver) = async_channel::bounded(1);
+
+        let mut world = World::new();
+        let entity = world
+            .spawn()
+            .insert(pool.spawn(async move { Output(receiver.recv().await.unwrap()) }))
+            .id();
+        let mut stage = SystemStage::parallel().with_system(poll_tasks::<Output>);
+
+        stage.run(&mut world);
+        assert!(world.get::<Task<Output>>(entity).is_some());
+        assert!(world.get::<Output>(entity).is_none());
+
+        sender.try_send(42).unwrap();
+        let mut frames = 0;
+        while world.get::<Task<Output>>(entity).is_some() {
+            frames += 1;
+            assert!(frames < 10_000, "the task never completed");
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            stage.run(&mut world);
+        }
+        assert_eq!(world.get::<Output>(entity), Some(&Output(42)));
+    }
+}
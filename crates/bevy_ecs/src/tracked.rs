@@ -0,0 +1,302 @@
+//! Components that remember their value from the previous frame.
+//!
+//! Wrap a component in [`Tracked`] and add the [`update_tracked`] system for it at the start of
+//! the frame. Systems can then read both [`Tracked::current`] and [`Tracked::previous`], which is
+//! useful for interpolation and for computing deltas.
+//!
+//! Alternatively, keep the component as it is and add the [`update_previous`] system for it, which
+//! gives each entity with the component a [`Previous<T>`] copy. Systems can then query `&mut T` to
+//! write the current value and `&Previous<T>` to read the previous one, as render extraction does.
+
+use crate::{
+    change_detection::DetectChanges,
+    component::Component,
+    entity::Entity,
+    query::Changed,
+    system::{Commands, Query},
+};
+use std::ops::Deref;
+
+/// A component holding the current value of `T` along with the value it had at the previous run
+/// of [`update_tracked::<T>`].
+///
+/// A [`Tracked<T>`] stores two copies of `T`, so it takes twice the memory of `T` itself, and
+/// [`update_tracked`] clones `T` for every entity whose [`Tracked<T>`] changed.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, tracked::{Tracked, update_tracked}};
+/// #[derive(Component, Clone, Copy)]
+/// struct Position(f32);
+///
+/// fn print_velocity(query: Query<&Tracked<Position>>) {
+///     for position in query.iter() {
+///         println!("moved by {}", position.current().0 - position.previous().0);
+///     }
+/// }
+///
+/// let mut stage = SystemStage::parallel()
+///     .with_system(update_tracked::<Position>.label("update_tracked"))
+///     .with_system(print_velocity.after("update_tracked"));
+/// # let mut world = World::new();
+/// # world.spawn().insert(Tracked::new(Position(0.0)));
+/// # stage.run(&mut world);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Tracked<T> {
+    current: T,
+    previous: T,
+}
+
+impl<T: Component> Component for Tracked<T> {
+    type Storage = T::Storage;
+}
+
+impl<T: Clone> Tracked<T> {
+    /// Creates a new [`Tracked`] whose previous value is the same as `value`.
+    pub fn new(value: T) -> Self {
+        Tracked {
+            previous: value.clone(),
+            current: value,
+        }
+    }
+}
+
+impl<T> Tracked<T> {
+    /// Returns the current value.
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    /// Returns a mutable reference to the current value.
+    pub fn current_mut(&mut self) -> &mut T {
+        &mut self.current
+    }
+
+    /// Replaces the current value.
+    pub fn set(&mut self, value: T) {
+        self.current = value;
+    }
+
+    /// Returns the value at the previous run of [`update_tracked`], before any changes made
+    /// since then.
+    pub fn previous(&self) -> &T {
+        &self.previous
+    }
+}
+
+/// Copies the current value of every changed [`Tracked<T>`] into its previous value.
+///
+/// This should run once per frame, before the systems that change the tracked values, for
+/// example in the first stage. It doesn't mark the [`Tracked<T>`] as changed, so
+/// `Changed<Tracked<T>>` only reports the changes to the current value.
+pub fn update_tracked<T: Component + Clone>(
+    mut query: Query<&mut Tracked<T>, Changed<Tracked<T>>>,
+) {
+    for mut tracked in query.iter_mut() {
+        let tracked = tracked.bypass_change_detection();
+        tracked.previous.clone_from(&tracked.current);
+    }
+}
+
+/// A component holding the value a `T` component of the same entity had at the previous run of
+/// [`update_previous::<T>`].
+///
+/// Unlike [`Tracked<T>`], this leaves `T` itself untouched, so that the systems writing `T` don't
+/// need to know that its previous value is kept. An entity only has a [`Previous<T>`] from the
+/// first run of [`update_previous::<T>`] after `T` was inserted.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, tracked::{Previous, update_previous}};
+/// #[derive(Component, Clone, Copy)]
+/// struct Position(f32);
+///
+/// fn print_velocity(query: Query<(&Position, &Previous<Position>)>) {
+///     for (position, previous) in query.iter() {
+///         println!("moved by {}", position.0 - previous.0);
+///     }
+/// }
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_stage("first", SystemStage::single_threaded().with_system(update_previous::<Position>));
+/// schedule.add_stage("update", SystemStage::parallel().with_system(print_velocity));
+/// # let mut world = World::new();
+/// # world.spawn().insert(Position(0.0));
+/// # schedule.run(&mut world);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Previous<T>(T);
+
+impl<T: Component> Component for Previous<T> {
+    type Storage = T::Storage;
+}
+
+impl<T> Deref for Previous<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// The changed `T` components updated by [`update_previous`], with their [`Previous<T>`].
+type ChangedWithPrevious<'w, 's, T> =
+    Query<'w, 's, (Entity, &'static T, Option<&'static mut Previous<T>>), Changed<T>>;
+
+/// Copies the value of every changed `T` component into the [`Previous<T>`] of its entity,
+/// inserting it if the entity doesn't have one yet.
+///
+/// This should run once per frame, before the systems that change `T`, for example in the first
+/// stage. Inserted [`Previous<T>`] components are only visible once the stage's commands are
+/// applied.
+pub fn update_previous<T: Component + Clone>(
+    mut commands: Commands,
+    mut query: ChangedWithPrevious<T>,
+) {
+    for (entity, current, previous) in query.iter_mut() {
+        if let Some(mut previous) = previous {
+            previous.0.clone_from(current);
+        } else {
+            commands.entity(entity).insert(Previous(current.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_previous, update_tracked, Previous, Tracked};
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        query::Changed,
+        schedule::{ParallelSystemDescriptorCoercion, Schedule, Stage, SystemStage},
+        system::{Local, Query, ResMut},
+        world::World,
+    };
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Position(f32);
+
+    #[test]
+    fn previous_holds_last_frame_value() {
+        let mut world = World::new();
+        let entity = world.spawn().insert(Tracked::new(Position(1.0))).id();
+
+        let mut stage = SystemStage::single_threaded().with_system(update_tracked::<Position>);
+        stage.run(&mut world);
+
+        world
+            .get_mut::<Tracked<Position>>(entity)
+            .unwrap()
+            .set(Position(2.0));
+        let tracked = world.get::<Tracked<Position>>(entity).unwrap();
+        assert_eq!(tracked.previous(), &Position(1.0));
+        assert_eq!(tracked.current(), &Position(2.0));
+
+        // The next frame starts by remembering the value the last one ended with.
+        stage.run(&mut world);
+        world
+            .get_mut::<Tracked<Position>>(entity)
+            .unwrap()
+            .current_mut()
+            .0 += 1.0;
+        let tracked = world.get::<Tracked<Position>>(entity).unwrap();
+        assert_eq!(tracked.previous(), &Position(2.0));
+        assert_eq!(tracked.current(), &Position(3.0));
+
+        // Once unchanged for a frame, both values are the same.
+        stage.run(&mut world);
+        stage.run(&mut world);
+        let tracked = world.get::<Tracked<Position>>(entity).unwrap();
+        assert_eq!(tracked.previous(), &Position(3.0));
+        assert_eq!(tracked.current(), &Position(3.0));
+    }
+
+    #[test]
+    fn update_tracked_is_not_a_change() {
+        #[derive(Default)]
+        struct Changes(usize);
+
+        fn move_once(mut query: Query<&mut Tracked<Position>>, mut frame: Local<usize>) {
+            *frame += 1;
+            if *frame == 2 {
+                for mut tracked in query.iter_mut() {
+                    tracked.set(Position(1.0));
+                }
+            }
+        }
+
+        fn count_changes(
+            query: Query<(), Changed<Tracked<Position>>>,
+            mut changes: ResMut<Changes>,
+        ) {
+            changes.0 += query.iter().count();
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Changes>();
+        let entity = world.spawn().insert(Tracked::new(Position(0.0))).id();
+        let mut schedule = Schedule::default();
+        schedule.add_stage(
+            "first",
+            SystemStage::single_threaded().with_system(update_tracked::<Position>),
+        );
+        schedule.add_stage(
+            "update",
+            SystemStage::single_threaded()
+                .with_system(move_once)
+                .with_system(count_changes.after(move_once)),
+        );
+
+        // The insertion, then the move, are changes.
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Changes>().0, 2);
+
+        // Remembering the moved value in the next frame isn't.
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Changes>().0, 2);
+        let tracked = world.get::<Tracked<Position>>(entity).unwrap();
+        assert_eq!(tracked.previous(), &Position(1.0));
+    }
+
+    #[test]
+    fn previous_lags_by_one_frame() {
+        #[derive(Default)]
+        struct Log(Vec<(f32, Option<f32>)>);
+
+        fn step(
+            mut query: Query<(&mut Position, Option<&Previous<Position>>)>,
+            mut log: ResMut<Log>,
+        ) {
+            for (mut position, previous) in query.iter_mut() {
+                position.0 += 1.0;
+                log.0
+                    .push((position.0, previous.map(|previous| (**previous).0)));
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let entity = world.spawn().insert(Position(0.0)).id();
+        let mut schedule = Schedule::default();
+        schedule.add_stage(
+            "first",
+            SystemStage::single_threaded().with_system(update_previous::<Position>),
+        );
+        schedule.add_stage("update", SystemStage::single_threaded().with_system(step));
+        for _ in 0..3 {
+            schedule.run(&mut world);
+        }
+
+        // Each frame reads the value the last one ended with.
+        assert_eq!(
+            world.resource::<Log>().0,
+            vec![(1.0, Some(0.0)), (2.0, Some(1.0)), (3.0, Some(2.0))]
+        );
+        assert_eq!(world.get::<Position>(entity), Some(&Position(3.0)));
+        assert_eq!(
+            world.get::<Previous<Position>>(entity),
+            Some(&Previous(Position(2.0)))
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use super::World;
+use crate::{
+    bundle::DynamicComponents,
+    component::{Component, ComponentId},
+    entity::Entity,
+};
+use bevy_ptr::OwningPtr;
+use bevy_utils::HashMap;
+use std::{mem::ManuallyDrop, ptr::NonNull};
+
+/// A component inserted while inserts are coalesced, waiting to be written to its entity.
+struct PendingComponent {
+    component_id: ComponentId,
+    /// The boxed component.
+    ptr: NonNull<u8>,
+    /// Drops the component and frees its box.
+    drop: unsafe fn(NonNull<u8>),
+    /// Frees the box of the component, once the component was moved out of it.
+    dealloc: unsafe fn(NonNull<u8>),
+}
+
+impl PendingComponent {
+    fn new<T: Component>(component_id: ComponentId, component: T) -> Self {
+        unsafe fn drop<T>(ptr: NonNull<u8>) {
+            std::mem::drop(Box::from_raw(ptr.as_ptr().cast::<T>()));
+        }
+        unsafe fn dealloc<T>(ptr: NonNull<u8>) {
+            std::mem::drop(Box::from_raw(ptr.as_ptr().cast::<ManuallyDrop<T>>()));
+        }
+        Self {
+            component_id,
+            ptr: NonNull::from(Box::leak(Box::new(component))).cast(),
+            drop: drop::<T>,
+            dealloc: dealloc::<T>,
+        }
+    }
+
+    fn drop_component(self) {
+        // SAFETY: the component is still in its box, which is only freed here
+        unsafe { (self.drop)(self.ptr) }
+    }
+}
+
+/// The components inserted by commands while [`World::coalesce_inserts`] runs, by entity, in the
+/// order they were inserted.
+#[derive(Default)]
+pub(crate) struct CoalescedInserts {
+    entities: Vec<(Entity, Vec<PendingComponent>)>,
+    indices: HashMap<Entity, usize>,
+}
+
+// SAFETY: the pending components are `Component`s, which are `Send` and `Sync`
+unsafe impl Send for CoalescedInserts {}
+// SAFETY: the pending components are `Component`s, which are `Send` and `Sync`
+unsafe impl Sync for CoalescedInserts {}
+
+impl Drop for CoalescedInserts {
+    fn drop(&mut self) {
+        for (_, components) in self.entities.drain(..) {
+            components
+                .into_iter()
+                .for_each(PendingComponent::drop_component);
+        }
+    }
+}
+
+impl World {
+    /// Runs `f`, holding back the components inserted by [`Insert`](crate::system::Insert)
+    /// commands until `f` returns, so that all the components inserted on an entity are written
+    /// at once, with a single archetype move.
+    ///
+    /// Inserts are only held back across commands that insert components: the held back components
+    /// are written before any other command is applied, so that it sees them. A component inserted
+    /// several times on an entity keeps its last value.
+    ///
+    /// Calls can be nested, when a command applied by `f` runs a stage: the components held back
+    /// are still all written when the inner call returns, before the outer stage goes on.
+    pub(crate) fn coalesce_inserts<R>(&mut self, f: impl FnOnce(&mut World) -> R) -> R {
+        let outermost = self.coalesced_inserts.is_none();
+        if outermost {
+            self.coalesced_inserts = Some(CoalescedInserts::default());
+        }
+        let result = f(self);
+        self.flush_coalesced_inserts();
+        if outermost {
+            self.coalesced_inserts = None;
+        }
+        result
+    }
+
+    /// Holds back `component` until the coalesced inserts are flushed, if inserts are coalesced.
+    ///
+    /// Returns the component if it has to be inserted right away instead, or if `entity` doesn't
+    /// exist.
+    pub(crate) fn insert_coalesced<T: Component>(
+        &mut self,
+        entity: Entity,
+        component: T,
+    ) -> Result<(), T> {
+        if self.coalesced_inserts.is_none() || !self.entities.contains(entity) {
+            return Err(component);
+        }
+        let component_id = self.init_component::<T>();
+        let pending = self.coalesced_inserts.as_mut().unwrap();
+        let index = *pending.indices.entry(entity).or_insert_with(|| {
+            pending.entities.push((entity, Vec::new()));
+            pending.entities.len() - 1
+        });
+        pending.entities[index]
+            .1
+            .push(PendingComponent::new(component_id, component));
+        Ok(())
+    }
+
+    /// Writes the components held back by [`World::coalesce_inserts`] so far.
+    pub(crate) fn flush_coalesced_inserts(&mut self) {
+        let mut entities = match &mut self.coalesced_inserts {
+            Some(pending) if !pending.entities.is_empty() => {
+                pending.indices.clear();
+                std::mem::take(&mut pending.entities)
+            }
+            _ => return,
+        };
+        for (entity, mut components) in entities.drain(..) {
+            // Keep the last value of each component, in the order of the component ids so that
+            // the same components always make the same bundle.
+            components.reverse();
+            components.sort_by_key(|component| component.component_id);
+            let mut kept: Vec<PendingComponent> = Vec::with_capacity(components.len());
+            for component in components {
+                match kept.last() {
+                    Some(last) if last.component_id == component.component_id => {
+                        component.drop_component();
+                    }
+                    _ => kept.push(component),
+                }
+            }
+
+            let location = match self.entities.get(entity) {
+                Some(location) => location,
+                None => {
+                    kept.into_iter().for_each(PendingComponent::drop_component);
+                    continue;
+                }
+            };
+            self.assert_structural_change_allowed();
+            let change_tick = self.change_tick();
+            let component_ids: Vec<ComponentId> = kept
+                .iter()
+                .map(|component| component.component_id)
+                .collect();
+            // SAFETY: the component ids were initialized by `insert_coalesced`
+            let bundle_info = unsafe {
+                self.bundles
+                    .init_dynamic_info(&mut self.components, &component_ids)
+            };
+            let mut bundle_inserter = bundle_info.get_bundle_inserter(
+                &mut self.entities,
+                &mut self.archetypes,
+                &mut self.components,
+                &mut self.storages,
+                location.archetype_id,
+                change_tick,
+            );
+            // SAFETY: each pointer is a boxed component of the matching component id, which the
+            // inserter moves out of the box
+            let ptrs = kept
+                .iter()
+                .map(|component| unsafe { OwningPtr::new(component.ptr) })
+                .collect();
+            // SAFETY: location matches current entity. The components match `bundle_info`
+            unsafe {
+                bundle_inserter.insert_components(entity, location.index, DynamicComponents(ptrs));
+            }
+            for component in kept {
+                // SAFETY: the component was moved out of its box
+                unsafe { (component.dealloc)(component.ptr) }
+            }
+        }
+        if let Some(pending) = &mut self.coalesced_inserts {
+            pending.entities = entities;
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use crate::{
+    bundle::Bundle,
+    component::{Component, ComponentDescriptor, ComponentId, Components, StorageType},
+    entity::Entity,
+    storage::Storages,
+    world::World,
+};
+use bevy_ptr::{OwningPtr, Ptr};
+use std::{
+    alloc::Layout,
+    any::{Any, TypeId},
+    borrow::Cow,
+};
+
+type RegisterFn = fn(&mut Components, &mut Storages) -> Vec<ComponentId>;
+
+/// Reads a component of a type known when it was registered as [`Any`].
+pub(crate) type AsAnyFn = for<'a> unsafe fn(Ptr<'a>) -> &'a dyn Any;
+
+/// # Safety
+///
+/// `ptr` must point to a valid `T`.
+unsafe fn as_any<T: Any>(ptr: Ptr<'_>) -> &dyn Any {
+    ptr.deref::<T>()
+}
+
+enum Registration {
+    Typed(RegisterFn),
+    Inspectable(fn(&mut Components, &mut Storages) -> ComponentId, AsAnyFn),
+    Descriptor(ComponentDescriptor),
+    Dynamic {
+        name: Cow<'static, str>,
+        storage_type: Option<StorageType>,
+        layout: Layout,
+        drop: Option<for<'a> unsafe fn(OwningPtr<'a>)>,
+    },
+}
+
+/// A list of component registrations that can be applied to a [`World`] all at once with
+/// [`World::register_components`].
+///
+/// This lets an app set up all of its component metadata in one place at startup. Plugins can
+/// build their own [`ComponentRegistry`] and [`extend`](ComponentRegistry::extend) the app's with
+/// it.
+///
+/// The storage of a Rust component type is always the one chosen by its [`Component`]
+/// implementation. The [default storage](ComponentRegistry::with_default_storage) only applies to
+/// dynamic components registered with [`ComponentRegistry::register_dynamic`] that don't specify
+/// their own storage.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, world::ComponentRegistry};
+/// #[derive(Component)]
+/// struct Position(f32);
+///
+/// #[derive(Component)]
+/// #[component(storage = "SparseSet")]
+/// struct Selected;
+///
+/// let mut registry = ComponentRegistry::default();
+/// registry.register_many::<(Position, Selected)>();
+///
+/// let mut world = World::new();
+/// let ids = world.register_components(registry);
+/// assert_eq!(ids.len(), 2);
+/// ```
+pub struct ComponentRegistry {
+    default_storage: StorageType,
+    registrations: Vec<Registration>,
+}
+
+impl Default for ComponentRegistry {
+    fn default() -> Self {
+        ComponentRegistry {
+            default_storage: StorageType::Table,
+            registrations: Vec::new(),
+        }
+    }
+}
+
+impl ComponentRegistry {
+    /// Sets the storage used by dynamic components that don't specify their own.
+    ///
+    /// Defaults to [`StorageType::Table`].
+    pub fn with_default_storage(mut self, storage_type: StorageType) -> Self {
+        self.default_storage = storage_type;
+        self
+    }
+
+    /// Returns the storage used by dynamic components that don't specify their own.
+    pub fn default_storage(&self) -> StorageType {
+        self.default_storage
+    }
+
+    /// Registers the component type `T`, which makes its values readable with
+    /// [`World::inspect`].
+    pub fn register<T: Component>(&mut self) -> &mut Self {
+        self.registrations.push(Registration::Inspectable(
+            |components, storages| components.init_component::<T>(storages),
+            as_any::<T>,
+        ));
+        self
+    }
+
+    /// Registers every component of the bundle `B`, such as a tuple of component types.
+    ///
+    /// Unlike with [`register`](Self::register), their values can't be read with
+    /// [`World::inspect`].
+    pub fn register_many<B: Bundle>(&mut self) -> &mut Self {
+        self.registrations
+            .push(Registration::Typed(B::component_ids));
+        self
+    }
+
+    /// Registers a new component from `descriptor`.
+    pub fn register_descriptor(&mut self, descriptor: ComponentDescriptor) -> &mut Self {
+        self.registrations
+            .push(Registration::Descriptor(descriptor));
+        self
+    }
+
+    /// Registers a new dynamic component. If `storage_type` is `None`, the
+    /// [default storage](ComponentRegistry::with_default_storage) is used.
+    ///
+    /// # Safety
+    ///
+    /// The same as [`ComponentDescriptor::new_with_layout`]: `drop` must be safe to call with an
+    /// [`OwningPtr`] pointing to any value of this component, and the component's data must be
+    /// safe to access from any thread.
+    pub unsafe fn register_dynamic(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        storage_type: Option<StorageType>,
+        layout: Layout,
+        drop: Option<for<'a> unsafe fn(OwningPtr<'a>)>,
+    ) -> &mut Self {
+        self.registrations.push(Registration::Dynamic {
+            name: name.into(),
+            storage_type,
+            layout,
+            drop,
+        });
+        self
+    }
+
+    /// Appends the registrations of `other` to this registry. The default storage of `other` is
+    /// kept for its dynamic components that don't specify their own.
+    pub fn extend(&mut self, other: ComponentRegistry) -> &mut Self {
+        for registration in other.registrations {
+            self.registrations.push(match registration {
+                Registration::Dynamic {
+                    name,
+                    storage_type,
+                    layout,
+                    drop,
+                } => Registration::Dynamic {
+                    name,
+                    storage_type: Some(storage_type.unwrap_or(other.default_storage)),
+                    layout,
+                    drop,
+                },
+                registration => registration,
+            });
+        }
+        self
+    }
+
+    /// Returns the number of registrations in this registry.
+    pub fn len(&self) -> usize {
+        self.registrations.len()
+    }
+
+    /// Returns `true` if this registry has no registrations.
+    pub fn is_empty(&self) -> bool {
+        self.registrations.is_empty()
+    }
+}
+
+impl World {
+    /// Applies every registration of `registry` to this [`World`], and returns the
+    /// [`ComponentId`]s of the registered components in order.
+    ///
+    /// Rust component types that are already initialized keep their existing [`ComponentId`].
+    pub fn register_components(&mut self, registry: ComponentRegistry) -> Vec<ComponentId> {
+        let default_storage = registry.default_storage;
+        let mut ids = Vec::new();
+        for registration in registry.registrations {
+            match registration {
+                Registration::Typed(register) => {
+                    ids.extend(register(&mut self.components, &mut self.storages));
+                }
+                Registration::Inspectable(register, as_any) => {
+                    let id = register(&mut self.components, &mut self.storages);
+                    self.component_accessors.insert(id, as_any);
+                    ids.push(id);
+                }
+                Registration::Descriptor(descriptor) => {
+                    ids.push(self.init_component_with_descriptor(descriptor));
+                }
+                Registration::Dynamic {
+                    name,
+                    storage_type,
+                    layout,
+                    drop,
+                } => {
+                    // SAFETY: upheld by the caller of `ComponentRegistry::register_dynamic`
+                    let descriptor = unsafe {
+                        ComponentDescriptor::new_with_layout(
+                            name,
+                            storage_type.unwrap_or(default_storage),
+                            layout,
+                            drop,
+                        )
+                    };
+                    ids.push(self.init_component_with_descriptor(descriptor));
+                }
+            }
+        }
+        ids
+    }
+
+    /// Returns the [`TypeId`] and the value of each component of `entity` whose type was
+    /// [registered](ComponentRegistry::register) with [`World::register_components`], for example
+    /// to show them in an inspector. Other components are skipped, and nothing is returned if the
+    /// entity doesn't exist.
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, world::ComponentRegistry};
+    /// # use std::any::TypeId;
+    /// #[derive(Component, Debug, PartialEq)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// let mut registry = ComponentRegistry::default();
+    /// registry.register::<Health>();
+    /// world.register_components(registry);
+    ///
+    /// let entity = world.spawn().insert(Health(10)).id();
+    /// let (type_id, value) = world.inspect(entity).next().unwrap();
+    /// assert_eq!(type_id, TypeId::of::<Health>());
+    /// assert_eq!(value.downcast_ref::<Health>(), Some(&Health(10)));
+    /// ```
+    pub fn inspect(&self, entity: Entity) -> impl Iterator<Item = (TypeId, &dyn Any)> + '_ {
+        let archetype = self
+            .entities
+            .get(entity)
+            .map(|location| &self.archetypes[location.archetype_id]);
+        archetype
+            .into_iter()
+            .flat_map(|archetype| archetype.components())
+            .filter_map(move |component_id| {
+                let as_any = self.component_accessors.get(component_id)?;
+                let type_id = self.components.get_info(component_id)?.type_id()?;
+                let ptr = self.get_by_id(entity, component_id)?;
+                // SAFETY: the accessor was registered for the type of this component.
+                Some((type_id, unsafe { as_any(ptr) }))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ComponentRegistry;
+    use crate::{self as bevy_ecs, component::Component, component::StorageType, world::World};
+    use std::{alloc::Layout, any::TypeId};
+
+    #[derive(Component, Debug, PartialEq)]
+    struct A(usize);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct B(usize);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct C(usize);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct D(usize);
+
+    #[test]
+    fn register_components() {
+        let mut registry =
+            ComponentRegistry::default().with_default_storage(StorageType::SparseSet);
+        registry.register::<A>().register_many::<(B, C)>();
+
+        // A plugin contributing its own registrations.
+        let mut plugin_registry = ComponentRegistry::default();
+        plugin_registry.register::<D>();
+        // SAFETY: the component holds no data that needs dropping and is `Send + Sync`.
+        unsafe {
+            plugin_registry.register_dynamic("plugin dynamic", None, Layout::new::<u64>(), None);
+            registry.register_dynamic("dynamic", None, Layout::new::<u64>(), None);
+        }
+        registry.extend(plugin_registry);
+
+        let mut world = World::new();
+        let ids = world.register_components(registry);
+        assert_eq!(ids.len(), 6);
+
+        let storage_types = ids
+            .iter()
+            .map(|id| world.components().get_info(*id).unwrap().storage_type())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            storage_types,
+            vec![
+                StorageType::Table,
+                StorageType::SparseSet,
+                StorageType::Table,
+                // The dynamic component of the main registry uses its sparse set default...
+                StorageType::SparseSet,
+                StorageType::SparseSet,
+                // ...while the one from the plugin keeps the plugin's table default.
+                StorageType::Table,
+            ]
+        );
+        assert_eq!(ids[0], world.init_component::<A>());
+        assert_eq!(ids[4], world.init_component::<D>());
+
+        let entity = world.spawn().insert_bundle((A(0), B(1), C(2), D(3))).id();
+        assert_eq!(
+            world
+                .query::<(&A, &B, &C, &D)>()
+                .get(&world, entity)
+                .unwrap(),
+            (&A(0), &B(1), &C(2), &D(3))
+        );
+    }
+
+    #[test]
+    fn inspect_registered_components() {
+        let mut registry = ComponentRegistry::default();
+        registry.register::<A>().register::<B>();
+        let mut world = World::new();
+        world.register_components(registry);
+
+        // `C` isn't registered, so it is skipped.
+        let entity = world.spawn().insert_bundle((A(1), B(2), C(3))).id();
+        let mut values = world.inspect(entity).collect::<Vec<_>>();
+        values.sort_by_key(|(type_id, _)| *type_id != TypeId::of::<A>());
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0].0, TypeId::of::<A>());
+        assert_eq!(values[0].1.downcast_ref::<A>(), Some(&A(1)));
+        assert_eq!(values[1].0, TypeId::of::<B>());
+        assert_eq!(values[1].1.downcast_ref::<B>(), Some(&B(2)));
+        assert_eq!(world.get::<C>(entity), Some(&C(3)));
+
+        world.despawn(entity);
+        assert_eq!(world.inspect(entity).count(), 0);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    component::{Component, TableStorage},
+    entity::Entity,
+    system::{Query, ResMut},
+};
+use bevy_utils::HashMap;
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// A dense index assigned to an entity by [`EntityIndices`], stable for as long as the entity
+/// keeps this component.
+///
+/// This is meant to correlate entities with external flat arrays, such as GPU buffers: query it
+/// alongside the mirrored components, and write them to `buffer[index]`. Indices are inserted
+/// with [`EntityCommands::insert_entity_index`](crate::system::EntityCommands::insert_entity_index),
+/// and recycled once the entity is despawned or loses the component, so every index stays below
+/// [`EntityIndices::len`].
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, world::EntityIndex};
+/// # #[derive(Component)]
+/// # struct Color([f32; 4]);
+/// # struct ColorBuffer(Vec<[f32; 4]>);
+/// fn write_colors(query: Query<(&EntityIndex, &Color)>, mut buffer: ResMut<ColorBuffer>) {
+///     for (index, color) in query.iter() {
+///         let index = index.index() as usize;
+///         if buffer.0.len() <= index {
+///             buffer.0.resize(index + 1, [0.0; 4]);
+///         }
+///         buffer.0[index] = color.0;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(write_colors);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EntityIndex(u32);
+
+impl EntityIndex {
+    /// Returns the index.
+    #[inline]
+    pub fn index(self) -> u32 {
+        self.0
+    }
+}
+
+impl Component for EntityIndex {
+    type Storage = TableStorage;
+}
+
+/// A resource assigning the [`EntityIndex`]es, which keeps them dense by recycling the indices of
+/// despawned entities, lowest first.
+///
+/// Indices are only recycled by [`EntityIndices::reclaim_system`], which should run once per
+/// frame, or by [`EntityIndices::release`].
+#[derive(Debug, Default)]
+pub struct EntityIndices {
+    assigned: HashMap<Entity, u32>,
+    free: BinaryHeap<Reverse<u32>>,
+    len: u32,
+}
+
+impl EntityIndices {
+    /// Returns the index of `entity`, assigning it first if it has none. This doesn't insert the
+    /// [`EntityIndex`] component.
+    pub fn assign(&mut self, entity: Entity) -> EntityIndex {
+        let (free, len) = (&mut self.free, &mut self.len);
+        let index = *self
+            .assigned
+            .entry(entity)
+            .or_insert_with(|| match free.pop() {
+                Some(Reverse(index)) => index,
+                None => {
+                    *len += 1;
+                    *len - 1
+                }
+            });
+        EntityIndex(index)
+    }
+
+    /// Makes the index of `entity` available to other entities, and returns it.
+    pub fn release(&mut self, entity: Entity) -> Option<EntityIndex> {
+        let index = self.assigned.remove(&entity)?;
+        self.free.push(Reverse(index));
+        Some(EntityIndex(index))
+    }
+
+    /// Returns the index assigned to `entity`, if any.
+    pub fn get(&self, entity: Entity) -> Option<EntityIndex> {
+        self.assigned.get(&entity).copied().map(EntityIndex)
+    }
+
+    /// Returns the number of entities with an index.
+    pub fn count(&self) -> usize {
+        self.assigned.len()
+    }
+
+    /// Returns one more than the highest index ever assigned, which is the length an external
+    /// array needs to hold every index.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if no index was ever assigned.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Releases the indices of the entities that were despawned or lost their [`EntityIndex`].
+    ///
+    /// This checks every assigned index, so that no despawn is missed whenever it happened.
+    pub fn reclaim_system(mut indices: ResMut<EntityIndices>, query: Query<&EntityIndex>) {
+        let indices = &mut *indices;
+        let free = &mut indices.free;
+        indices.assigned.retain(|entity, index| {
+            let kept = query.get(*entity).is_ok();
+            if !kept {
+                free.push(Reverse(*index));
+            }
+            kept
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EntityIndex, EntityIndices};
+    use crate::{
+        entity::Entity,
+        schedule::{Stage, SystemStage},
+        system::{CommandQueue, Commands},
+        world::World,
+    };
+
+    fn spawn_indexed(world: &mut World, count: usize) {
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, world);
+        for _ in 0..count {
+            commands.spawn().insert_entity_index();
+        }
+        queue.apply(world);
+    }
+
+    fn indices(world: &mut World) -> Vec<u32> {
+        let mut indices = world
+            .query::<&EntityIndex>()
+            .iter(world)
+            .map(|index| index.index())
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices
+    }
+
+    #[test]
+    fn recycle_indices() {
+        let mut world = World::new();
+        let mut stage = SystemStage::single_threaded().with_system(EntityIndices::reclaim_system);
+        spawn_indexed(&mut world, 4);
+        stage.run(&mut world);
+        assert_eq!(indices(&mut world), vec![0, 1, 2, 3]);
+
+        let despawned = world
+            .query::<(Entity, &EntityIndex)>()
+            .iter(&world)
+            .filter(|(_, index)| matches!(index.index(), 1 | 2))
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        for entity in despawned {
+            world.despawn(entity);
+        }
+        // Indices are only recycled once the system ran.
+        spawn_indexed(&mut world, 1);
+        assert_eq!(indices(&mut world), vec![0, 3, 4]);
+
+        stage.run(&mut world);
+        assert_eq!(world.resource::<EntityIndices>().count(), 3);
+        spawn_indexed(&mut world, 3);
+        stage.run(&mut world);
+        assert_eq!(indices(&mut world), vec![0, 1, 2, 3, 4, 5]);
+        let entity_indices = world.resource::<EntityIndices>();
+        assert_eq!(entity_indices.len(), 6);
+        assert_eq!(entity_indices.count(), 6);
+    }
+}
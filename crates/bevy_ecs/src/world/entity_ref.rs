@@ -2,7 +2,9 @@ use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes},
     bundle::{Bundle, BundleInfo},
     change_detection::{MutUntyped, Ticks},
-    component::{Component, ComponentId, ComponentTicks, Components, StorageType},
+    component::{
+        ColumnChangeTick, Component, ComponentId, ComponentTicks, Components, StorageType,
+    },
     entity::{Entities, Entity, EntityLocation},
     storage::{SparseSet, Storages},
     world::{Mut, World},
@@ -100,10 +102,11 @@ impl<'w> EntityRef<'w> {
         change_tick: u32,
     ) -> Option<Mut<'w, T>> {
         get_component_and_ticks_with_type(self.world, TypeId::of::<T>(), self.entity, self.location)
-            .map(|(value, ticks)| Mut {
+            .map(|(value, ticks, column_tick)| Mut {
                 value: value.assert_unique().deref_mut::<T>(),
                 ticks: Ticks {
                     component_ticks: ticks.deref_mut(),
+                    column_tick: Some(column_tick),
                     last_change_tick,
                     change_tick,
                 },
@@ -221,10 +224,11 @@ impl<'w> EntityMut<'w> {
     #[inline]
     pub unsafe fn get_unchecked_mut<T: Component>(&self) -> Option<Mut<'_, T>> {
         get_component_and_ticks_with_type(self.world, TypeId::of::<T>(), self.entity, self.location)
-            .map(|(value, ticks)| Mut {
+            .map(|(value, ticks, column_tick)| Mut {
                 value: value.assert_unique().deref_mut::<T>(),
                 ticks: Ticks {
                     component_ticks: ticks.deref_mut(),
+                    column_tick: Some(column_tick),
                     last_change_tick: self.world.last_change_tick(),
                     change_tick: self.world.read_change_tick(),
                 },
@@ -232,6 +236,7 @@ impl<'w> EntityMut<'w> {
     }
 
     pub fn insert_bundle<T: Bundle>(&mut self, bundle: T) -> &mut Self {
+        self.world.assert_structural_change_allowed();
         let change_tick = self.world.change_tick();
         let bundle_info = self
             .world
@@ -255,6 +260,7 @@ impl<'w> EntityMut<'w> {
 
     // TODO: move to BundleInfo
     pub fn remove_bundle<T: Bundle>(&mut self) -> Option<T> {
+        self.world.assert_structural_change_allowed();
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -380,6 +386,7 @@ impl<'w> EntityMut<'w> {
     // TODO: move to BundleInfo
     /// Remove any components in the bundle that the entity has.
     pub fn remove_bundle_intersection<T: Bundle>(&mut self) {
+        self.world.assert_structural_change_allowed();
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -588,7 +595,7 @@ unsafe fn get_component_and_ticks(
     component_id: ComponentId,
     entity: Entity,
     location: EntityLocation,
-) -> Option<(Ptr<'_>, &UnsafeCell<ComponentTicks>)> {
+) -> Option<(Ptr<'_>, &UnsafeCell<ComponentTicks>, &ColumnChangeTick)> {
     let archetype = &world.archetypes[location.archetype_id];
     let component_info = world.components.get_info_unchecked(component_id);
     match component_info.storage_type() {
@@ -600,13 +607,19 @@ unsafe fn get_component_and_ticks(
             Some((
                 components.get_data_unchecked(table_row),
                 components.get_ticks_unchecked(table_row),
+                components.change_tick(),
             ))
         }
-        StorageType::SparseSet => world
-            .storages
-            .sparse_sets
-            .get(component_id)
-            .and_then(|sparse_set| sparse_set.get_with_ticks(entity)),
+        StorageType::SparseSet => {
+            world
+                .storages
+                .sparse_sets
+                .get(component_id)
+                .and_then(|sparse_set| {
+                    let (value, ticks) = sparse_set.get_with_ticks(entity)?;
+                    Some((value, ticks, sparse_set.change_tick()))
+                })
+        }
     }
 }
 
@@ -700,7 +713,7 @@ pub(crate) unsafe fn get_component_and_ticks_with_type(
     type_id: TypeId,
     entity: Entity,
     location: EntityLocation,
-) -> Option<(Ptr<'_>, &UnsafeCell<ComponentTicks>)> {
+) -> Option<(Ptr<'_>, &UnsafeCell<ComponentTicks>, &ColumnChangeTick)> {
     let component_id = world.components.get_id(type_id)?;
     get_component_and_ticks(world, component_id, entity, location)
 }
@@ -862,10 +875,11 @@ pub(crate) unsafe fn get_mut<T: Component>(
     let change_tick = world.change_tick();
     let last_change_tick = world.last_change_tick();
     get_component_and_ticks_with_type(world, TypeId::of::<T>(), entity, location).map(
-        |(value, ticks)| Mut {
+        |(value, ticks, column_tick)| Mut {
             value: value.assert_unique().deref_mut::<T>(),
             ticks: Ticks {
                 component_ticks: ticks.deref_mut(),
+                column_tick: Some(column_tick),
                 last_change_tick,
                 change_tick,
             },
@@ -882,16 +896,17 @@ pub(crate) unsafe fn get_mut_by_id(
     component_id: ComponentId,
 ) -> Option<MutUntyped> {
     // SAFETY: world access is unique, entity location and component_id required to be valid
-    get_component_and_ticks(world, component_id, entity, location).map(|(value, ticks)| {
-        MutUntyped {
+    get_component_and_ticks(world, component_id, entity, location).map(
+        |(value, ticks, column_tick)| MutUntyped {
             value: value.assert_unique(),
             ticks: Ticks {
                 component_ticks: ticks.deref_mut(),
+                column_tick: Some(column_tick),
                 last_change_tick: world.last_change_tick(),
                 change_tick: world.read_change_tick(),
             },
-        }
-    })
+        },
+    )
 }
 
 #[cfg(test)]
@@ -0,0 +1,316 @@
+use crate::{
+    entity::{Entity, EntityMap, MapEntitiesError},
+    reflect::{ReflectComponent, ReflectMapEntities},
+    world::World,
+};
+use bevy_reflect::TypeRegistry;
+use bevy_utils::{tracing::debug, HashMap};
+use std::fmt;
+
+/// An error that occurs when merging one [`World`] into another with [`World::merge`].
+#[derive(Debug)]
+pub enum WorldMergeError {
+    /// A component type of the merged world is not registered in the [`TypeRegistry`].
+    UnregisteredType { type_name: String },
+    /// A component type is registered, but does not reflect [`Component`](crate::component::Component).
+    UnregisteredComponent { type_name: String },
+    /// A component references an entity that is missing from the [`EntityMap`].
+    MapEntities(MapEntitiesError),
+}
+
+impl std::error::Error for WorldMergeError {}
+
+impl fmt::Display for WorldMergeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WorldMergeError::UnregisteredType { type_name } => write!(
+                f,
+                "the type {} is not registered in the type registry",
+                type_name
+            ),
+            WorldMergeError::UnregisteredComponent { type_name } => write!(
+                f,
+                "the type {} is registered, but does not reflect `Component`",
+                type_name
+            ),
+            WorldMergeError::MapEntities(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<MapEntitiesError> for WorldMergeError {
+    fn from(err: MapEntitiesError) -> Self {
+        WorldMergeError::MapEntities(err)
+    }
+}
+
+impl World {
+    /// Copies every entity of `other` into this [`World`], using the [`ReflectComponent`] type
+    /// data of `type_registry` to copy their components.
+    ///
+    /// Each entity of `other` is spawned with a fresh [`Entity`](crate::entity::Entity) id, which
+    /// is recorded in `entity_map`. Entities that already have an entry in `entity_map` are copied
+    /// onto the entity they are mapped to instead. Once all entities are copied, components with
+    /// [`ReflectMapEntities`] type data have their entity references remapped with `entity_map`.
+    ///
+    /// Resources are not copied.
+    ///
+    /// # Errors
+    ///
+    /// If a component type of `other` isn't registered with [`ReflectComponent`] type data, an
+    /// error is returned before anything is copied, and this world and `entity_map` are left
+    /// unchanged. If a component references an entity that is missing from `entity_map`, the
+    /// error is only returned once every entity is copied: the copied entities stay in this world
+    /// and in `entity_map`, but some of their entity references may not have been remapped.
+    ///
+    /// ```
+    /// # use bevy_ecs::{entity::EntityMap, prelude::*};
+    /// # use bevy_reflect::{Reflect, TypeRegistry};
+    /// #[derive(Component, Reflect, Default)]
+    /// #[reflect(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register::<Health>();
+    ///
+    /// let mut scratch = World::new();
+    /// let scratch_entity = scratch.spawn().insert(Health(10)).id();
+    ///
+    /// let mut world = World::new();
+    /// let mut entity_map = EntityMap::default();
+    /// world.merge(&scratch, &type_registry, &mut entity_map).unwrap();
+    ///
+    /// let entity = entity_map.get(scratch_entity).unwrap();
+    /// assert_eq!(world.get::<Health>(entity).unwrap().0, 10);
+    /// ```
+    pub fn merge(
+        &mut self,
+        other: &World,
+        type_registry: &TypeRegistry,
+        entity_map: &mut EntityMap,
+    ) -> Result<(), WorldMergeError> {
+        // Look up every component type before copying anything, so that an unregistered type
+        // leaves this world untouched.
+        let mut reflect_components = HashMap::default();
+        for archetype in other.archetypes().iter() {
+            if archetype.entities().is_empty() {
+                continue;
+            }
+            for component_id in archetype.components() {
+                if reflect_components.contains_key(&component_id) {
+                    continue;
+                }
+                let component_info = other
+                    .components()
+                    .get_info(component_id)
+                    .expect("component_ids in archetypes should have ComponentInfo");
+                let reflect_component = component_info
+                    .type_id()
+                    .and_then(|type_id| type_registry.get(type_id))
+                    .ok_or_else(|| WorldMergeError::UnregisteredType {
+                        type_name: component_info.name().to_string(),
+                    })?
+                    .data::<ReflectComponent>()
+                    .ok_or_else(|| WorldMergeError::UnregisteredComponent {
+                        type_name: component_info.name().to_string(),
+                    })?;
+                reflect_components.insert(component_id, reflect_component);
+            }
+        }
+
+        for archetype in other.archetypes().iter() {
+            for other_entity in archetype.entities() {
+                let entity = *entity_map
+                    .entry(*other_entity)
+                    .or_insert_with(|| self.spawn().id());
+                for component_id in archetype.components() {
+                    reflect_components[&component_id].copy(other, self, *other_entity, entity);
+                }
+            }
+        }
+
+        for registration in type_registry.iter() {
+            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                map_entities_reflect.map_entities(self, entity_map)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Spawns a new entity with a copy of every component of `source` whose type is registered
+    /// with [`ReflectComponent`] type data in `type_registry`, and returns it.
+    ///
+    /// Components whose type is not registered are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` does not exist.
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+    /// # use bevy_reflect::{Reflect, TypeRegistry};
+    /// #[derive(Component, Reflect, Default)]
+    /// #[reflect(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register::<Health>();
+    ///
+    /// let mut world = World::new();
+    /// let prefab = world.spawn().insert(Health(10)).id();
+    /// let instance = world.clone_entity(prefab, &type_registry);
+    /// assert_eq!(world.get::<Health>(instance).unwrap().0, 10);
+    /// ```
+    pub fn clone_entity(&mut self, source: Entity, type_registry: &TypeRegistry) -> Entity {
+        let location = self.entity(source).location();
+        let archetype = &self.archetypes()[location.archetype_id];
+        let mut values = Vec::new();
+        for component_id in archetype.components() {
+            let component_info = self
+                .components()
+                .get_info(component_id)
+                .expect("component_ids in archetypes should have ComponentInfo");
+            let reflect_component = component_info
+                .type_id()
+                .and_then(|type_id| type_registry.get(type_id))
+                .and_then(|registration| registration.data::<ReflectComponent>());
+            match reflect_component {
+                Some(reflect_component) => {
+                    let value = reflect_component
+                        .reflect(self, source)
+                        .expect("the entity's archetype contains this component")
+                        .clone_value();
+                    values.push((reflect_component, value));
+                }
+                None => debug!(
+                    "Component {} is not registered with `ReflectComponent` and will not be cloned",
+                    component_info.name()
+                ),
+            }
+        }
+
+        let entity = self.spawn().id();
+        for (reflect_component, value) in values {
+            reflect_component.add(self, entity, &*value);
+        }
+        entity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WorldMergeError;
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+        reflect::{ReflectComponent, ReflectMapEntities},
+        world::World,
+    };
+    use bevy_reflect::{Reflect, TypeRegistry};
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Value(usize);
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, MapEntities)]
+    struct Link(Entity);
+
+    impl Default for Link {
+        fn default() -> Self {
+            Link(Entity::from_raw(u32::MAX))
+        }
+    }
+
+    impl MapEntities for Link {
+        fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+            self.0 = entity_map.get(self.0)?;
+            Ok(())
+        }
+    }
+
+    #[derive(Component)]
+    struct Unregistered;
+
+    fn type_registry() -> TypeRegistry {
+        let mut type_registry = TypeRegistry::default();
+        type_registry.register::<Value>();
+        type_registry.register::<Link>();
+        type_registry
+    }
+
+    #[test]
+    fn merge() {
+        let mut world = World::new();
+        let existing = world.spawn().insert(Value(100)).id();
+
+        let mut other = World::new();
+        let parent = other.spawn().insert(Value(0)).id();
+        let child = other.spawn().insert_bundle((Value(1), Link(parent))).id();
+
+        let mut entity_map = EntityMap::default();
+        world
+            .merge(&other, &type_registry(), &mut entity_map)
+            .unwrap();
+
+        assert_eq!(entity_map.len(), 2);
+        let new_parent = entity_map.get(parent).unwrap();
+        let new_child = entity_map.get(child).unwrap();
+        assert_ne!(new_parent, existing);
+        assert_ne!(new_child, existing);
+        assert_eq!(world.get::<Value>(existing), Some(&Value(100)));
+        assert_eq!(world.get::<Value>(new_parent), Some(&Value(0)));
+        assert_eq!(world.get::<Value>(new_child), Some(&Value(1)));
+        assert_eq!(world.get::<Link>(new_child), Some(&Link(new_parent)));
+        assert_eq!(world.entities().len(), 3);
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Name(String);
+
+    #[test]
+    fn clone_entity() {
+        let mut type_registry = type_registry();
+        type_registry.register::<Name>();
+
+        let mut world = World::new();
+        let target = world.spawn().id();
+        let source = world
+            .spawn()
+            .insert_bundle((
+                Value(1),
+                Link(target),
+                Name("source".to_string()),
+                Unregistered,
+            ))
+            .id();
+
+        let clone = world.clone_entity(source, &type_registry);
+        assert_ne!(clone, source);
+        assert_eq!(world.get::<Value>(clone), Some(&Value(1)));
+        assert_eq!(world.get::<Link>(clone), Some(&Link(target)));
+        assert_eq!(world.get::<Name>(clone), Some(&Name("source".to_string())));
+        assert!(world.get::<Unregistered>(clone).is_none());
+        assert_eq!(world.get::<Value>(source), Some(&Value(1)));
+    }
+
+    #[test]
+    fn merge_unregistered_component() {
+        let mut world = World::new();
+        let mut other = World::new();
+        other.spawn().insert(Value(0));
+        other.spawn().insert(Unregistered);
+
+        let mut entity_map = EntityMap::default();
+        let result = world.merge(&other, &type_registry(), &mut entity_map);
+        assert!(matches!(
+            result,
+            Err(WorldMergeError::UnregisteredType { .. })
+        ));
+        // Nothing was copied, not even the entities whose components are all registered.
+        assert_eq!(world.entities().len(), 0);
+        assert_eq!(entity_map.len(), 0);
+    }
+}
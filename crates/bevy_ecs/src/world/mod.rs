@@ -1,10 +1,33 @@
+mod coalesced_inserts;
+mod component_registry;
+mod entity_index;
 mod entity_ref;
+#[cfg(feature = "bevy_reflect")]
+mod merge;
+mod prefab_registry;
+#[cfg(feature = "bevy_reflect")]
+mod snapshot;
 mod spawn_batch;
+mod structural_guard;
+#[cfg(feature = "bevy_reflect")]
+mod time_travel;
+mod trait_resource;
 mod world_cell;
 
 pub use crate::change_detection::Mut;
+pub use component_registry::*;
+pub use entity_index::*;
 pub use entity_ref::*;
+#[cfg(feature = "bevy_reflect")]
+pub use merge::*;
+pub use prefab_registry::*;
+#[cfg(feature = "bevy_reflect")]
+pub use snapshot::*;
 pub use spawn_batch::*;
+pub(crate) use structural_guard::StructuralChangeGuard;
+#[cfg(feature = "bevy_reflect")]
+pub use time_travel::*;
+pub use trait_resource::*;
 pub use world_cell::*;
 
 use crate::{
@@ -13,9 +36,9 @@ use crate::{
     change_detection::{MutUntyped, Ticks},
     component::{
         Component, ComponentDescriptor, ComponentId, ComponentInfo, ComponentTicks, Components,
-        StorageType,
+        ResourceRemoval, StorageType,
     },
-    entity::{AllocAtWithoutReplacement, Entities, Entity},
+    entity::{AllocAtWithoutReplacement, Entities, Entity, EntityLocation},
     query::{QueryState, WorldQuery},
     storage::{Column, SparseSet, Storages},
     system::Resource,
@@ -86,8 +109,17 @@ pub struct World {
     pub(crate) storages: Storages,
     pub(crate) bundles: Bundles,
     pub(crate) removed_components: SparseSet<ComponentId, Vec<Entity>>,
+    /// The number of times [`World::clear_trackers`] cleared the removed components.
+    pub(crate) removed_components_generation: u32,
+    /// The removals of the resources that were removed and not inserted again.
+    pub(crate) removed_resources: SparseSet<ComponentId, ResourceRemoval>,
+    /// The accessors of the components registered by a [`ComponentRegistry`], for
+    /// [`World::inspect`].
+    pub(crate) component_accessors: SparseSet<ComponentId, AsAnyFn>,
     /// Access cache used by [WorldCell].
     pub(crate) archetype_component_access: ArchetypeComponentAccess,
+    /// The components held back by [`World::coalesce_inserts`], while it runs.
+    pub(crate) coalesced_inserts: Option<coalesced_inserts::CoalescedInserts>,
     main_thread_validator: MainThreadValidator,
     pub(crate) change_tick: AtomicU32,
     pub(crate) last_change_tick: u32,
@@ -103,7 +135,11 @@ impl Default for World {
             storages: Default::default(),
             bundles: Default::default(),
             removed_components: Default::default(),
+            removed_components_generation: 0,
+            removed_resources: Default::default(),
+            component_accessors: Default::default(),
             archetype_component_access: Default::default(),
+            coalesced_inserts: None,
             main_thread_validator: Default::default(),
             // Default value is `1`, and `last_change_tick`s default to `0`, such that changes
             // are detected on first system runs and for direct world queries.
@@ -125,6 +161,32 @@ impl World {
         World::default()
     }
 
+    /// Creates a new empty [`World`] that allocates [`Entity`] ids strictly sequentially.
+    ///
+    /// The ids of despawned entities are never reused, so each new entity gets the next unused
+    /// id regardless of what was despawned before. Entities reserved by [`Commands`](crate::system::Commands)
+    /// take their ids in the order they are reserved, and entities spawned directly on the world
+    /// continue after them. Running the same sequence of spawns therefore always produces the same
+    /// ids, which makes tests that inspect [`Entity`] values reliable.
+    ///
+    /// Reservations made by systems that run in parallel happen in execution order, so they are
+    /// only reproducible if those systems are ordered, or run in a single-threaded stage.
+    ///
+    /// ```
+    /// use bevy_ecs::world::World;
+    ///
+    /// let mut world = World::with_deterministic_allocation();
+    /// let a = world.spawn().id();
+    /// world.despawn(a);
+    /// let b = world.spawn().id();
+    /// assert_eq!(b.id(), a.id() + 1);
+    /// ```
+    pub fn with_deterministic_allocation() -> World {
+        let mut world = World::new();
+        world.entities.sequential = true;
+        world
+    }
+
     /// Retrieves this [`World`]'s unique ID
     #[inline]
     pub fn id(&self) -> WorldId {
@@ -385,6 +447,52 @@ impl World {
         Some(unsafe { EntityMut::new(self, entity, location) })
     }
 
+    /// Returns `true` if `entity` exists in this [`World`].
+    ///
+    /// This compares the generation of `entity` too, so an [`Entity`] whose id was despawned and
+    /// then reused by a newer entity is not alive. This makes it suitable for pruning stale
+    /// entities that were stored across frames.
+    ///
+    /// ```
+    /// use bevy_ecs::world::World;
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().id();
+    /// assert!(world.is_alive(entity));
+    ///
+    /// world.despawn(entity);
+    /// assert!(!world.is_alive(entity));
+    /// ```
+    #[inline]
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entities.get(entity).is_some()
+    }
+
+    /// Returns the current [`EntityLocation`] of `entity`: its archetype and its index in that
+    /// archetype, or [`None`] if it doesn't exist.
+    ///
+    /// This is a low-level hook, for example to key caches by storage location. The location is
+    /// only valid until the next structural change: it moves when a component is added to or
+    /// removed from the entity, and its index can change when another entity of its archetype is
+    /// removed. Systems can read locations with the [`Entities`] parameter, through
+    /// [`Entities::get`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Frozen;
+    /// let mut world = World::new();
+    /// let entity = world.spawn().id();
+    /// let location = world.entity_location(entity).unwrap();
+    ///
+    /// world.entity_mut(entity).insert(Frozen);
+    /// assert_ne!(world.entity_location(entity), Some(location));
+    /// ```
+    #[inline]
+    pub fn entity_location(&self, entity: Entity) -> Option<EntityLocation> {
+        self.entities.get(entity)
+    }
+
     /// Spawns a new [`Entity`] and returns a corresponding [`EntityMut`], which can be used
     /// to add components to the entity or retrieve its id.
     ///
@@ -547,6 +655,7 @@ impl World {
         for entities in self.removed_components.values_mut() {
             entities.clear();
         }
+        self.removed_components_generation = self.removed_components_generation.wrapping_add(1);
 
         self.last_change_tick = self.increment_change_tick();
     }
@@ -692,6 +801,22 @@ impl World {
         unsafe { self.insert_resource_with_id(component_id, value) };
     }
 
+    /// Replaces the value of the resource of type `R` with `value`, and returns the old value.
+    ///
+    /// Unlike [`insert_resource`](World::insert_resource), this requires the resource to already
+    /// exist. The resource is marked as changed, so systems reading it with
+    /// [`Res::is_changed`](crate::change_detection::DetectChanges::is_changed) react to the new
+    /// value, but it is not considered as newly added. This is useful for hot-reloading resources
+    /// such as configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource does not exist.
+    #[track_caller]
+    pub fn replace_resource<R: Resource>(&mut self, value: R) -> R {
+        std::mem::replace(&mut *self.resource_mut::<R>(), value)
+    }
+
     /// Inserts a new non-send resource with standard starting values.
     ///
     /// If the resource already exists, nothing happens.
@@ -749,9 +874,40 @@ impl World {
         }
         // SAFETY: if a resource column exists, row 0 exists as well. caller takes ownership of the
         // ptr value / drop is called when R is dropped
-        let (ptr, _) = unsafe { column.swap_remove_and_forget_unchecked(0) };
+        let (ptr, ticks) = unsafe { column.swap_remove_and_forget_unchecked(0) };
         // SAFETY: column is of type R
-        Some(unsafe { ptr.read::<R>() })
+        let value = unsafe { ptr.read::<R>() };
+        self.record_resource_removal(component_id, ticks);
+        Some(value)
+    }
+
+    /// Records that the resource with the id `component_id`, with the given `ticks`, was just
+    /// removed.
+    fn record_resource_removal(&mut self, component_id: ComponentId, ticks: ComponentTicks) {
+        let removal = ResourceRemoval {
+            removed: self.change_tick(),
+            added: ticks.added,
+        };
+        self.removed_resources.insert(component_id, removal);
+    }
+
+    /// Forgets the removal of the resource with the id `component_id`, which is about to be
+    /// inserted again, and returns the ticks of the inserted value.
+    ///
+    /// A resource removed and inserted again within the same frame keeps the added tick of the
+    /// removed value, so the pair reads as a change rather than as a removal and an addition.
+    fn take_resource_removal(
+        &mut self,
+        component_id: ComponentId,
+        change_tick: u32,
+    ) -> ComponentTicks {
+        let mut ticks = ComponentTicks::new(change_tick);
+        if let Some(removal) = self.removed_resources.remove(component_id) {
+            if removal.is_removed(self.last_change_tick, change_tick) {
+                ticks.added = removal.added;
+            }
+        }
+        ticks
     }
 
     /// Returns `true` if a resource of type `R` exists. Otherwise returns `false`.
@@ -783,6 +939,38 @@ impl World {
         ticks.is_added(self.last_change_tick(), self.read_change_tick())
     }
 
+    /// Returns `true` if the resource of type `R` was removed since the last call to
+    /// [`World::clear_trackers`], and wasn't inserted again since.
+    ///
+    /// A resource that is removed and inserted again within the same frame isn't reported as
+    /// removed, nor by [`World::is_resource_added`]: it keeps the added tick of the removed value,
+    /// and is reported as changed.
+    ///
+    /// ```
+    /// # use bevy_ecs::world::World;
+    /// struct Level(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.insert_resource(Level(1));
+    /// world.clear_trackers();
+    ///
+    /// world.remove_resource::<Level>();
+    /// assert!(world.is_resource_removed::<Level>());
+    ///
+    /// world.insert_resource(Level(2));
+    /// assert!(!world.is_resource_removed::<Level>());
+    /// assert!(!world.is_resource_added::<Level>());
+    /// assert!(world.is_resource_changed::<Level>());
+    /// ```
+    pub fn is_resource_removed<R: Resource>(&self) -> bool {
+        self.components
+            .get_resource_id(TypeId::of::<R>())
+            .and_then(|component_id| self.removed_resources.get(component_id))
+            .is_some_and(|removal| {
+                removal.is_removed(self.last_change_tick(), self.read_change_tick())
+            })
+    }
+
     pub fn is_resource_changed<R: Resource>(&self) -> bool {
         let component_id =
             if let Some(component_id) = self.components.get_resource_id(TypeId::of::<R>()) {
@@ -800,6 +988,46 @@ impl World {
         ticks.is_changed(self.last_change_tick(), self.read_change_tick())
     }
 
+    /// Returns an iterator over the [`TypeId`]s of the resources that were added or mutably
+    /// dereferenced since `last_change_tick`.
+    ///
+    /// Passing [`World::last_change_tick`] gives the resources changed since the last call to
+    /// [`World::clear_trackers`], which usually means during this frame. Resources without a Rust
+    /// type, such as those inserted with [`World::insert_resource_by_id`], are not returned.
+    ///
+    /// Resources are returned in the order their types were first registered in this world, which
+    /// doesn't depend on any hasher: the same insertions give the same order in every run.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use std::any::TypeId;
+    /// struct Score(u32);
+    /// struct Level(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.insert_resource(Score(0));
+    /// world.insert_resource(Level(1));
+    /// world.clear_trackers();
+    ///
+    /// world.resource_mut::<Score>().0 += 10;
+    /// let changed: Vec<TypeId> = world.changed_resources(world.last_change_tick()).collect();
+    /// assert_eq!(changed, vec![TypeId::of::<Score>()]);
+    /// ```
+    pub fn changed_resources(&self, last_change_tick: u32) -> impl Iterator<Item = TypeId> + '_ {
+        let change_tick = self.read_change_tick();
+        self.archetypes
+            .resource()
+            .unique_components()
+            .iter()
+            .filter(move |(_, column)| {
+                // SAFETY: resources table always have row 0
+                !column.is_empty()
+                    && unsafe { column.get_ticks_unchecked(0).deref() }
+                        .is_changed(last_change_tick, change_tick)
+            })
+            .filter_map(|(component_id, _)| self.components.get_info(*component_id)?.type_id())
+    }
+
     /// Gets a reference to the resource of the given type
     ///
     /// # Panics
@@ -1134,6 +1362,7 @@ impl World {
             value: &mut value,
             ticks: Ticks {
                 component_ticks: &mut ticks,
+                column_tick: None,
                 last_change_tick,
                 change_tick,
             },
@@ -1180,6 +1409,7 @@ impl World {
             value: column.get_data_ptr().assert_unique().deref_mut(),
             ticks: Ticks {
                 component_ticks: column.get_ticks_unchecked(0).deref_mut(),
+                column_tick: None,
                 last_change_tick: self.last_change_tick(),
                 change_tick: self.read_change_tick(),
             },
@@ -1214,11 +1444,12 @@ impl World {
     #[inline]
     unsafe fn insert_resource_with_id<R>(&mut self, component_id: ComponentId, value: R) {
         let change_tick = self.change_tick();
+        let ticks = self.take_resource_removal(component_id, change_tick);
         let column = self.initialize_resource_internal(component_id);
         if column.is_empty() {
             // SAFETY: column is of type R and has been allocated above
             OwningPtr::make(value, |ptr| {
-                column.push(ptr, ComponentTicks::new(change_tick));
+                column.push(ptr, ticks);
             });
         } else {
             // SAFETY: column is of type R and has already been allocated
@@ -1246,11 +1477,12 @@ impl World {
                 "insert_resource_by_id called with component id which doesn't exist in this world"
             )
         });
+        let ticks = self.take_resource_removal(component_id, change_tick);
         // SAFETY: component_id is valid, checked by the lines above
         let column = self.initialize_resource_internal(component_id);
         if column.is_empty() {
             // SAFETY: column is of type R and has been allocated above
-            column.push(value, ComponentTicks::new(change_tick));
+            column.push(value, ticks);
         } else {
             let ptr = column.get_data_unchecked_mut(0);
             std::ptr::copy_nonoverlapping::<u8>(
@@ -1342,6 +1574,7 @@ impl World {
     /// This should be called before doing operations that might operate on queued entities,
     /// such as inserting a [Component].
     pub(crate) fn flush(&mut self) {
+        self.assert_structural_change_allowed();
         let empty_archetype = self.archetypes.empty_mut();
         let table = &mut self.storages.tables[empty_archetype.table_id()];
         // PERF: consider pre-allocating space for flushed entities
@@ -1385,9 +1618,13 @@ impl World {
         for column in resource_archetype.unique_components.values_mut() {
             column.check_change_ticks(change_tick);
         }
+        for removal in self.removed_resources.values_mut() {
+            removal.check_ticks(change_tick);
+        }
     }
 
     pub fn clear_entities(&mut self) {
+        self.assert_structural_change_allowed();
         self.storages.tables.clear();
         self.storages.sparse_sets.clear();
         self.archetypes.clear_entities();
@@ -1435,6 +1672,7 @@ impl World {
             // - index is in-bounds because the column is initialized and non-empty
             // - no other reference to the ticks of the same row can exist at the same time
             component_ticks: unsafe { &mut *column.get_ticks_unchecked(0).get() },
+            column_tick: None,
             last_change_tick: self.last_change_tick(),
             change_tick: self.read_change_tick(),
         };
@@ -1463,7 +1701,10 @@ impl World {
             return None;
         }
         // SAFETY: if a resource column exists, row 0 exists as well
+        let ticks = unsafe { *column.get_ticks_unchecked(0).deref() };
+        // SAFETY: if a resource column exists, row 0 exists as well
         unsafe { column.swap_remove_unchecked(0) };
+        self.record_resource_removal(component_id, ticks);
 
         Some(())
     }
@@ -1721,6 +1962,106 @@ mod tests {
         assert_eq!(resource.0, 42);
     }
 
+    #[test]
+    fn replace_resource() {
+        #[derive(Debug, PartialEq)]
+        struct Config(u32);
+
+        let mut world = World::new();
+        world.insert_resource(Config(1));
+        world.clear_trackers();
+        assert!(!world.is_resource_changed::<Config>());
+
+        assert_eq!(world.replace_resource(Config(2)), Config(1));
+        assert_eq!(world.resource::<Config>(), &Config(2));
+        assert!(world.is_resource_changed::<Config>());
+        assert!(!world.is_resource_added::<Config>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn replace_missing_resource() {
+        struct Config;
+
+        let mut world = World::new();
+        world.replace_resource(Config);
+    }
+
+    #[test]
+    fn changed_resources() {
+        struct Score(u32);
+        struct Level(u32);
+        struct Lives(u32);
+
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        world.insert_resource(Level(1));
+        world.insert_resource(Lives(3));
+
+        let mut changed = world
+            .changed_resources(world.last_change_tick())
+            .collect::<Vec<_>>();
+        changed.sort();
+        let mut expected = vec![
+            TypeId::of::<Score>(),
+            TypeId::of::<Level>(),
+            TypeId::of::<Lives>(),
+        ];
+        expected.sort();
+        assert_eq!(changed, expected);
+
+        world.clear_trackers();
+        assert_eq!(world.changed_resources(world.last_change_tick()).count(), 0);
+
+        world.resource_mut::<Level>().0 += 1;
+        // Reading a resource doesn't count as a change.
+        assert_eq!(world.resource::<Score>().0 + world.resource::<Lives>().0, 3);
+        let changed = world
+            .changed_resources(world.last_change_tick())
+            .collect::<Vec<_>>();
+        assert_eq!(changed, vec![TypeId::of::<Level>()]);
+
+        // Removed resources are not returned.
+        world.remove_resource::<Level>();
+        assert_eq!(world.changed_resources(world.last_change_tick()).count(), 0);
+    }
+
+    #[test]
+    fn resource_iteration_order() {
+        struct Score;
+        struct Level;
+        struct Lives;
+
+        fn insert_in_order(world: &mut World, reverse: bool) -> Vec<TypeId> {
+            if reverse {
+                world.insert_resource(Lives);
+                world.insert_resource(Level);
+                world.insert_resource(Score);
+            } else {
+                world.insert_resource(Score);
+                world.insert_resource(Level);
+                world.insert_resource(Lives);
+            }
+            world.changed_resources(0).collect()
+        }
+
+        // Fresh worlds stand for separate runs: the same insertions give the same order.
+        let order = insert_in_order(&mut World::new(), false);
+        assert_eq!(order, insert_in_order(&mut World::new(), false));
+        assert_eq!(
+            order,
+            vec![
+                TypeId::of::<Score>(),
+                TypeId::of::<Level>(),
+                TypeId::of::<Lives>()
+            ]
+        );
+        // The order is the registration order of the resource types.
+        let mut reversed = insert_in_order(&mut World::new(), true);
+        reversed.reverse();
+        assert_eq!(order, reversed);
+    }
+
     #[test]
     fn get_resource_mut_by_id() {
         let mut world = World::new();
@@ -1862,4 +2203,102 @@ mod tests {
             [Some(baz_id)].into()
         );
     }
+
+    #[test]
+    fn is_alive_checks_generation() {
+        let mut world = World::new();
+        let stale = world.spawn().insert(Foo).id();
+        world.despawn(stale);
+
+        let recycled = world.spawn().insert(Foo).id();
+        assert_eq!(recycled.id(), stale.id());
+        assert_ne!(recycled.generation(), stale.generation());
+
+        assert!(!world.is_alive(stale));
+        assert!(world.is_alive(recycled));
+        let mut query = world.query::<&Foo>();
+        assert!(query.get(&world, stale).is_err());
+        assert!(query.get(&world, recycled).is_ok());
+    }
+
+    #[test]
+    fn entity_location_moves_with_archetype() {
+        use crate::{entity::Entities, system::SystemState};
+
+        let mut world = World::new();
+        let first = world.spawn().insert(Foo).id();
+        let entity = world.spawn().insert(Foo).id();
+        let before = world.entity_location(entity).unwrap();
+        assert_eq!(before.index, 1);
+
+        world.entity_mut(entity).insert(Bar);
+        let after = world.entity_location(entity).unwrap();
+        assert_ne!(after.archetype_id, before.archetype_id);
+        assert_eq!(after.index, 0);
+
+        // Removing another entity of the archetype moves the last one into its row.
+        let other = world.spawn().insert_bundle((Foo, Bar)).id();
+        world.despawn(entity);
+        assert_eq!(world.entity_location(other), Some(after));
+        assert_eq!(world.entity_location(entity), None);
+
+        let mut entities = SystemState::<&Entities>::new(&mut world);
+        assert_eq!(
+            entities.get(&world).get(first),
+            world.entity_location(first)
+        );
+    }
+
+    #[test]
+    fn deterministic_allocation() {
+        use crate::{
+            entity::Entity,
+            system::{CommandQueue, Commands},
+        };
+
+        fn spawn_sequence() -> Vec<Entity> {
+            let mut world = World::with_deterministic_allocation();
+            let mut ids = Vec::new();
+            let a = world.spawn().insert(Foo).id();
+            ids.push(a);
+            ids.push(world.spawn().id());
+            world.despawn(a);
+
+            let mut queue = CommandQueue::default();
+            let mut commands = Commands::new(&mut queue, &world);
+            ids.push(commands.spawn().insert(Bar).id());
+            ids.push(commands.spawn().id());
+            ids.push(world.spawn().id());
+            queue.apply(&mut world);
+            ids.push(world.spawn().id());
+            ids
+        }
+
+        let ids = spawn_sequence();
+        assert_eq!(ids, spawn_sequence());
+        assert_eq!(
+            ids.iter().map(|entity| entity.id()).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn deterministic_allocation_respawn_freed_entity() {
+        let mut world = World::with_deterministic_allocation();
+        let entity = world.spawn().id();
+        assert!(world.despawn(entity));
+        assert_eq!(world.entities().len(), 0);
+
+        world.get_or_spawn(entity).unwrap().insert(Foo);
+        assert_eq!(world.entities().len(), 1);
+        assert!(world.get::<Foo>(entity).is_some());
+        assert!(world.despawn(entity));
+        assert_eq!(world.entities().len(), 0);
+
+        // A freed id is also counted again when it is respawned in a batch.
+        world.insert_or_spawn_batch([(entity, (Foo,))]).unwrap();
+        assert_eq!(world.entities().len(), 1);
+        // Fresh ids still skip the freed ones.
+        assert_eq!(world.spawn().id().id(), entity.id() + 1);
+    }
 }
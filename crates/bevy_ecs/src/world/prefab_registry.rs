@@ -0,0 +1,86 @@
+use crate::{bundle::Bundle, world::EntityMut};
+use bevy_utils::HashMap;
+use std::borrow::Cow;
+
+type PrefabFn = Box<dyn Fn(&mut EntityMut) + Send + Sync>;
+
+/// A resource of reusable bundles, or "prefabs", that can be spawned by name with
+/// [`Commands::spawn_prefab`](crate::system::Commands::spawn_prefab).
+///
+/// This lets spawns be data-driven, such as spawning an enemy type read from a level file,
+/// without hardcoding the bundle at each spawn site. Like in a
+/// [`ComponentRegistry`](super::ComponentRegistry), plugins can register their prefabs at
+/// startup.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, world::PrefabRegistry};
+/// #[derive(Component)]
+/// struct Health(u32);
+/// #[derive(Component, Default)]
+/// struct Coin;
+///
+/// let mut prefabs = PrefabRegistry::default();
+/// prefabs
+///     .register("goblin", || (Health(10),))
+///     .register_default::<(Coin,)>("coin");
+///
+/// let mut world = World::new();
+/// world.insert_resource(prefabs);
+/// ```
+#[derive(Default)]
+pub struct PrefabRegistry {
+    prefabs: HashMap<Cow<'static, str>, PrefabFn>,
+}
+
+impl PrefabRegistry {
+    /// Registers the prefab `name`, spawned with the bundle returned by `bundle`. A prefab that
+    /// was registered with the same name is replaced.
+    pub fn register<B: Bundle>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        bundle: impl Fn() -> B + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.prefabs.insert(
+            name.into(),
+            Box::new(move |entity| {
+                entity.insert_bundle(bundle());
+            }),
+        );
+        self
+    }
+
+    /// Registers the prefab `name`, spawned with the default value of the bundle type `B`. A
+    /// prefab that was registered with the same name is replaced.
+    pub fn register_default<B: Bundle + Default>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> &mut Self {
+        self.register(name, B::default)
+    }
+
+    /// Returns `true` if the prefab `name` is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.prefabs.contains_key(name)
+    }
+
+    /// Returns the names of the registered prefabs, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.prefabs.keys().map(|name| name.as_ref())
+    }
+
+    /// Unregisters the prefab `name`. Returns `false` if it wasn't registered.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.prefabs.remove(name).is_some()
+    }
+
+    /// Inserts the bundle of the prefab `name` onto `entity`. Returns `false`, and leaves `entity`
+    /// unchanged, if the prefab isn't registered.
+    pub fn insert_into(&self, name: &str, entity: &mut EntityMut) -> bool {
+        if let Some(prefab) = self.prefabs.get(name) {
+            prefab(entity);
+            true
+        } else {
+            false
+        }
+    }
+}
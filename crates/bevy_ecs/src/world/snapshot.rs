@@ -0,0 +1,340 @@
+use crate::{
+    entity::Entity,
+    reflect::{ReflectComponent, ReflectResource},
+    world::World,
+};
+use bevy_reflect::{Reflect, TypeRegistry};
+use bevy_utils::{tracing::debug, HashMap, HashSet};
+
+/// The state of the reflected resources of a [`World`], captured by
+/// [`World::snapshot_resources`] and restored by [`World::restore_resources`].
+pub struct ResourceSnapshot {
+    resources: Vec<(ReflectResource, Option<Box<dyn Reflect>>)>,
+}
+
+/// The entities of a [`World`] and their reflected components, captured by [`World::snapshot`]
+/// and restored by [`World::restore`].
+pub struct WorldSnapshot {
+    components: Vec<ReflectComponent>,
+    entities: Vec<EntitySnapshot>,
+}
+
+struct EntitySnapshot {
+    entity: Entity,
+    /// The captured components, as indices into [`WorldSnapshot::components`] and their value.
+    values: Vec<(usize, Box<dyn Reflect>)>,
+}
+
+impl World {
+    /// Captures every entity of this [`World`], along with the value of each of its components
+    /// whose type is registered with [`ReflectComponent`] type data in `type_registry`.
+    ///
+    /// Components whose type is not registered are skipped, and logged at the debug level.
+    /// Resources are not captured, see [`World::snapshot_resources`].
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+    /// # use bevy_reflect::{Reflect, TypeRegistry};
+    /// #[derive(Component, Reflect, Default)]
+    /// #[reflect(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register::<Health>();
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().insert(Health(10)).id();
+    /// let snapshot = world.snapshot(&type_registry);
+    ///
+    /// world.despawn(entity);
+    /// world.restore(&snapshot);
+    /// assert_eq!(world.get::<Health>(entity).unwrap().0, 10);
+    /// ```
+    pub fn snapshot(&self, type_registry: &TypeRegistry) -> WorldSnapshot {
+        let mut components = Vec::new();
+        let mut component_indices = HashMap::default();
+        let mut unregistered = HashSet::default();
+        let mut entities = Vec::with_capacity(self.entities().len() as usize);
+        for archetype in self.archetypes().iter() {
+            for entity in archetype.entities() {
+                let mut values = Vec::new();
+                for component_id in archetype.components() {
+                    let index = *component_indices.entry(component_id).or_insert_with(|| {
+                        let reflect_component = self
+                            .components()
+                            .get_info(component_id)
+                            .and_then(|info| info.type_id())
+                            .and_then(|type_id| type_registry.get(type_id))
+                            .and_then(|registration| registration.data::<ReflectComponent>());
+                        reflect_component.map(|reflect_component| {
+                            components.push(reflect_component.clone());
+                            components.len() - 1
+                        })
+                    });
+                    match index {
+                        Some(index) => {
+                            let value = components[index]
+                                .reflect(self, *entity)
+                                .expect("the entity's archetype contains this component");
+                            values.push((index, value.clone_value()));
+                        }
+                        None => {
+                            unregistered.insert(component_id);
+                        }
+                    }
+                }
+                entities.push(EntitySnapshot {
+                    entity: *entity,
+                    values,
+                });
+            }
+        }
+
+        for component_id in unregistered {
+            let info = self
+                .components()
+                .get_info(component_id)
+                .expect("component_ids in archetypes should have ComponentInfo");
+            debug!(
+                "Component {} is not registered with `ReflectComponent` and will not be snapshotted",
+                info.name()
+            );
+        }
+        WorldSnapshot {
+            components,
+            entities,
+        }
+    }
+
+    /// Restores the entities captured by [`World::snapshot`], with the same [`Entity`] ids, and
+    /// sets their captured components to their captured value.
+    ///
+    /// Entities that were spawned since the snapshot was taken are despawned, and captured
+    /// component types that were added to an entity since are removed from it. Components whose
+    /// type was not captured are left as-is.
+    ///
+    /// Only the ids of captured entities are preserved: entities spawned after restoring may not
+    /// get the same ids as entities spawned after taking the snapshot.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        let captured = snapshot
+            .entities
+            .iter()
+            .map(|snapshot| snapshot.entity)
+            .collect::<HashSet<_>>();
+        let spawned = self
+            .archetypes()
+            .iter()
+            .flat_map(|archetype| archetype.entities())
+            .filter(|entity| !captured.contains(entity))
+            .copied()
+            .collect::<Vec<_>>();
+        for entity in spawned {
+            self.despawn(entity);
+        }
+
+        for EntitySnapshot { entity, values } in &snapshot.entities {
+            // Any entity with the same id but another generation was despawned above.
+            self.get_or_spawn(*entity)
+                .expect("entity ids of the snapshot should be free");
+            for (index, reflect_component) in snapshot.components.iter().enumerate() {
+                if reflect_component.reflect(self, *entity).is_some()
+                    && !values.iter().any(|(value_index, _)| *value_index == index)
+                {
+                    reflect_component.remove(self, *entity);
+                }
+            }
+            for (index, value) in values {
+                snapshot.components[*index].add(self, *entity, &**value);
+            }
+        }
+    }
+
+    /// Captures the value of every resource type registered with [`ReflectResource`] type data in
+    /// `type_registry`, including whether it is present at all.
+    ///
+    /// Resources whose type is not registered are not captured, and will be left as-is by
+    /// [`World::restore_resources`].
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, reflect::ReflectResource};
+    /// # use bevy_reflect::{Reflect, TypeRegistry};
+    /// #[derive(Reflect, Default)]
+    /// #[reflect(Resource)]
+    /// struct Score(u32);
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register::<Score>();
+    ///
+    /// let mut world = World::new();
+    /// world.insert_resource(Score(1));
+    /// let snapshot = world.snapshot_resources(&type_registry);
+    ///
+    /// world.resource_mut::<Score>().0 = 2;
+    /// world.restore_resources(&snapshot);
+    /// assert_eq!(world.resource::<Score>().0, 1);
+    /// ```
+    pub fn snapshot_resources(&self, type_registry: &TypeRegistry) -> ResourceSnapshot {
+        for component_id in self.archetypes().resource().components() {
+            let info = self
+                .components()
+                .get_info(component_id)
+                .expect("component_ids in archetypes should have ComponentInfo");
+            let registered = info
+                .type_id()
+                .and_then(|type_id| type_registry.get(type_id))
+                .and_then(|registration| registration.data::<ReflectResource>())
+                .is_some();
+            if !registered {
+                debug!(
+                    "Resource {} is not registered with `ReflectResource` and will not be snapshotted",
+                    info.name()
+                );
+            }
+        }
+
+        let resources = type_registry
+            .iter()
+            .filter_map(|registration| registration.data::<ReflectResource>())
+            .map(|reflect_resource| {
+                let value = reflect_resource
+                    .reflect(self)
+                    .map(|resource| resource.clone_value());
+                (reflect_resource.clone(), value)
+            })
+            .collect();
+        ResourceSnapshot { resources }
+    }
+
+    /// Restores the resources captured by [`World::snapshot_resources`] to their captured value.
+    ///
+    /// Captured resources that were missing when the snapshot was taken are removed.
+    pub fn restore_resources(&mut self, snapshot: &ResourceSnapshot) {
+        for (reflect_resource, value) in &snapshot.resources {
+            match value {
+                Some(value) => reflect_resource.insert(self, &**value),
+                None => reflect_resource.remove(self),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        entity::Entity,
+        reflect::{ReflectComponent, ReflectResource},
+        world::World,
+    };
+    use bevy_reflect::{Reflect, TypeRegistry};
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Counter {
+        count: usize,
+        history: Vec<usize>,
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Spawned(usize);
+
+    #[derive(Debug, PartialEq)]
+    struct Unregistered(usize);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Position(i32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Tags(Vec<u32>);
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Target(Entity);
+
+    impl Default for Target {
+        fn default() -> Self {
+            Target(Entity::from_raw(u32::MAX))
+        }
+    }
+
+    #[derive(Component, Debug, PartialEq)]
+    struct UnregisteredComponent;
+
+    #[test]
+    fn snapshot_world() {
+        let mut type_registry = TypeRegistry::default();
+        type_registry.register::<Position>();
+        type_registry.register::<Tags>();
+        type_registry.register::<Target>();
+
+        let mut world = World::new();
+        let a = world.spawn().insert(Position(1)).id();
+        let b = world
+            .spawn()
+            .insert_bundle((Position(2), Tags(vec![1, 2]), Target(a)))
+            .id();
+        let c = world
+            .spawn()
+            .insert_bundle((Tags(vec![3]), UnregisteredComponent))
+            .id();
+        let snapshot = world.snapshot(&type_registry);
+
+        world.get_mut::<Position>(a).unwrap().0 = 10;
+        world.entity_mut(a).insert(Tags(vec![4]));
+        world.entity_mut(b).remove::<Tags>();
+        world.despawn(c);
+        // Reuses the id of `c` with a newer generation.
+        let d = world.spawn().insert(Position(4)).id();
+        assert_eq!(d.id(), c.id());
+
+        world.restore(&snapshot);
+        assert_eq!(world.entities().len(), 3);
+        assert!(!world.entities().contains(d));
+        assert_eq!(world.get::<Position>(a), Some(&Position(1)));
+        assert_eq!(world.get::<Tags>(a), None);
+        assert_eq!(world.get::<Position>(b), Some(&Position(2)));
+        assert_eq!(world.get::<Tags>(b), Some(&Tags(vec![1, 2])));
+        assert_eq!(world.get::<Target>(b), Some(&Target(a)));
+        assert_eq!(world.get::<Position>(c), None);
+        assert_eq!(world.get::<Tags>(c), Some(&Tags(vec![3])));
+        assert_eq!(world.get::<UnregisteredComponent>(c), None);
+    }
+
+    #[test]
+    fn snapshot_resources() {
+        let mut type_registry = TypeRegistry::default();
+        type_registry.register::<Counter>();
+        type_registry.register::<Spawned>();
+
+        let mut world = World::new();
+        world.insert_resource(Counter {
+            count: 1,
+            history: vec![0],
+        });
+        world.insert_resource(Unregistered(1));
+        let snapshot = world.snapshot_resources(&type_registry);
+
+        {
+            let mut counter = world.resource_mut::<Counter>();
+            counter.count = 2;
+            counter.history.push(1);
+        }
+        world.insert_resource(Spawned(2));
+        world.insert_resource(Unregistered(2));
+
+        world.restore_resources(&snapshot);
+        assert_eq!(
+            world.resource::<Counter>(),
+            &Counter {
+                count: 1,
+                history: vec![0],
+            }
+        );
+        assert!(!world.contains_resource::<Spawned>());
+        assert_eq!(world.resource::<Unregistered>(), &Unregistered(2));
+    }
+}
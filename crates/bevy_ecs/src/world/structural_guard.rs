@@ -0,0 +1,70 @@
+use super::World;
+#[cfg(debug_assertions)]
+use super::WorldId;
+use std::borrow::Cow;
+#[cfg(debug_assertions)]
+use std::cell::RefCell;
+
+#[cfg(debug_assertions)]
+thread_local! {
+    /// The system running on this thread with shared access to a world, if any.
+    static RUNNING_SYSTEM: RefCell<Option<(WorldId, Cow<'static, str>)>> = const { RefCell::new(None) };
+}
+
+/// Forbids structural changes to a [`World`] from the current thread while a system runs on it
+/// with shared access, in debug builds.
+///
+/// Systems that don't have exclusive access to the world must defer their structural changes,
+/// such as spawning entities or inserting components, to [`Commands`](crate::system::Commands),
+/// which keeps the archetypes and tables stable while queries iterate over them. Only unsound
+/// code can break this rule, and [`World::assert_structural_change_allowed`] catches it.
+pub(crate) struct StructuralChangeGuard {
+    #[cfg(debug_assertions)]
+    previous: Option<(WorldId, Cow<'static, str>)>,
+}
+
+impl StructuralChangeGuard {
+    /// Forbids structural changes to `world` from the current thread while the returned guard
+    /// lives, as the system `system_name` runs on it.
+    #[inline]
+    pub(crate) fn new(world: &World, system_name: Cow<'static, str>) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            let previous =
+                RUNNING_SYSTEM.with(|running| running.replace(Some((world.id(), system_name))));
+            Self { previous }
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            let _ = (world, system_name);
+            Self {}
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Drop for StructuralChangeGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        RUNNING_SYSTEM.with(|running| *running.borrow_mut() = previous);
+    }
+}
+
+impl World {
+    /// Panics, in debug builds, if a system with shared access to this world runs on the current
+    /// thread, as a structural change is about to happen.
+    #[inline]
+    pub(crate) fn assert_structural_change_allowed(&self) {
+        #[cfg(debug_assertions)]
+        RUNNING_SYSTEM.with(|running| {
+            if let Some((id, name)) = &*running.borrow() {
+                assert!(
+                    *id != self.id(),
+                    "The system {} changed the structure of the world while it ran. Systems \
+                    without exclusive world access must defer structural changes with `Commands`.",
+                    name
+                );
+            }
+        });
+    }
+}
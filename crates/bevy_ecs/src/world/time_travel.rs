@@ -0,0 +1,206 @@
+use crate::{
+    change_detection::Mut,
+    world::{ResourceSnapshot, World, WorldSnapshot},
+};
+use bevy_reflect::TypeRegistryArc;
+use std::collections::VecDeque;
+
+/// A developer tool that captures the state of a [`World`] every frame, so that it can be
+/// stepped back to an earlier frame.
+///
+/// Each capture is a [`World::snapshot`] of the entities along with a
+/// [`World::snapshot_resources`] of the resources, so only the component and resource types
+/// registered in the type registry are captured and restored. At most
+/// [`depth`](TimeTravel::depth) captures are kept: older ones are dropped as new ones are taken,
+/// which bounds the memory used.
+///
+/// Add [`capture_time_travel`] as an exclusive system at the end of the frame, and call
+/// [`TimeTravel::rewind`] from an exclusive system or a console command to go back.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, reflect::ReflectResource, world::{capture_time_travel, TimeTravel}};
+/// # use bevy_reflect::{Reflect, TypeRegistryArc};
+/// #[derive(Reflect, Default)]
+/// #[reflect(Resource)]
+/// struct Score(u32);
+///
+/// let type_registry = TypeRegistryArc::default();
+/// type_registry.write().register::<Score>();
+///
+/// let mut world = World::new();
+/// world.insert_resource(Score(0));
+/// world.insert_resource(TimeTravel::new(type_registry, 60));
+///
+/// let mut stage = SystemStage::single_threaded()
+///     .with_system(|mut score: ResMut<Score>| score.0 += 1)
+///     .with_system(capture_time_travel.exclusive_system().at_end());
+/// for _ in 0..3 {
+///     stage.run(&mut world);
+/// }
+///
+/// world.resource_scope(|world, mut time_travel: Mut<TimeTravel>| {
+///     assert!(time_travel.rewind(world, 2));
+/// });
+/// assert_eq!(world.resource::<Score>().0, 2);
+/// ```
+pub struct TimeTravel {
+    type_registry: TypeRegistryArc,
+    depth: usize,
+    enabled: bool,
+    captures: VecDeque<(WorldSnapshot, ResourceSnapshot)>,
+}
+
+impl TimeTravel {
+    /// Creates an enabled [`TimeTravel`] keeping up to `depth` captures of the types registered in
+    /// `type_registry`.
+    pub fn new(type_registry: TypeRegistryArc, depth: usize) -> Self {
+        TimeTravel {
+            type_registry,
+            depth,
+            enabled: true,
+            captures: VecDeque::with_capacity(depth),
+        }
+    }
+
+    /// Returns the maximum number of captures kept.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Changes the maximum number of captures kept, dropping the oldest ones if there are more.
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+        while self.captures.len() > depth {
+            self.captures.pop_front();
+        }
+    }
+
+    /// Returns `true` if [`TimeTravel::capture`] takes captures.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables captures. Captures that were already taken are kept.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns the number of captures currently kept.
+    pub fn len(&self) -> usize {
+        self.captures.len()
+    }
+
+    /// Returns `true` if no captures are kept.
+    pub fn is_empty(&self) -> bool {
+        self.captures.is_empty()
+    }
+
+    /// Drops all captures.
+    pub fn clear(&mut self) {
+        self.captures.clear();
+    }
+
+    /// Captures the current state of `world`, if enabled, dropping the oldest capture if there
+    /// are already [`depth`](TimeTravel::depth) of them.
+    pub fn capture(&mut self, world: &World) {
+        if !self.enabled || self.depth == 0 {
+            return;
+        }
+        if self.captures.len() == self.depth {
+            self.captures.pop_front();
+        }
+        let type_registry = self.type_registry.read();
+        self.captures.push_back((
+            world.snapshot(&type_registry),
+            world.snapshot_resources(&type_registry),
+        ));
+    }
+
+    /// Restores `world` to the state of the capture taken `frames` captures ago, where `1` is
+    /// the latest capture, and returns `true`.
+    ///
+    /// The captures taken after the restored one are dropped, so rewinding again goes further
+    /// back. Returns `false` and leaves `world` unchanged if `frames` is `0` or there are fewer
+    /// captures.
+    pub fn rewind(&mut self, world: &mut World, frames: usize) -> bool {
+        if frames == 0 || frames > self.captures.len() {
+            return false;
+        }
+        let index = self.captures.len() - frames;
+        self.captures.truncate(index + 1);
+        let (snapshot, resources) = &self.captures[index];
+        world.restore(snapshot);
+        world.restore_resources(resources);
+        true
+    }
+}
+
+/// Captures the state of the world into its [`TimeTravel`] resource, if it has one.
+///
+/// This is an exclusive system, which should run once per frame, usually at the end.
+pub fn capture_time_travel(world: &mut World) {
+    if world.contains_resource::<TimeTravel>() {
+        world.resource_scope(|world, mut time_travel: Mut<TimeTravel>| {
+            time_travel.capture(world);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{capture_time_travel, TimeTravel};
+    use crate::{
+        self as bevy_ecs,
+        change_detection::Mut,
+        component::Component,
+        reflect::{ReflectComponent, ReflectResource},
+        world::World,
+    };
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Frame(u32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Position(u32);
+
+    #[test]
+    fn rewind_frames() {
+        let type_registry = TypeRegistryArc::default();
+        type_registry.write().register::<Frame>();
+        type_registry.write().register::<Position>();
+
+        let mut world = World::new();
+        world.insert_resource(Frame(0));
+        world.insert_resource(TimeTravel::new(type_registry, 2));
+        let entity = world.spawn().insert(Position(0)).id();
+
+        for frame in 1..=3 {
+            world.resource_mut::<Frame>().0 = frame;
+            world.get_mut::<Position>(entity).unwrap().0 = frame * 10;
+            capture_time_travel(&mut world);
+        }
+        // Only the two latest captures are kept.
+        assert_eq!(world.resource::<TimeTravel>().len(), 2);
+
+        world.resource_mut::<Frame>().0 = 4;
+        world.despawn(entity);
+        let spawned = world.spawn().insert(Position(40)).id();
+
+        world.resource_scope(|world, mut time_travel: Mut<TimeTravel>| {
+            assert!(!time_travel.rewind(world, 3));
+            assert!(time_travel.rewind(world, 2));
+            assert_eq!(time_travel.len(), 1);
+        });
+        assert_eq!(world.resource::<Frame>(), &Frame(2));
+        assert_eq!(world.get::<Position>(entity), Some(&Position(20)));
+        assert!(world.get_entity(spawned).is_none());
+
+        // Disabled time travel keeps its captures but doesn't take new ones.
+        world.resource_mut::<TimeTravel>().set_enabled(false);
+        capture_time_travel(&mut world);
+        assert_eq!(world.resource::<TimeTravel>().len(), 1);
+    }
+}
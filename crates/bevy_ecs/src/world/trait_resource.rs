@@ -0,0 +1,188 @@
+use crate::{system::Resource, world::World};
+use std::{any::TypeId, fmt};
+
+type Getter<T> = Box<dyn for<'w> Fn(&'w World) -> Option<&'w T> + Send + Sync>;
+
+/// A registry of the resource types that can be read as the trait object `T`, such as
+/// `dyn ConfigProvider`.
+///
+/// Resource types are registered with [`World::register_resource_trait`], which stores this
+/// registry as a resource of the [`World`]. [`World::resource_by_trait`] then fetches whichever
+/// registered resource is present, without knowing its concrete type. This is useful for plugins
+/// that accept one of several resources implementing a common trait.
+pub struct TraitResources<T: ?Sized + 'static> {
+    getters: Vec<(TypeId, &'static str, Getter<T>)>,
+}
+
+impl<T: ?Sized + 'static> Default for TraitResources<T> {
+    fn default() -> Self {
+        TraitResources {
+            getters: Vec::new(),
+        }
+    }
+}
+
+impl<T: ?Sized + 'static> TraitResources<T> {
+    /// Registers the resource type `R`, which is read as `T` with `cast`.
+    ///
+    /// Registering `R` again replaces its previous `cast`.
+    pub fn register<R: Resource>(&mut self, cast: fn(&R) -> &T) -> &mut Self {
+        let getter: Getter<T> = Box::new(move |world| world.get_resource::<R>().map(cast));
+        let type_id = TypeId::of::<R>();
+        match self.getters.iter_mut().find(|(id, ..)| *id == type_id) {
+            Some((.., existing)) => *existing = getter,
+            None => self
+                .getters
+                .push((type_id, std::any::type_name::<R>(), getter)),
+        }
+        self
+    }
+}
+
+/// An error that occurs when fetching a resource with [`World::resource_by_trait`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum TraitResourceError {
+    /// None of the resource types registered for the trait are present in the [`World`].
+    NotFound,
+    /// Several resource types registered for the trait are present in the [`World`].
+    Ambiguous { type_names: Vec<&'static str> },
+}
+
+impl std::error::Error for TraitResourceError {}
+
+impl fmt::Display for TraitResourceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TraitResourceError::NotFound => {
+                write!(f, "no resource implementing the trait exists in the world")
+            }
+            TraitResourceError::Ambiguous { type_names } => write!(
+                f,
+                "several resources implementing the trait exist in the world: {}",
+                type_names.join(", ")
+            ),
+        }
+    }
+}
+
+impl World {
+    /// Registers the resource type `R` as readable through the trait object `T` with
+    /// [`World::resource_by_trait`], using `cast` to convert a reference to `R`.
+    ///
+    /// ```
+    /// use bevy_ecs::world::World;
+    ///
+    /// trait ConfigProvider: Send + Sync {
+    ///     fn window_title(&self) -> &str;
+    /// }
+    ///
+    /// struct GameConfig;
+    ///
+    /// impl ConfigProvider for GameConfig {
+    ///     fn window_title(&self) -> &str {
+    ///         "My Game"
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.register_resource_trait::<GameConfig, dyn ConfigProvider>(|config| config);
+    /// world.insert_resource(GameConfig);
+    ///
+    /// let config = world.resource_by_trait::<dyn ConfigProvider>().unwrap();
+    /// assert_eq!(config.window_title(), "My Game");
+    /// ```
+    pub fn register_resource_trait<R: Resource, T: ?Sized + 'static>(
+        &mut self,
+        cast: fn(&R) -> &T,
+    ) {
+        self.get_resource_or_insert_with(TraitResources::<T>::default)
+            .register(cast);
+    }
+
+    /// Returns the resource registered for the trait object `T` with
+    /// [`World::register_resource_trait`].
+    ///
+    /// Exactly one of the registered resource types must be present in the [`World`];
+    /// otherwise a [`TraitResourceError`] is returned.
+    pub fn resource_by_trait<T: ?Sized + 'static>(&self) -> Result<&T, TraitResourceError> {
+        let registry = self
+            .get_resource::<TraitResources<T>>()
+            .ok_or(TraitResourceError::NotFound)?;
+        let mut found = None;
+        let mut type_names = Vec::new();
+        for (_, type_name, getter) in &registry.getters {
+            if let Some(resource) = getter(self) {
+                found = Some(resource);
+                type_names.push(*type_name);
+            }
+        }
+        match (found, type_names.len()) {
+            (Some(resource), 1) => Ok(resource),
+            (None, _) => Err(TraitResourceError::NotFound),
+            _ => Err(TraitResourceError::Ambiguous { type_names }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TraitResourceError;
+    use crate::world::World;
+
+    trait ConfigProvider: Send + Sync {
+        fn volume(&self) -> f32;
+    }
+
+    struct DesktopConfig;
+    struct MobileConfig(f32);
+
+    impl ConfigProvider for DesktopConfig {
+        fn volume(&self) -> f32 {
+            1.0
+        }
+    }
+
+    impl ConfigProvider for MobileConfig {
+        fn volume(&self) -> f32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn resource_by_trait() {
+        let mut world = World::new();
+        assert!(matches!(
+            world.resource_by_trait::<dyn ConfigProvider>(),
+            Err(TraitResourceError::NotFound)
+        ));
+
+        world.register_resource_trait::<DesktopConfig, dyn ConfigProvider>(|config| config);
+        world.register_resource_trait::<MobileConfig, dyn ConfigProvider>(|config| config);
+        assert!(matches!(
+            world.resource_by_trait::<dyn ConfigProvider>(),
+            Err(TraitResourceError::NotFound)
+        ));
+
+        world.insert_resource(MobileConfig(0.5));
+        let config = world.resource_by_trait::<dyn ConfigProvider>().unwrap();
+        assert_eq!(config.volume(), 0.5);
+
+        world.insert_resource(DesktopConfig);
+        assert!(matches!(
+            world.resource_by_trait::<dyn ConfigProvider>(),
+            Err(TraitResourceError::Ambiguous { type_names }) if type_names.len() == 2
+        ));
+    }
+
+    #[test]
+    fn register_resource_trait_twice() {
+        let mut world = World::new();
+        world.register_resource_trait::<MobileConfig, dyn ConfigProvider>(|config| config);
+        world.register_resource_trait::<MobileConfig, dyn ConfigProvider>(|config| config);
+        world.insert_resource(MobileConfig(0.5));
+
+        // The second registration replaced the first, so a single resource is found.
+        let config = world.resource_by_trait::<dyn ConfigProvider>().unwrap();
+        assert_eq!(config.volume(), 0.5);
+    }
+}
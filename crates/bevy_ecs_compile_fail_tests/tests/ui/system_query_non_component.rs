@@ -0,0 +1,12 @@
+use bevy_ecs::prelude::*;
+
+// `T` isn't bounded by `Component`, so it can't be requested in a `Query`.
+fn system<T: Send + Sync + 'static>(_query: Query<&T>) {}
+
+fn add_system<T: Send + Sync + 'static>(stage: &mut SystemStage) {
+    stage.add_system(system::<T>);
+}
+
+fn main() {
+    add_system::<u32>(&mut SystemStage::parallel());
+}
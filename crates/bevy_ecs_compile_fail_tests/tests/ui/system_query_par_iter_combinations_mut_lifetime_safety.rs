@@ -0,0 +1,16 @@
+use bevy_ecs::prelude::*;
+use std::sync::Mutex;
+
+#[derive(Component)]
+struct A(usize);
+
+fn system(mut query: Query<&mut A>) {
+    let results = Mutex::new(Vec::new());
+    query.par_iter_combinations_mut(1, |[a, b]| {
+        // this should fail to compile
+        results.lock().unwrap().push(a);
+        results.lock().unwrap().push(b);
+    });
+}
+
+fn main() {}
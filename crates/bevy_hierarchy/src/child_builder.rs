@@ -69,6 +69,11 @@ fn update_old_parents(world: &mut World, parent: Entity, children: &[Entity]) {
 fn remove_children(parent: Entity, children: &[Entity], world: &mut World) {
     let mut events: SmallVec<[HierarchyEvent; 8]> = SmallVec::new();
     for child in children {
+        // only detach children that actually belong to `parent`, so that a child of another
+        // entity keeps its `Parent` in sync with that entity's `Children`
+        if world.get::<Parent>(*child).map(Parent::get) != Some(parent) {
+            continue;
+        }
         world.entity_mut(*child).remove::<Parent>();
         events.push(HierarchyEvent::ChildRemoved {
             child: *child,
@@ -666,6 +671,59 @@ mod tests {
         assert!(world.get::<Parent>(child4).is_none());
     }
 
+    #[test]
+    fn push_children_moves_child_between_parents() {
+        let mut world = World::default();
+        let entities = world
+            .spawn_batch(vec![(C(1),), (C(2),), (C(3),)])
+            .collect::<Vec<Entity>>();
+        let (parent1, parent2, child) = (entities[0], entities[1], entities[2]);
+
+        let mut queue = CommandQueue::default();
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(parent1).push_children(&[child]);
+        }
+        queue.apply(&mut world);
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(parent2).push_children(&[child]);
+        }
+        queue.apply(&mut world);
+
+        assert_eq!(*world.get::<Parent>(child).unwrap(), Parent(parent2));
+        assert_eq!(
+            world.get::<Children>(parent2).unwrap().0.as_slice(),
+            &[child]
+        );
+        assert!(world.get::<Children>(parent1).is_none());
+    }
+
+    #[test]
+    fn remove_children_of_other_parent() {
+        let mut world = World::default();
+        let entities = world
+            .spawn_batch(vec![(C(1),), (C(2),), (C(3),)])
+            .collect::<Vec<Entity>>();
+        let (parent1, parent2, child) = (entities[0], entities[1], entities[2]);
+
+        world.entity_mut(parent1).push_children(&[child]);
+
+        let mut queue = CommandQueue::default();
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(parent2).remove_children(&[child]);
+        }
+        queue.apply(&mut world);
+        world.entity_mut(parent2).remove_children(&[child]);
+
+        assert_eq!(*world.get::<Parent>(child).unwrap(), Parent(parent1));
+        assert_eq!(
+            world.get::<Children>(parent1).unwrap().0.as_slice(),
+            &[child]
+        );
+    }
+
     #[test]
     fn regression_push_children_same_archetype() {
         let mut world = World::new();
@@ -0,0 +1,215 @@
+use crate::{
+    prelude::{Children, Parent},
+    HierarchyEvent,
+};
+use bevy_ecs::{
+    component::Component,
+    entity::{Entities, Entity},
+    event::Events,
+    system::{Command, Commands, Local, Query},
+    world::World,
+};
+use bevy_utils::HashMap;
+use smallvec::SmallVec;
+
+/// What [`fix_orphans`] does with this entity when its parent is despawned without it.
+///
+/// Entities without this component use [`OrphanPolicy::Reparent`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrphanPolicy {
+    /// Moves the entity to the closest ancestor of its parent that is still alive. If there is
+    /// none, the entity loses its [`Parent`] and becomes a root.
+    #[default]
+    Reparent,
+    /// Despawns the entity. Its own children are then orphans, and follow their own policy.
+    Despawn,
+}
+
+/// Finds the entities whose [`Parent`] was despawned, and applies their [`OrphanPolicy`].
+///
+/// Despawning an entity with [`despawn_recursive`](crate::DespawnRecursiveExt::despawn_recursive)
+/// despawns its children too, but a plain despawn leaves them with a [`Parent`] that refers to an
+/// entity that doesn't exist anymore. This system is a safety net against these dangling
+/// references.
+///
+/// To find the ancestors of despawned parents, the system remembers the [`Parent`] of every
+/// entity at its previous run, so it should run regularly, for example once per frame. The
+/// fixes are applied with [`Commands`], at the end of the stage.
+pub fn fix_orphans(
+    mut commands: Commands,
+    entities: &Entities,
+    children: Query<(Entity, &Parent, Option<&OrphanPolicy>)>,
+    mut known_parents: Local<HashMap<Entity, Entity>>,
+) {
+    let mut parents = HashMap::with_capacity_and_hasher(known_parents.len(), Default::default());
+    for (entity, parent, policy) in children.iter() {
+        let parent = parent.get();
+        if entities.contains(parent) {
+            parents.insert(entity, parent);
+            continue;
+        }
+
+        let mut ancestor = parent;
+        let mut new_parent = None;
+        // Bounded, in case the remembered parents form a cycle.
+        for _ in 0..known_parents.len() {
+            match known_parents.get(&ancestor) {
+                Some(&next) if entities.contains(next) => {
+                    new_parent = Some(next);
+                    break;
+                }
+                Some(&next) => ancestor = next,
+                None => break,
+            }
+        }
+        if let Some(new_parent) = new_parent {
+            // Remember where the orphan's descendants should go if it is despawned.
+            parents.insert(entity, new_parent);
+        }
+
+        match policy.copied().unwrap_or_default() {
+            OrphanPolicy::Reparent => commands.add(ReparentOrphan {
+                child: entity,
+                previous_parent: parent,
+                new_parent,
+            }),
+            OrphanPolicy::Despawn => commands.entity(entity).despawn(),
+        }
+    }
+    *known_parents = parents;
+}
+
+/// Command that moves a child whose parent was despawned to a new parent, or makes it a root
+///
+/// Despawned entities are also removed from the [`Children`] of the new parent.
+#[derive(Debug)]
+pub struct ReparentOrphan {
+    /// The orphan to move
+    pub child: Entity,
+    /// The despawned parent of the orphan
+    pub previous_parent: Entity,
+    /// The new parent of the orphan, if any
+    pub new_parent: Option<Entity>,
+}
+
+impl Command for ReparentOrphan {
+    fn write(self, world: &mut World) {
+        let new_parent = self
+            .new_parent
+            .filter(|new_parent| world.get_entity(*new_parent).is_some());
+        let mut child = match world.get_entity_mut(self.child) {
+            Some(child) => child,
+            None => return,
+        };
+        if child.get::<Parent>().map(Parent::get) != Some(self.previous_parent) {
+            // The orphan was already dealt with.
+            return;
+        }
+
+        let event = if let Some(new_parent) = new_parent {
+            child.insert(Parent(new_parent));
+            // The new parent may still list despawned children, such as the previous parent.
+            let mut children: SmallVec<[Entity; 8]> = world
+                .get::<Children>(new_parent)
+                .map(|children| {
+                    children
+                        .iter()
+                        .copied()
+                        .filter(|entity| world.get_entity(*entity).is_some())
+                        .collect()
+                })
+                .unwrap_or_default();
+            if !children.contains(&self.child) {
+                children.push(self.child);
+            }
+            world.entity_mut(new_parent).insert(Children(children));
+            HierarchyEvent::ChildMoved {
+                child: self.child,
+                previous_parent: self.previous_parent,
+                new_parent,
+            }
+        } else {
+            child.remove::<Parent>();
+            HierarchyEvent::ChildRemoved {
+                child: self.child,
+                parent: self.previous_parent,
+            }
+        };
+        if let Some(mut events) = world.get_resource_mut::<Events<HierarchyEvent>>() {
+            events.send(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        component::Component,
+        schedule::{Stage, SystemStage},
+        world::World,
+    };
+
+    use super::{fix_orphans, OrphanPolicy};
+    use crate::{
+        child_builder::BuildWorldChildren,
+        components::{Children, Parent},
+    };
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[test]
+    fn reparent_orphans() {
+        let mut world = World::new();
+        let grandparent = world.spawn().id();
+        let parent = world.spawn().id();
+        let child = world.spawn().id();
+        let grandchild = world.spawn().insert(Marker).id();
+        world.entity_mut(grandparent).push_children(&[parent]);
+        world.entity_mut(parent).push_children(&[child]);
+        world.entity_mut(child).push_children(&[grandchild]);
+
+        let mut stage = SystemStage::single_threaded().with_system(fix_orphans);
+        stage.run(&mut world);
+
+        world.despawn(parent);
+        stage.run(&mut world);
+        assert_eq!(world.get::<Parent>(child).unwrap().get(), grandparent);
+        assert_eq!(&**world.get::<Children>(grandparent).unwrap(), &[child]);
+        assert_eq!(world.get::<Parent>(grandchild).unwrap().get(), child);
+
+        // Without a living ancestor, orphans become roots.
+        world.despawn(grandparent);
+        stage.run(&mut world);
+        assert!(world.get::<Parent>(child).is_none());
+        assert!(world.get::<Marker>(grandchild).is_some());
+    }
+
+    #[test]
+    fn despawn_orphans() {
+        let mut world = World::new();
+        let grandparent = world.spawn().id();
+        let parent = world.spawn().id();
+        let child = world.spawn().insert(OrphanPolicy::Despawn).id();
+        let grandchild = world.spawn().id();
+        world.entity_mut(grandparent).push_children(&[parent]);
+        world.entity_mut(parent).push_children(&[child]);
+        world.entity_mut(child).push_children(&[grandchild]);
+
+        let mut stage = SystemStage::single_threaded().with_system(fix_orphans);
+        stage.run(&mut world);
+
+        world.despawn(parent);
+        stage.run(&mut world);
+        assert!(world.get_entity(child).is_none());
+
+        // The despawned orphan's own children follow their policy, and climb to the closest
+        // living ancestor.
+        stage.run(&mut world);
+        assert_eq!(world.get::<Parent>(grandchild).unwrap().get(), grandparent);
+        assert_eq!(
+            &**world.get::<Children>(grandparent).unwrap(),
+            &[grandchild]
+        );
+    }
+}
@@ -40,6 +40,14 @@ impl<T> Task<T> {
     pub async fn cancel(self) -> Option<T> {
         self.0.cancel().await
     }
+
+    /// Polls the task once without blocking, and returns its output if it has completed.
+    ///
+    /// This lets code that runs repeatedly, like a system, check on a task without waiting for
+    /// it. The task must not be polled again once it has returned its output.
+    pub fn poll_once(&mut self) -> Option<T> {
+        futures_lite::future::block_on(futures_lite::future::poll_once(self))
+    }
 }
 
 impl<T> Future for Task<T> {
@@ -326,6 +326,29 @@ mod tests {
         assert_eq!(count.load(Ordering::Relaxed), 100);
     }
 
+    #[test]
+    fn test_poll_task_across_frames() {
+        let pool = TaskPool::new();
+        let (sender, receiver) = async_channel::bounded(1);
+        let mut task = pool.spawn(async move { receiver.recv().await.unwrap() * 2 });
+
+        // The task can't complete until the value is sent, so it is still pending this frame.
+        assert_eq!(future::block_on(future::poll_once(&mut task)), None);
+
+        sender.try_send(21).unwrap();
+        // Poll once per frame until the result is available.
+        let mut frames = 0;
+        let result = loop {
+            if let Some(result) = future::block_on(future::poll_once(&mut task)) {
+                break result;
+            }
+            frames += 1;
+            assert!(frames < 10_000, "the task never completed");
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        };
+        assert_eq!(result, 42);
+    }
+
     #[test]
     fn test_mixed_spawn_local_and_spawn() {
         let pool = TaskPool::new();
@@ -1,10 +1,12 @@
 mod fixed_timestep;
+mod lifetime;
 mod stopwatch;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
 
 pub use fixed_timestep::*;
+pub use lifetime::*;
 pub use stopwatch::*;
 pub use time::*;
 pub use timer::*;
@@ -12,7 +14,7 @@ pub use timer::*;
 pub mod prelude {
     //! The Bevy Time Prelude.
     #[doc(hidden)]
-    pub use crate::{Time, Timer};
+    pub use crate::{Lifetime, Time, Timer};
 }
 
 use bevy_app::prelude::*;
@@ -32,6 +34,7 @@ impl Plugin for TimePlugin {
         app.init_resource::<Time>()
             .init_resource::<FixedTimesteps>()
             .register_type::<Timer>()
+            .register_type::<Lifetime>()
             // time system is added as an "exclusive system" to ensure it runs before other systems
             // in CoreStage::First
             .add_system_to_stage(
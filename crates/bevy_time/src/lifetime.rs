@@ -0,0 +1,102 @@
+use crate::Time;
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_utils::Duration;
+
+/// A component that despawns its entity once `remaining` runs out, for temporary entities such as
+/// effects or projectiles.
+///
+/// The countdown only runs if [`tick_lifetimes`] is added to the app.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_time::Lifetime;
+/// # use bevy_utils::Duration;
+/// fn spawn_spark(mut commands: Commands) {
+///     commands.spawn().insert(Lifetime::new(Duration::from_millis(300)));
+/// }
+/// # bevy_ecs::system::assert_is_system(spawn_spark);
+/// ```
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct Lifetime {
+    /// The time left before the entity is despawned.
+    pub remaining: Duration,
+}
+
+impl Lifetime {
+    /// Creates a lifetime with `remaining` time left.
+    pub fn new(remaining: Duration) -> Self {
+        Self { remaining }
+    }
+
+    /// Creates a lifetime with `remaining` seconds left.
+    pub fn from_seconds(remaining: f32) -> Self {
+        Self::new(Duration::from_secs_f32(remaining))
+    }
+}
+
+/// Counts the [`Lifetime`] of every entity down by [`Time::delta`], and despawns the entities
+/// whose lifetime ran out.
+///
+/// Despawns go through [`Commands`], so they take effect once the stage's commands are applied:
+/// an entity spawned with a zero lifetime still exists until this system next runs.
+pub fn tick_lifetimes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Lifetime)>,
+) {
+    for (entity, mut lifetime) in query.iter_mut() {
+        lifetime.remaining = lifetime.remaining.saturating_sub(time.delta());
+        if lifetime.remaining.is_zero() {
+            commands.entity(entity).despawn_if_exists();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tick_lifetimes, Lifetime};
+    use crate::Time;
+    use bevy_ecs::{prelude::*, schedule::Stage};
+    use bevy_utils::{Duration, Instant};
+
+    #[test]
+    fn despawn_expired_entities() {
+        let mut world = World::new();
+        let start = Instant::now();
+        world.insert_resource(Time::default());
+        let mut stage = SystemStage::single_threaded().with_system(tick_lifetimes);
+        let mut frame = |world: &mut World, frame: u32| {
+            world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_millis(100) * frame);
+            stage.run(world);
+        };
+
+        let short = world
+            .spawn()
+            .insert(Lifetime::new(Duration::from_millis(250)))
+            .id();
+        let expired = world.spawn().insert(Lifetime::default()).id();
+        let forever = world.spawn().id();
+
+        // The first frame has no delta, but despawns entities without time left.
+        frame(&mut world, 0);
+        assert!(world.get_entity(expired).is_none());
+        assert_eq!(
+            world.get::<Lifetime>(short).unwrap().remaining,
+            Duration::from_millis(250)
+        );
+
+        frame(&mut world, 1);
+        frame(&mut world, 2);
+        assert_eq!(
+            world.get::<Lifetime>(short).unwrap().remaining,
+            Duration::from_millis(50)
+        );
+        frame(&mut world, 3);
+        assert!(world.get_entity(short).is_none());
+        assert!(world.get_entity(forever).is_some());
+    }
+}
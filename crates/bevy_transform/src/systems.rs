@@ -190,6 +190,44 @@ mod test {
         );
     }
 
+    #[test]
+    fn skips_unchanged_subtrees() {
+        let mut world = World::default();
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(transform_propagate_system);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+
+        let mut child = Entity::from_raw(0);
+        let parent = world
+            .spawn()
+            .insert_bundle(TransformBundle::from(Transform::from_xyz(1.0, 0.0, 0.0)))
+            .with_children(|parent| {
+                child = parent
+                    .spawn_bundle(TransformBundle::from(Transform::from_xyz(0.0, 2.0, 0.0)))
+                    .id();
+            })
+            .id();
+        schedule.run(&mut world);
+
+        // Overwrite the child's `GlobalTransform` without touching any `Transform`.
+        // Since nothing above it changed, propagation must leave it alone.
+        let sentinel = GlobalTransform::from_xyz(9.0, 9.0, 9.0);
+        *world.get_mut::<GlobalTransform>(child).unwrap() = sentinel;
+        schedule.run(&mut world);
+        assert_eq!(*world.get::<GlobalTransform>(child).unwrap(), sentinel);
+
+        // Moving the parent moves the child.
+        world.get_mut::<Transform>(parent).unwrap().translation.x = 3.0;
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get::<GlobalTransform>(child).unwrap(),
+            GlobalTransform::from_xyz(3.0, 0.0, 0.0) * Transform::from_xyz(0.0, 2.0, 0.0)
+        );
+    }
+
     #[test]
     fn correct_children() {
         let mut world = World::default();
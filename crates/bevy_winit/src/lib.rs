@@ -578,6 +578,9 @@ pub fn winit_runner_with(mut app: App) {
                     delta: Vec2::new(delta.0 as f32, delta.1 as f32),
                 });
             }
+            event::Event::LoopDestroyed => {
+                app.shutdown();
+            }
             event::Event::Suspended => {
                 winit_state.active = false;
             }